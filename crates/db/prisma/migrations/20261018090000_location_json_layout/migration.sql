@@ -0,0 +1,2 @@
+-- AlterTable
+ALTER TABLE "Location" ADD COLUMN "json_layout" TEXT NOT NULL DEFAULT 'flat';
@@ -0,0 +1,6 @@
+-- AlterTable
+ALTER TABLE "Location" ADD COLUMN "messages_file" TEXT NOT NULL DEFAULT 'messages.ts';
+ALTER TABLE "Location" ADD COLUMN "locales_directory" TEXT NOT NULL DEFAULT 'locales';
+ALTER TABLE "Location" ADD COLUMN "locale_file_pattern" TEXT NOT NULL DEFAULT '{lang}.json';
+ALTER TABLE "Location" ADD COLUMN "languages_file" TEXT NOT NULL DEFAULT 'locales/locales.ts';
+ALTER TABLE "Location" ADD COLUMN "source_language" TEXT;
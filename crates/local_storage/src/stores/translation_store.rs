@@ -25,7 +25,7 @@ pub struct TranslationStoreState {
     pub translation_entries: Vec<TranslationEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
 pub struct TranslationEntry {
     #[serde(default)]
     pub key: String,
@@ -33,8 +33,44 @@ pub struct TranslationEntry {
     pub value: String,
     #[serde(default)]
     pub translations: HashMap<String, String>,
+    /// Whether the code references the key
     #[serde(default)]
     pub in_use: bool,
+    /// Not referenced directly but reachable through a computed access such as
+    /// `messages[key]`, so it must not be removed as unused
+    #[serde(default)]
+    pub possibly_used: bool,
+    /// Places in the code that reference the key
+    #[serde(default)]
+    pub usages: Vec<SourceLocation>,
+    /// Plural variants per language, keyed by CLDR category (`one`, `few`, `other`...)
+    #[serde(default)]
+    pub plurals: HashMap<String, HashMap<String, String>>,
+    /// CLDR categories a language needs but has no variant for
+    #[serde(default)]
+    pub missing_plural_forms: HashMap<String, Vec<String>>,
+    /// Note for translators that the file keeps with the key
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Further metadata of the key such as ARB placeholders, keyed by dotted field path
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Value every language resolves to at runtime, its own or one inherited from a parent
+    /// culture
+    #[serde(default)]
+    pub effective_values: HashMap<String, String>,
+    /// Languages without a value of their own, with the language their effective value comes
+    /// from (`de` for `de-AT`, `default` for the neutral file)
+    #[serde(default)]
+    pub fallbacks: HashMap<String, String>,
+}
+
+/// A position in a source file, with the path relative to the folder that was searched.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Type)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
 }
 
 impl TranslationEntry {
@@ -44,6 +80,7 @@ impl TranslationEntry {
             value: value.to_string(),
             translations,
             in_use,
+            ..Default::default()
         }
     }
 }
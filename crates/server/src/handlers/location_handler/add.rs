@@ -1,33 +1,74 @@
 use crate::handlers::location_handler::types::ScanInput;
+use crate::handlers::location_handler::update::layout_params;
+use crate::handlers::translation_handler::formats;
 use chrono::Local;
 use db::context::RouterCtx;
 use db::prisma::location::Data;
-use db::prisma::{location, PrismaClient};
+use db::prisma::{location, settings, PrismaClient};
+use local_storage::stores::translation_store::TranslationEntry;
 use prisma_client_rust::QueryError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use translation_handler::backend::discovery::{discover_resources, resource_name};
+use translation_handler::frontend::json::detect_layout;
+use translation_handler::frontend::layout::FrontendLayout;
 use translation_handler::TranslationHandler;
 
-fn sanitize_path(input_path: &str) -> String {
-    input_path
-        .replace("/messages.ts", "")
-        .replace("\\messages.ts", "")
+/// Resolves the location path and layout of a frontend location. Without an explicit layout
+/// the selected messages file decides the location path and the defaults apply around it.
+fn frontend_location(input: &ScanInput) -> Option<(String, FrontendLayout)> {
+    if let Some(layout) = &input.layout {
+        return Some((input.path.clone(), layout.clone()));
+    }
+    if !input.path.ends_with(".ts") {
+        return None;
+    }
+    let messages_file = Path::new(&input.path);
+    let path = messages_file.parent()?.to_string_lossy().to_string();
+    let mut layout = FrontendLayout {
+        messages_file: messages_file.file_name()?.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let locale_files: Vec<PathBuf> = layout
+        .locale_files(&path)
+        .into_iter()
+        .map(|(_, file)| file)
+        .collect();
+    layout.json_layout = detect_layout(&locale_files);
+    Some((path, layout))
 }
 
 pub async fn add_location(ctx: RouterCtx, input: ScanInput) -> Result<Vec<Data>, rspc::Error> {
     let db = &ctx.db;
-    if input.path.contains("messages.ts") {
-        let sanitized_path = sanitize_path(&input.path);
-        let keys = TranslationHandler::get_key_values_from_messages_ts(&sanitized_path).await;
-        let key_value = TranslationHandler::get_frontend_translations(&sanitized_path).await;
-        let untranslated_keys = key_value
-            .into_iter()
-            .filter(|entry| {
-                entry
-                    .translations
-                    .iter()
-                    .all(|(k, v)| k == "en-GB" || v.trim().is_empty())
-            })
-            .count();
+    if input.layout.is_none() {
+        if let Some(tag) = formats().detect(Path::new(&input.path)) {
+            let location = format_location_upsert(db, input.name, input.path, tag).await?;
+            return Ok(vec![location]);
+        }
+    }
+    if let Some((sanitized_path, layout)) = frontend_location(&input) {
+        layout
+            .validate()
+            .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+        let keys = TranslationHandler::get_key_values_from_messages_ts(&sanitized_path, &layout)
+            .await
+            .map_err(|error| {
+                rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+            })?;
+        let key_value = TranslationHandler::get_frontend_translations(&sanitized_path, &layout)
+            .await
+            .map_err(|error| {
+                rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+            })?;
+        let settings = db
+            .settings()
+            .find_unique(settings::id::equals(1))
+            .exec()
+            .await?
+            .unwrap();
+        let untranslated_keys = count_untranslated_keys(
+            &key_value,
+            layout.source_language(&settings.default_language),
+        );
         println!("adding new FE location");
         let location = location_database_upsert(
             db,
@@ -36,6 +77,7 @@ pub async fn add_location(ctx: RouterCtx, input: ScanInput) -> Result<Vec<Data>,
             "FE".to_string(),
             keys.len() as i32,
             untranslated_keys as i32,
+            Some(&layout),
         )
         .await
         .expect("failed to upsert FE location");
@@ -43,47 +85,90 @@ pub async fn add_location(ctx: RouterCtx, input: ScanInput) -> Result<Vec<Data>,
         response.push(location);
         return Ok(response);
     } else {
-        if let Some(resources_paths) =
-            translation_handler::backend::getter::get_resources_from_csproj(
-                input.path.clone().as_str(),
-            )
-        {
-            println!("{:?}", resources_paths);
-            let now = Local::now();
-            let _loc_count = db
-                .location()
-                .create_many(
-                    resources_paths
-                        .iter()
-                        .map(|path| {
-                            let loc_translations = translation_handler::backend::getter::get_translations_from_location(path.clone().as_str());
-                            let full_path = Path::new(path);
-                            let parent = full_path.parent().unwrap().file_name().unwrap().to_string_lossy();
-                            location::create_unchecked(
-                                "BE".to_string(),
-                                parent.to_string(),
-                                path.clone(),
-                                loc_translations.len() as i32,
-                                2,
-                                now.to_string(),
-                                vec![],
-                            )
-                        })
-                        .collect(),
-                )
-                .skip_duplicates()
-                .exec()
-                .await;
-            let locations: Vec<Data> = Vec::new();
-            return Ok(locations);
+        let internal_error = |error: std::io::Error| {
+            rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
         };
-        Err(rspc::Error::new(
-            rspc::ErrorCode::InternalServerError,
-            "error".to_string(),
-        ))
+        let resource_groups = discover_resources(Path::new(&input.path)).map_err(internal_error)?;
+        if resource_groups.is_empty() {
+            return Err(rspc::Error::new(
+                rspc::ErrorCode::BadRequest,
+                format!("no resx files found in {}", input.path),
+            ));
+        }
+        let mut locations = Vec::new();
+        for group in &resource_groups {
+            let name =
+                resource_name(&group.neutral, group.project.as_deref()).map_err(internal_error)?;
+            let path = group.neutral.to_string_lossy().to_string();
+            locations.push(format_location_upsert(db, name, path, "BE").await?);
+        }
+        Ok(locations)
     }
 }
 
+/// Stores a location whose type was recognised from its files, such as an Android `res`
+/// directory or a directory of YAML locale files, with the keys its format counts.
+pub(crate) async fn format_location_upsert(
+    db: &PrismaClient,
+    name: String,
+    path: String,
+    tag: &str,
+) -> Result<Data, rspc::Error> {
+    let location = location_database_upsert(db, name, path, tag.to_string(), 0, 0, None).await?;
+    update_key_counts(db, location).await
+}
+
+/// Counts the keys of a stored location through the format of its tag.
+pub(crate) async fn update_key_counts(
+    db: &PrismaClient,
+    location: Data,
+) -> Result<Data, rspc::Error> {
+    let settings = db
+        .settings()
+        .find_unique(settings::id::equals(1))
+        .exec()
+        .await?
+        .unwrap();
+    let internal_error = |error: std::io::Error| {
+        rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+    };
+    let format = formats().for_location(&location).map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let untranslated_keys = count_untranslated_keys(&entries, &source_language);
+    Ok(db
+        .location()
+        .update(
+            location::id::equals(location.id),
+            vec![
+                location::num_of_keys::set(entries.len() as i32),
+                location::num_of_untranslated_keys::set(untranslated_keys as i32),
+            ],
+        )
+        .exec()
+        .await?)
+}
+
+/// Keys that have no translation besides the one in the source language.
+pub(crate) fn count_untranslated_keys(
+    entries: &[TranslationEntry],
+    source_language: &str,
+) -> usize {
+    entries
+        .iter()
+        .filter(|entry| {
+            entry
+                .translations
+                .iter()
+                .all(|(k, v)| k == source_language || v.trim().is_empty())
+        })
+        .count()
+}
+
+/// Stores a location by its path. The layout columns are only written for frontend locations,
+/// other types keep the column defaults.
 pub async fn location_database_upsert(
     db: &PrismaClient,
     name: String,
@@ -91,8 +176,14 @@ pub async fn location_database_upsert(
     tag: String,
     keys: i32,
     untranslated_keys: i32,
+    layout: Option<&FrontendLayout>,
 ) -> Result<Data, QueryError> {
     let now = Local::now();
+    let mut update_params = vec![
+        location::num_of_keys::set(keys),
+        location::num_of_untranslated_keys::set(untranslated_keys),
+    ];
+    update_params.extend(layout.map(layout_params).unwrap_or_default());
 
     db.location()
         .upsert(
@@ -104,12 +195,9 @@ pub async fn location_database_upsert(
                 keys,
                 untranslated_keys,
                 now.to_string(),
-                vec![],
+                layout.map(layout_params).unwrap_or_default(),
             ),
-            vec![
-                location::num_of_keys::set(keys),
-                location::num_of_untranslated_keys::set(untranslated_keys),
-            ],
+            update_params,
         )
         .exec()
         .await
@@ -6,10 +6,11 @@ mod update;
 
 use self::types::ScanInput;
 use crate::handlers::location_handler::delete::delete_location;
-use crate::handlers::location_handler::types::RescanInput;
+use crate::handlers::location_handler::types::{RescanInput, UpdateLocationInput};
 use add::add_location;
 use db::context::RouterCtx;
 use rescan::rescan_location;
+use update::update_location;
 use rspc::{Router, RouterBuilder};
 use local_storage::stores::location_store::Location;
 
@@ -24,4 +25,7 @@ pub fn get_location_router() -> RouterBuilder<RouterCtx> {
         .mutation("delete_location", |t| {
             t(|ctx, input: Location| delete_location(ctx, input))
         })
+        .mutation("update_location", |t| {
+            t(|ctx, input: UpdateLocationInput| update_location(ctx, input))
+        })
 }
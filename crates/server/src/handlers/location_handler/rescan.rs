@@ -1,38 +1,71 @@
-use crate::handlers::location_handler::add::location_database_upsert;
+use crate::handlers::location_handler::add::{
+    count_untranslated_keys, location_database_upsert, update_key_counts,
+};
 use crate::handlers::location_handler::types::RescanInput;
 use db::context::RouterCtx;
 use db::prisma::location::Data;
+use db::prisma::{location, settings};
+use translation_handler::frontend::layout::FrontendLayout;
 use translation_handler::TranslationHandler;
 
 pub async fn rescan_location(ctx: RouterCtx, input: RescanInput) -> Result<Data, rspc::Error> {
-    //TODO: make this work for backend
     let db = &ctx.db;
-    let keys = TranslationHandler::get_key_values_from_messages_ts(&input.path).await;
-    let key_value = TranslationHandler::get_frontend_translations(&input.path).await;
-
-    let untranslated_keys = key_value
-        .into_iter()
-        .filter(|entry| {
-            entry
-                .translations
-                .iter()
-                .all(|(k, v)| k == "en-GB" || v.trim().is_empty())
-        })
-        .count();
-
-    let (irrelevant_or_new, fe_or_be) = if input.tag == String::from("FE") {
-        (String::from("irrelevant"), input.tag)
-    } else {
-        (String::from("new"), String::from("BE"))
+    // Locations kept in files, backend resx files among them, are counted through their format
+    if input.tag != "FE" {
+        let location = db
+            .location()
+            .find_unique(location::path::equals(input.path.clone()))
+            .exec()
+            .await?
+            .ok_or_else(|| {
+                rspc::Error::new(
+                    rspc::ErrorCode::NotFound,
+                    format!("no location found for {}", input.path),
+                )
+            })?;
+        return update_key_counts(db, location).await;
+    }
+    let layout = match db
+        .location()
+        .find_unique(location::path::equals(input.path.clone()))
+        .exec()
+        .await?
+    {
+        Some(location) => FrontendLayout::from_location(&location).map_err(|error| {
+            rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+        })?,
+        None => FrontendLayout::default(),
     };
+    let keys = TranslationHandler::get_key_values_from_messages_ts(&input.path, &layout)
+        .await
+        .map_err(|error| {
+            rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+        })?;
+    let key_value = TranslationHandler::get_frontend_translations(&input.path, &layout)
+        .await
+        .map_err(|error| {
+            rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
+        })?;
+
+    let settings = db
+        .settings()
+        .find_unique(settings::id::equals(1))
+        .exec()
+        .await?
+        .unwrap();
+    let untranslated_keys = count_untranslated_keys(
+        &key_value,
+        layout.source_language(&settings.default_language),
+    );
 
     let location = location_database_upsert(
         db,
-        irrelevant_or_new,
+        String::from("irrelevant"),
         input.path,
-        fe_or_be,
+        input.tag,
         keys.len() as i32,
         untranslated_keys as i32,
+        Some(&layout),
     )
         .await?;
 
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use translation_handler::frontend::layout::FrontendLayout;
 
 #[derive(Deserialize, Serialize, Type)]
 pub struct ScanResponse {
@@ -11,6 +12,8 @@ pub struct ScanResponse {
 pub struct ScanInput {
     pub(crate) path: String,
     pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) layout: Option<FrontendLayout>,
 }
 
 #[derive(Deserialize, Serialize, Type)]
@@ -18,3 +21,9 @@ pub struct RescanInput {
     pub(crate) path: String,
     pub(crate) tag: String,
 }
+
+#[derive(Deserialize, Serialize, Type)]
+pub struct UpdateLocationInput {
+    pub(crate) path: String,
+    pub(crate) layout: FrontendLayout,
+}
@@ -1 +1,36 @@
+use crate::handlers::location_handler::types::UpdateLocationInput;
+use db::context::RouterCtx;
+use db::prisma::location;
+use db::prisma::location::Data;
+use translation_handler::frontend::layout::FrontendLayout;
 
+pub(crate) fn layout_params(layout: &FrontendLayout) -> Vec<location::SetParam> {
+    vec![
+        location::messages_file::set(layout.messages_file.clone()),
+        location::locales_directory::set(layout.locales_directory.clone()),
+        location::locale_file_pattern::set(layout.locale_file_pattern.clone()),
+        location::languages_file::set(layout.languages_file.clone()),
+        location::source_language::set(layout.source_language.clone()),
+        location::json_layout::set(layout.json_layout.as_str().to_string()),
+    ]
+}
+
+pub async fn update_location(
+    ctx: RouterCtx,
+    input: UpdateLocationInput,
+) -> Result<Data, rspc::Error> {
+    let db = &ctx.db;
+    input
+        .layout
+        .validate()
+        .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+    let location = db
+        .location()
+        .update(
+            location::path::equals(input.path),
+            layout_params(&input.layout),
+        )
+        .exec()
+        .await?;
+    Ok(location)
+}
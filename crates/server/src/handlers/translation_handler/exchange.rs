@@ -0,0 +1,290 @@
+use super::{get_settings, internal_error, location_format};
+use db::context::RouterCtx;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use translation_handler::exchange::gettext::{
+    export_catalog, export_template, imported_values, Catalog,
+};
+use translation_handler::exchange::spreadsheet::{self, export_matrix, read_matrix, write_matrix};
+use translation_handler::exchange::xliff::{self, XliffDocument, XliffVersion};
+use translation_handler::exchange::{apply_import, plan_import, ImportReport};
+
+#[derive(Deserialize, specta::Type)]
+pub struct ExportBody {
+    path: String,
+    /// Directory the exported files are written to
+    directory: String,
+}
+
+#[derive(Deserialize, specta::Type)]
+pub struct ImportBody {
+    path: String,
+    /// The exchange file to import
+    file: String,
+    /// Language of the file, when the file does not name it
+    #[serde(default)]
+    language: Option<String>,
+    /// Only report what would change
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Deserialize, specta::Type)]
+pub struct XliffExportBody {
+    path: String,
+    directory: String,
+    version: XliffVersion,
+    /// Leave out keys that already have a translation
+    #[serde(default)]
+    untranslated_only: bool,
+}
+
+#[derive(Deserialize, specta::Type)]
+pub struct SpreadsheetExportBody {
+    path: String,
+    /// A `.csv` or `.xlsx` file to write
+    file: String,
+}
+
+/// File names of exported files start with the location name, minus characters that are not
+/// allowed in file names.
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    match stem.trim() {
+        "" => "messages".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+/// Rejects an import with values for languages the location has no file for, as those values
+/// would be reported as changed without being written anywhere.
+fn check_languages<'a>(
+    file: &str,
+    known: &[String],
+    languages: impl IntoIterator<Item = &'a str>,
+) -> Result<(), rspc::Error> {
+    let mut unknown_languages: Vec<&str> = languages
+        .into_iter()
+        .filter(|language| !known.iter().any(|known| known == language))
+        .collect();
+    unknown_languages.sort_unstable();
+    unknown_languages.dedup();
+    if unknown_languages.is_empty() {
+        return Ok(());
+    }
+    Err(rspc::Error::new(
+        rspc::ErrorCode::BadRequest,
+        format!(
+            "{} has values for languages the location does not have: {}",
+            file,
+            unknown_languages.join(", ")
+        ),
+    ))
+}
+
+/// Writes a `.pot` template and a `.po` file per language. Existing `.po` files in the
+/// directory count as the previous export.
+pub async fn export_gettext(ctx: RouterCtx, input: ExportBody) -> Result<Vec<String>, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let settings = get_settings(&ctx.db).await?;
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+
+    let directory = Path::new(&input.directory);
+    let stem = file_stem(&location.name);
+    let template_path = directory.join(format!("{}.pot", stem));
+    export_template(&entries, &source_language)
+        .write(&template_path)
+        .map_err(internal_error)?;
+
+    let mut written: Vec<PathBuf> = vec![template_path];
+    for language in languages
+        .iter()
+        .filter(|language| **language != source_language)
+    {
+        let po_path = directory.join(format!("{}.{}.po", stem, language));
+        let previous = if po_path.is_file() {
+            Some(Catalog::read(&po_path).map_err(internal_error)?)
+        } else {
+            None
+        };
+        export_catalog(&entries, &source_language, language, previous.as_ref())
+            .write(&po_path)
+            .map_err(internal_error)?;
+        written.push(po_path);
+    }
+    Ok(written
+        .into_iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+pub async fn import_gettext(
+    ctx: RouterCtx,
+    input: ImportBody,
+) -> Result<ImportReport, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let settings = get_settings(&ctx.db).await?;
+    let catalog = Catalog::read(Path::new(&input.file))
+        .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+    let language = input
+        .language
+        .or_else(|| catalog.language())
+        .ok_or_else(|| {
+            rspc::Error::new(
+                rspc::ErrorCode::BadRequest,
+                format!("{} has no Language header", input.file),
+            )
+        })?;
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+    check_languages(&input.file, &languages, [language.as_str()])?;
+
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let report = plan_import(
+        &entries,
+        &source_language,
+        imported_values(&catalog, &language),
+    );
+    if !input.dry_run {
+        apply_import(format, &location, &settings, &entries, &report.changed)
+            .await
+            .map_err(internal_error)?;
+    }
+    Ok(report)
+}
+
+/// Writes an `.xlf` file per target language.
+pub async fn export_xliff(
+    ctx: RouterCtx,
+    input: XliffExportBody,
+) -> Result<Vec<String>, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let settings = get_settings(&ctx.db).await?;
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+
+    let directory = Path::new(&input.directory);
+    let stem = file_stem(&location.name);
+    let mut written = Vec::new();
+    for language in languages
+        .iter()
+        .filter(|language| **language != source_language)
+    {
+        let xliff_path = directory.join(format!("{}.{}.xlf", stem, language));
+        xliff::export_document(
+            &entries,
+            &location.name,
+            &source_language,
+            language,
+            input.version,
+            input.untranslated_only,
+        )
+        .write(&xliff_path)
+        .map_err(internal_error)?;
+        written.push(xliff_path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Applies the targets of a returned XLIFF file. Units whose id is no longer a key or whose
+/// source text changed since the export are reported and left alone.
+pub async fn import_xliff(ctx: RouterCtx, input: ImportBody) -> Result<ImportReport, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let settings = get_settings(&ctx.db).await?;
+    let document = XliffDocument::read(Path::new(&input.file))
+        .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+    let language = match (input.language, document.target_language.clone()) {
+        (Some(language), Some(target)) if language != target => {
+            return Err(rspc::Error::new(
+                rspc::ErrorCode::BadRequest,
+                format!("{} targets {}, not {}", input.file, target, language),
+            ))
+        }
+        (Some(language), _) | (None, Some(language)) => language,
+        (None, None) => {
+            return Err(rspc::Error::new(
+                rspc::ErrorCode::BadRequest,
+                format!("{} has no target language", input.file),
+            ))
+        }
+    };
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+    check_languages(&input.file, &languages, [language.as_str()])?;
+
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let report = plan_import(
+        &entries,
+        &source_language,
+        xliff::imported_values(&document, &language),
+    );
+    if !input.dry_run {
+        apply_import(format, &location, &settings, &entries, &report.changed)
+            .await
+            .map_err(internal_error)?;
+    }
+    Ok(report)
+}
+
+/// Writes the whole location as one sheet: a row per key and a column per language.
+pub async fn export_spreadsheet(
+    ctx: RouterCtx,
+    input: SpreadsheetExportBody,
+) -> Result<String, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+    let file = Path::new(&input.file);
+    write_matrix(file, &export_matrix(&entries, &languages))
+        .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+    Ok(input.file)
+}
+
+/// Compares an edited sheet with the current files and applies the cells that differ. Cells
+/// that were left as exported are not written back.
+pub async fn import_spreadsheet(
+    ctx: RouterCtx,
+    input: ImportBody,
+) -> Result<ImportReport, rspc::Error> {
+    let (location, format) = location_format(&ctx.db, input.path).await?;
+    let settings = get_settings(&ctx.db).await?;
+    let values = read_matrix(Path::new(&input.file))
+        .and_then(|matrix| spreadsheet::imported_values(&matrix))
+        .map_err(|error| rspc::Error::new(rspc::ErrorCode::BadRequest, error.to_string()))?;
+
+    let languages = format.languages(&location).await.map_err(internal_error)?;
+    check_languages(
+        &input.file,
+        &languages,
+        values.iter().map(|value| value.language.as_str()),
+    )?;
+
+    let source_language = format
+        .source_language(&location, &settings)
+        .map_err(internal_error)?;
+    let entries = format.list(&location).await.map_err(internal_error)?;
+    let report = plan_import(&entries, &source_language, values);
+    if !input.dry_run {
+        apply_import(format, &location, &settings, &entries, &report.changed)
+            .await
+            .map_err(internal_error)?;
+    }
+    Ok(report)
+}
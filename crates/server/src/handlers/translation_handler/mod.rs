@@ -1,31 +1,63 @@
+mod exchange;
+
 use db::context::RouterCtx;
+use exchange::{
+    export_gettext, export_spreadsheet, export_xliff, import_gettext, import_spreadsheet,
+    import_xliff, ExportBody, ImportBody, SpreadsheetExportBody, XliffExportBody,
+};
 use local_storage::stores::translation_store::TranslationEntry;
-use prisma_client_rust::QueryError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::sync::OnceLock;
+use translation_handler::checker::check_entries;
+use translation_handler::format::{FormatRegistry, NewKey, TranslationFormat};
 use translation_handler::frontend::updater::UpdatedKeyValues;
-use translation_handler::TranslationHandler;
+use translation_handler::plural::PluralCategory;
 
 use db::prisma::{location, settings, PrismaClient};
 use rspc::{Router as RspcRouter, RouterBuilder as RspcRouterBuilder};
 
-enum LocationType {
-    Frontend,
-    Backend,
+/// Formats of all location types, keyed by location tag.
+pub(crate) fn formats() -> &'static FormatRegistry {
+    static FORMATS: OnceLock<FormatRegistry> = OnceLock::new();
+    FORMATS.get_or_init(FormatRegistry::default)
+}
+
+async fn find_location(db: &PrismaClient, path: String) -> Result<location::Data, rspc::Error> {
+    db.location()
+        .find_unique(location::path::equals(path.clone()))
+        .exec()
+        .await?
+        .ok_or_else(|| {
+            rspc::Error::new(
+                rspc::ErrorCode::NotFound,
+                format!("no location found for {}", path),
+            )
+        })
 }
-async fn match_location_type(db: &PrismaClient, path: String) -> Result<LocationType, QueryError> {
-    match db
-        .location()
-        .find_unique(location::path::equals(path))
+
+async fn get_settings(db: &PrismaClient) -> Result<settings::Data, rspc::Error> {
+    Ok(db
+        .settings()
+        .find_unique(settings::id::equals(1))
         .exec()
         .await?
-        .unwrap()
-        .tag
-        .as_str()
-    {
-        "FE" => return Ok(LocationType::Frontend),
-        "BE" => return Ok(LocationType::Backend),
-        _ => unreachable!(),
-    }
+        .unwrap())
+}
+
+/// The location at `path` together with the format its files are in.
+async fn location_format(
+    db: &PrismaClient,
+    path: String,
+) -> Result<(location::Data, &'static dyn TranslationFormat), rspc::Error> {
+    let location = find_location(db, path).await?;
+    let format = formats().for_location(&location).map_err(internal_error)?;
+    Ok((location, format))
+}
+
+fn internal_error(error: io::Error) -> rspc::Error {
+    rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
 }
 
 #[derive(Deserialize)]
@@ -56,6 +88,21 @@ pub struct AddNewKeyBody {
     ts_key: String,
     json_key: String,
     value: String,
+    /// Source language plural forms, which replace `value` when given
+    #[serde(default)]
+    plural_values: HashMap<PluralCategory, String>,
+    /// Note for translators, such as where the text is shown
+    #[serde(default)]
+    description: Option<String>,
+    /// Values of other languages, added along with the source value where the format allows
+    #[serde(default)]
+    translations: HashMap<String, String>,
+}
+
+#[derive(Deserialize, specta::Type)]
+pub struct AddLanguageBody {
+    path: String,
+    language: String,
 }
 
 #[derive(Deserialize, specta::Type)]
@@ -68,157 +115,110 @@ pub fn get_translation_router() -> RspcRouterBuilder<RouterCtx> {
     RspcRouter::<RouterCtx>::new()
         .mutation("get_translations", |t| {
             t(|ctx, path: String| async move {
-                match match_location_type(&ctx.db, path.clone())
-                    .await
-                    .expect("failed to find location in database")
-                {
-                    LocationType::Frontend => {
-                        TranslationHandler::get_frontend_translations(&path).await
-                    }
-                    LocationType::Backend => {
-                        TranslationHandler::get_backend_translations(&path).await
-                    }
-                }
+                let (location, format) = location_format(&ctx.db, path).await?;
+                format.list(&location).await.map_err(internal_error)
             })
         })
         .query("get_number_of_keys", |t| {
             t(|ctx, path: String| async move {
-                match match_location_type(&ctx.db, path.clone())
+                let (location, format) = location_format(&ctx.db, path).await?;
+                format
+                    .list(&location)
                     .await
-                    .expect("failed to find location in database")
-                {
-                    LocationType::Frontend => TranslationHandler::get_frontend_translations(&path)
-                        .await
-                        .len() as u32,
-                    LocationType::Backend => TranslationHandler::get_backend_translations(&path)
-                        .await
-                        .len() as u32,
-                }
+                    .map(|keys| keys.len() as u32)
+                    .map_err(internal_error)
+            })
+        })
+        .query("check_placeholders", |t| {
+            t(|ctx, path: String| async move {
+                let (location, format) = location_format(&ctx.db, path).await?;
+                let settings = get_settings(&ctx.db).await?;
+                let source_language = format
+                    .source_language(&location, &settings)
+                    .map_err(internal_error)?;
+                let entries = format.list(&location).await.map_err(internal_error)?;
+                Ok(check_entries(
+                    &entries,
+                    &source_language,
+                    format.message_syntax(),
+                ))
             })
         })
         .query("get_languages", |t| {
-            t(|_ctx, path: String| async move {
-                TranslationHandler::extract_language_codes_from_locales(path)
+            t(|ctx, path: String| async move {
+                let (location, format) = location_format(&ctx.db, path).await?;
+                format.languages(&location).await.map_err(internal_error)
+            })
+        })
+        .mutation("add_language", |t| {
+            t(|ctx, input: AddLanguageBody| async move {
+                let language = input.language.trim();
+                if language.is_empty() || language.contains(['/', '\\', '.']) {
+                    return Err(rspc::Error::new(
+                        rspc::ErrorCode::BadRequest,
+                        format!("`{}` is not a valid language code", input.language),
+                    ));
+                }
+                let (location, format) = location_format(&ctx.db, input.path).await?;
+                format
+                    .add_language(&location, language)
+                    .await
+                    .map_err(internal_error)
             })
         })
         .mutation("add_key", |t| {
             t(|ctx, input: AddNewKeyBody| async move {
-                match match_location_type(&ctx.db, input.path.clone())
+                let (location, format) = location_format(&ctx.db, input.path).await?;
+                let settings = get_settings(&ctx.db).await?;
+                let key = NewKey {
+                    ts_key: input.ts_key,
+                    json_key: input.json_key,
+                    value: input.value,
+                    plural_values: input.plural_values,
+                    description: input.description,
+                    translations: input.translations,
+                };
+                format
+                    .add(&location, &settings, key)
                     .await
-                    .expect("failed to find location in database")
-                {
-                    LocationType::Frontend => {
-                        let db: &PrismaClient = &ctx.db;
-                        let settings = db
-                            .settings()
-                            .find_unique(settings::id::equals(1))
-                            .exec()
-                            .await?
-                            .unwrap();
-                        let keys = TranslationHandler::add_new_frontend_key(
-                            input.path.clone(),
-                            input.ts_key.clone(),
-                            input.json_key.clone(),
-                            input.value.clone(),
-                            settings.clone(),
-                        )
-                        .await
-                        .map_err(|error| {
-                            rspc::Error::new(
-                                rspc::ErrorCode::InternalServerError,
-                                error.to_string(),
-                            )
-                        })?;
-
-                        Ok(keys)
-                    }
-                    LocationType::Backend => {
-                        let db: &PrismaClient = &ctx.db;
-                        let settings = db
-                            .settings()
-                            .find_unique(settings::id::equals(1))
-                            .exec()
-                            .await?
-                            .unwrap();
-                        let keys = TranslationHandler::add_new_backend_key(
-                            input.path.clone(),
-                            input.ts_key.clone(),
-                            input.value.clone(),
-                            settings.clone(),
-                        )
-                        .await
-                        .map_err(|error| {
-                            rspc::Error::new(
-                                rspc::ErrorCode::InternalServerError,
-                                error.to_string(),
-                            )
-                        })?;
-
-                        Ok(keys)
-                    }
-                }
+                    .map_err(internal_error)
             })
         })
         .mutation("remove_keys", |t| {
             t(|ctx, input: RemoveTranslationBody| async move {
-                match match_location_type(&ctx.db, input.path.clone())
+                let (location, format) = location_format(&ctx.db, input.path).await?;
+                format
+                    .remove(&location, input.ts_key, input.json_key)
                     .await
-                    .expect("failed to find location in database")
-                {
-                    LocationType::Frontend => TranslationHandler::remove_frontend_key(
-                        input.path,
-                        input.ts_key,
-                        input.json_key,
-                    )
-                    .await
-                    .map_err(|error| {
-                        rspc::Error::new(rspc::ErrorCode::InternalServerError, error.to_string())
-                    }),
-                    LocationType::Backend => {
-                        TranslationHandler::remove_backend_key(input.path, input.ts_key)
-                            .await
-                            .map_err(|error| {
-                                rspc::Error::new(
-                                    rspc::ErrorCode::InternalServerError,
-                                    error.to_string(),
-                                )
-                            })
-                    }
-                }
+                    .map_err(internal_error)
             })
         })
+        .mutation("export_gettext", |t| {
+            t(|ctx, input: ExportBody| export_gettext(ctx, input))
+        })
+        .mutation("import_gettext", |t| {
+            t(|ctx, input: ImportBody| import_gettext(ctx, input))
+        })
+        .mutation("export_xliff", |t| {
+            t(|ctx, input: XliffExportBody| export_xliff(ctx, input))
+        })
+        .mutation("import_xliff", |t| {
+            t(|ctx, input: ImportBody| import_xliff(ctx, input))
+        })
+        .mutation("export_spreadsheet", |t| {
+            t(|ctx, input: SpreadsheetExportBody| export_spreadsheet(ctx, input))
+        })
+        .mutation("import_spreadsheet", |t| {
+            t(|ctx, input: ImportBody| import_spreadsheet(ctx, input))
+        })
         .mutation("update_keys", |t| {
             t(|ctx, input: UpdateKeysBody| async move {
-                match match_location_type(&ctx.db, input.path.clone())
+                let (location, format) = location_format(&ctx.db, input.path).await?;
+                let settings = get_settings(&ctx.db).await?;
+                format
+                    .update(&location, &settings, input.key)
                     .await
-                    .expect("failed to find location in database")
-                {
-                    LocationType::Frontend => {
-                        let settings: &settings::Data = &ctx
-                            .db
-                            .settings()
-                            .find_unique(settings::id::equals(1))
-                            .exec()
-                            .await?
-                            .unwrap();
-
-                        TranslationHandler::update_keys(
-                            input.path,
-                            input.key,
-                            settings.clone().to_owned(),
-                        )
-                        .await
-                        .map_err(|error| {
-                            rspc::Error::new(
-                                rspc::ErrorCode::InternalServerError,
-                                error.to_string(),
-                            )
-                        })
-                    }
-                    LocationType::Backend => {
-                        unimplemented!()
-                    }
-                }
+                    .map_err(internal_error)
             })
         })
 }
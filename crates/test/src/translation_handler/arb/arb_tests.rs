@@ -0,0 +1,98 @@
+use std::fs;
+use translation_handler::arb::{
+    add_language, get_languages, get_translations, is_location, remove_keys, set_value,
+    source_language,
+};
+
+const ENGLISH: &str = r#"{
+  "@@locale": "en",
+  "title": "Inbox",
+  "@title": {
+    "description": "Title of the inbox page"
+  },
+  "unread": "{count, plural, =0{No messages} other{{count} messages}}",
+  "@unread": {
+    "description": "Number of unread messages",
+    "placeholders": {
+      "count": {
+        "type": "int",
+        "example": "3"
+      }
+    }
+  }
+}
+"#;
+
+fn l10n_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(temp_dir.path().join("app_en.arb"), ENGLISH).unwrap();
+    fs::write(
+        temp_dir.path().join("app_de.arb"),
+        "{\n  \"@@locale\": \"de\",\n  \"title\": \"Posteingang\"\n}\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("l10n.yaml"), "arb-dir: lib/l10n\n").unwrap();
+    temp_dir
+}
+
+#[test]
+fn metadata_becomes_description_and_metadata_of_entries() {
+    let temp_dir = l10n_directory();
+    assert!(is_location(temp_dir.path()));
+    assert_eq!(vec!["de", "en"], get_languages(temp_dir.path()));
+    assert_eq!("en", source_language(temp_dir.path(), "en-US"));
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(vec!["title", "unread"], keys);
+    assert_eq!("Posteingang", entries[0].translations["de"]);
+    assert_eq!("", entries[1].translations["de"]);
+    assert_eq!(
+        Some("Title of the inbox page"),
+        entries[0].description.as_deref()
+    );
+    assert!(entries[0].metadata.is_empty());
+    assert_eq!(
+        Some("Number of unread messages"),
+        entries[1].description.as_deref()
+    );
+    assert_eq!("int", entries[1].metadata["placeholders.count.type"]);
+    assert_eq!("3", entries[1].metadata["placeholders.count.example"]);
+}
+
+#[test]
+fn edits_keep_metadata_and_remove_it_with_the_key() {
+    let temp_dir = l10n_directory();
+    set_value(temp_dir.path(), "de", "unread", "{count} Nachrichten").unwrap();
+    set_value(temp_dir.path(), "en", "title", "Messages").unwrap();
+    assert!(set_value(temp_dir.path(), "en", "@title", "{}").is_err());
+    remove_keys(temp_dir.path(), &["unread".to_string()]).unwrap();
+
+    assert_eq!(
+        r#"{
+  "@@locale": "en",
+  "title": "Messages",
+  "@title": {
+    "description": "Title of the inbox page"
+  }
+}
+"#,
+        fs::read_to_string(temp_dir.path().join("app_en.arb")).unwrap()
+    );
+    assert_eq!(
+        "{\n  \"@@locale\": \"de\",\n  \"title\": \"Posteingang\"\n}\n",
+        fs::read_to_string(temp_dir.path().join("app_de.arb")).unwrap()
+    );
+}
+
+#[test]
+fn languages_are_added_as_files_with_the_same_prefix() {
+    let temp_dir = l10n_directory();
+    add_language(temp_dir.path(), "pt-BR").unwrap();
+    assert!(add_language(temp_dir.path(), "pt_BR").is_err());
+    assert_eq!(
+        "{\n  \"@@locale\": \"pt_BR\"\n}\n",
+        fs::read_to_string(temp_dir.path().join("app_pt_BR.arb")).unwrap()
+    );
+    assert_eq!(vec!["de", "en", "pt_BR"], get_languages(temp_dir.path()));
+}
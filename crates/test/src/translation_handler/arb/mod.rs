@@ -0,0 +1,4 @@
+// Tests for the translation_handler arb module
+
+#[cfg(test)]
+pub mod arb_tests;
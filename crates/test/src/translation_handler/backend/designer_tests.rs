@@ -0,0 +1,112 @@
+use std::fs;
+use translation_handler::backend::designer::{identifier, regenerate_designer};
+use translation_handler::backend::xml::XmlHandler;
+
+const CSPROJ: &str = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <RootNamespace>Shop.Web</RootNamespace>
+  </PropertyGroup>
+  <ItemGroup>
+    <EmbeddedResource Update="Resources\Shared Texts.resx">
+      <Generator>ResXFileCodeGenerator</Generator>
+      <LastGenOutput>Shared Texts.Designer.cs</LastGenOutput>
+    </EmbeddedResource>
+    <EmbeddedResource Update="Resources\Public.resx">
+      <Generator>PublicResXFileCodeGenerator</Generator>
+      <CustomToolNamespace>Shop.Texts</CustomToolNamespace>
+    </EmbeddedResource>
+    <EmbeddedResource Update="Resources\Plain.resx" />
+  </ItemGroup>
+</Project>
+"#;
+
+const RESX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Save" xml:space="preserve">
+    <value>Save &amp; close</value>
+  </data>
+  <data name="2fa.hint" xml:space="preserve">
+    <value>Enter the code
+from your app</value>
+  </data>
+  <data name="Empty" xml:space="preserve">
+    <value />
+  </data>
+  <data name="Logo" type="System.Resources.ResXFileRef, System.Windows.Forms">
+    <value>logo.png;System.Byte[], mscorlib</value>
+  </data>
+</root>
+"#;
+
+fn project() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(temp_dir.path().join("Shop.Web.csproj"), CSPROJ).unwrap();
+    let resources = temp_dir.path().join("Resources");
+    fs::create_dir_all(&resources).unwrap();
+    for name in ["Shared Texts.resx", "Public.resx", "Plain.resx"] {
+        fs::write(resources.join(name), RESX).unwrap();
+    }
+    temp_dir
+}
+
+#[test]
+fn csproj_items_carry_generator_settings() {
+    let items = XmlHandler::get_resource_items(CSPROJ);
+    assert_eq!(3, items.len());
+    assert_eq!(r"Resources\Shared Texts.resx", items[0].path);
+    assert_eq!(Some("ResXFileCodeGenerator"), items[0].generator.as_deref());
+    assert_eq!(
+        Some("Shop.Texts"),
+        items[1].custom_tool_namespace.as_deref()
+    );
+    assert_eq!(None, items[2].generator);
+    assert_eq!(
+        Some("Shop.Web".to_string()),
+        XmlHandler::get_root_namespace(CSPROJ)
+    );
+    assert_eq!("_2fa_hint", identifier("2fa.hint"));
+}
+
+#[test]
+fn designer_matches_the_code_generator() {
+    let temp_dir = project();
+    let resources = temp_dir.path().join("Resources");
+    let designer = regenerate_designer(&resources.join("Shared Texts.resx"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(resources.join("Shared Texts.Designer.cs"), designer);
+    let source = fs::read_to_string(&designer).unwrap();
+    assert!(source.starts_with(
+        "//------------------------------------------------------------------------------\r\n// <auto-generated>\r\n"
+    ));
+    assert!(source.contains("\r\nnamespace Shop.Web.Resources {\r\n    using System;\r\n"));
+    assert!(source.contains("    internal class Shared_Texts {\r\n"));
+    assert!(source.contains(
+        "new global::System.Resources.ResourceManager(\"Shop.Web.Resources.Shared Texts\", typeof(Shared_Texts).Assembly);"
+    ));
+    assert!(source.contains(
+        "        /// <summary>\r\n        ///   Looks up a localized string similar to Enter the code\r\n        ///from your app.\r\n        /// </summary>\r\n        internal static string _2fa_hint {\r\n            get {\r\n                return ResourceManager.GetString(\"2fa.hint\", resourceCulture);\r\n            }\r\n        }\r\n"
+    ));
+    assert!(source.contains("similar to Save &amp; close."));
+    assert!(source.contains("internal static string Empty {"));
+    let order: Vec<usize> = ["_2fa_hint {", "Empty {", "Logo {", "Save {"]
+        .iter()
+        .map(|property| source.find(property).unwrap())
+        .collect();
+    assert!(order.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(source.ends_with("        }\r\n    }\r\n}\r\n"));
+
+    let public = regenerate_designer(&resources.join("Public.resx"))
+        .unwrap()
+        .unwrap();
+    let source = fs::read_to_string(public).unwrap();
+    assert!(source.contains("namespace Shop.Texts {"));
+    assert!(source.contains("    public class Public {"));
+    assert!(source.contains("        public static string Save {"));
+    assert!(source.contains("(\"Shop.Web.Resources.Public\", typeof(Public).Assembly)"));
+
+    assert_eq!(
+        None,
+        regenerate_designer(&resources.join("Plain.resx")).unwrap()
+    );
+}
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use translation_handler::backend::discovery::{discover_resources, project_files, resource_name};
+use translation_handler::backend::getter::get_resources_from_csproj;
+
+const SOLUTION: &str = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Web", "src\Web\Web.csproj", "{6B1F0B5E-6C7A-4F5E-9E55-2B1B7C9E8F01}"
+EndProject
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "Solution Items", "Solution Items", "{0D3A1A36-0B0C-4C5F-8C55-6E39F0D4A2B3}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Legacy", "src\Legacy\Legacy.csproj", "{A4C0E1B2-3D4E-4F50-8A1B-2C3D4E5F6A7B}"
+EndProject
+"#;
+
+const SDK_PROJECT: &str = r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <ItemGroup>
+    <EmbeddedResource Update="Resources\Strings.resx">
+      <Generator>ResXFileCodeGenerator</Generator>
+    </EmbeddedResource>
+    <EmbeddedResource Remove="Resources\Drafts\**" />
+    <EmbeddedResource Include="..\Shared\Common.resx" Link="Resources\Common.resx" />
+    <EmbeddedResource Include="..\Shared\Common.*.resx" />
+  </ItemGroup>
+</Project>
+"#;
+
+const LEGACY_PROJECT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Project ToolsVersion="15.0" xmlns="http://schemas.microsoft.com/developer/msbuild/2003">
+  <ItemGroup>
+    <EmbeddedResource Include="Properties\Resources.resx">
+      <Generator>ResXFileCodeGenerator</Generator>
+    </EmbeddedResource>
+    <EmbeddedResource Include="Properties\Resources.fr.resx" />
+  </ItemGroup>
+</Project>
+"#;
+
+const RESX: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n</root>\n";
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn solution() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "Shop.sln", SOLUTION);
+    write(root, "src/Web/Web.csproj", SDK_PROJECT);
+    write(root, "src/Legacy/Legacy.csproj", LEGACY_PROJECT);
+    for resx in [
+        "src/Web/Resources/Strings.resx",
+        "src/Web/Resources/Strings.de-DE.resx",
+        "src/Web/Resources/Strings.fr.resx",
+        "src/Web/Views/Home.Index.resx",
+        "src/Web/Resources/Drafts/Draft.resx",
+        "src/Web/bin/Debug/Copied.resx",
+        "src/Shared/Common.resx",
+        "src/Shared/Common.it.resx",
+        "src/Shared/Unused.resx",
+        "src/Legacy/Properties/Resources.resx",
+        "src/Legacy/Properties/Resources.fr.resx",
+        "src/Legacy/Properties/NotEmbedded.resx",
+    ] {
+        write(root, resx, RESX);
+    }
+    temp_dir
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[test]
+fn solution_projects_are_enumerated() {
+    let temp_dir = solution();
+    let root = temp_dir.path();
+    let projects: Vec<String> = project_files(&root.join("Shop.sln"))
+        .unwrap()
+        .iter()
+        .map(|project| relative(root, project))
+        .collect();
+    assert_eq!(
+        vec!["src/Web/Web.csproj", "src/Legacy/Legacy.csproj"],
+        projects
+    );
+
+    let projects: Vec<String> = project_files(root)
+        .unwrap()
+        .iter()
+        .map(|project| relative(root, project))
+        .collect();
+    assert_eq!(
+        vec!["src/Legacy/Legacy.csproj", "src/Web/Web.csproj"],
+        projects
+    );
+}
+
+#[test]
+fn resources_are_grouped_with_their_satellites() {
+    let temp_dir = solution();
+    let root = temp_dir.path();
+    let groups = discover_resources(&root.join("Shop.sln")).unwrap();
+    let found: Vec<(String, BTreeMap<String, String>)> = groups
+        .iter()
+        .map(|group| {
+            let satellites = group
+                .satellites
+                .iter()
+                .map(|(culture, path)| (culture.clone(), relative(root, path)))
+                .collect();
+            (relative(root, &group.neutral), satellites)
+        })
+        .collect();
+    let expected = vec![
+        (
+            "src/Legacy/Properties/Resources.resx",
+            vec![("fr", "src/Legacy/Properties/Resources.fr.resx")],
+        ),
+        (
+            "src/Shared/Common.resx",
+            vec![("it", "src/Shared/Common.it.resx")],
+        ),
+        (
+            "src/Web/Resources/Strings.resx",
+            vec![
+                ("de-DE", "src/Web/Resources/Strings.de-DE.resx"),
+                ("fr", "src/Web/Resources/Strings.fr.resx"),
+            ],
+        ),
+        ("src/Web/Views/Home.Index.resx", vec![]),
+    ];
+    let expected: Vec<(String, BTreeMap<String, String>)> = expected
+        .into_iter()
+        .map(|(neutral, satellites)| {
+            let satellites = satellites
+                .into_iter()
+                .map(|(culture, path)| (culture.to_string(), path.to_string()))
+                .collect();
+            (neutral.to_string(), satellites)
+        })
+        .collect();
+    assert_eq!(expected, found);
+
+    // A directory finds the same projects as the solution
+    assert_eq!(groups, discover_resources(root).unwrap());
+}
+
+#[test]
+fn csproj_resources_include_names_with_dots() {
+    let temp_dir = solution();
+    let root = temp_dir.path();
+    let project = root.join("src/Web/Web.csproj");
+    let resources: Vec<PathBuf> = get_resources_from_csproj(&project.to_string_lossy())
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(
+        vec![
+            root.join("src/Shared/Common.resx"),
+            root.join("src/Web/Resources/Strings.resx"),
+            root.join("src/Web/Views/Home.Index.resx"),
+        ],
+        resources
+    );
+}
+
+#[test]
+fn csproj_resources_report_unreadable_projects() {
+    let temp_dir = solution();
+    let root = temp_dir.path();
+    let missing = root.join("src/Missing/Missing.csproj");
+    assert!(get_resources_from_csproj(&missing.to_string_lossy()).is_err());
+    let solution_file = root.join("Shop.sln");
+    assert!(get_resources_from_csproj(&solution_file.to_string_lossy())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn resource_names_follow_the_embedding_project() {
+    let temp_dir = solution();
+    let root = temp_dir.path();
+    let name = |path: &str| resource_name(&root.join(path), None).unwrap();
+    assert_eq!(
+        "Web.Resources.Strings",
+        name("src/Web/Resources/Strings.resx")
+    );
+    assert_eq!(
+        "Web.Views.Home.Index",
+        name("src/Web/Views/Home.Index.resx")
+    );
+    assert_eq!(
+        "Legacy.Properties.Resources",
+        name("src/Legacy/Properties/Resources.resx")
+    );
+    // Linked files belong to no project below their folder, so it has to be given
+    assert_eq!("Shared.Common", name("src/Shared/Common.resx"));
+    assert_eq!(
+        "Web.Resources.Common",
+        resource_name(
+            &root.join("src/Shared/Common.resx"),
+            Some(&root.join("src/Web/Web.csproj"))
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn discovered_resources_are_named_after_their_link_or_logical_name() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(
+        root,
+        "App/App.csproj",
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <RootNamespace>Shop.App</RootNamespace>
+  </PropertyGroup>
+  <ItemGroup>
+    <EmbeddedResource Include="..\SharedA\Texts\Errors.resx" Link="Texts\Errors.resx" />
+    <EmbeddedResource Include="..\SharedB\Texts\Errors.resx">
+      <Link>Texts\Legacy\Errors.resx</Link>
+    </EmbeddedResource>
+    <EmbeddedResource Update="Resources\Strings.resx" LogicalName="Shop.Strings.resources" />
+  </ItemGroup>
+</Project>
+"#,
+    );
+    for resx in [
+        "App/Resources/Strings.resx",
+        "SharedA/Texts/Errors.resx",
+        "SharedB/Texts/Errors.resx",
+    ] {
+        write(root, resx, RESX);
+    }
+
+    let names: Vec<(String, String)> = discover_resources(&root.join("App/App.csproj"))
+        .unwrap()
+        .iter()
+        .map(|group| {
+            let name = resource_name(&group.neutral, group.project.as_deref()).unwrap();
+            (relative(root, &group.neutral), name)
+        })
+        .collect();
+    let expected = [
+        ("App/Resources/Strings.resx", "Shop.Strings"),
+        ("SharedA/Texts/Errors.resx", "Shop.App.Texts.Errors"),
+        ("SharedB/Texts/Errors.resx", "Shop.App.Texts.Legacy.Errors"),
+    ];
+    assert_eq!(
+        expected
+            .map(|(path, name)| (path.to_string(), name.to_string()))
+            .to_vec(),
+        names
+    );
+}
@@ -0,0 +1,73 @@
+use std::fs;
+use translation_handler::backend::getter::get_backend_languages;
+use translation_handler::TranslationHandler;
+
+fn resx(values: &[(&str, &str)]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n");
+    for (name, value) in values {
+        xml.push_str(&format!(
+            "  <data name=\"{}\" xml:space=\"preserve\">\n    <value>{}</value>\n  </data>\n",
+            name, value
+        ));
+    }
+    xml.push_str("</root>\n");
+    xml
+}
+
+#[tokio::test]
+async fn cultures_resolve_through_their_parents() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let files = [
+        (
+            "Errors.Validation.resx",
+            resx(&[
+                ("Required", "Required"),
+                ("TooLong", "Too long"),
+                ("Invalid", "Invalid"),
+            ]),
+        ),
+        (
+            "Errors.Validation.de.resx",
+            resx(&[("Required", "Pflichtfeld"), ("TooLong", "Zu lang")]),
+        ),
+        (
+            "Errors.Validation.de-AT.resx",
+            resx(&[("Required", "Pflichtfeld!")]),
+        ),
+        ("Errors.resx", resx(&[("Unknown", "Unknown error")])),
+    ];
+    for (name, content) in &files {
+        fs::write(temp_dir.path().join(name), content).unwrap();
+    }
+    let neutral = temp_dir.path().join("Errors.Validation.resx");
+    let neutral = neutral.to_string_lossy();
+    assert_eq!(
+        vec!["default", "de", "de-AT"],
+        get_backend_languages(&neutral)
+    );
+    assert_eq!(
+        vec!["default"],
+        get_backend_languages(&temp_dir.path().join("Errors.resx").to_string_lossy())
+    );
+
+    let entries = TranslationHandler::get_backend_translations(&neutral)
+        .await
+        .unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(vec!["Invalid", "Required", "TooLong"], keys);
+
+    let required = &entries[1];
+    assert_eq!("Pflichtfeld!", required.effective_values["de-AT"]);
+    assert!(required.fallbacks.is_empty());
+
+    let too_long = &entries[2];
+    assert_eq!(None, too_long.translations.get("de-AT"));
+    assert_eq!("Zu lang", too_long.effective_values["de-AT"]);
+    assert_eq!("de", too_long.fallbacks["de-AT"]);
+    assert_eq!(None, too_long.fallbacks.get("de"));
+
+    let invalid = &entries[0];
+    assert_eq!("Invalid", invalid.effective_values["de"]);
+    assert_eq!("default", invalid.fallbacks["de"]);
+    assert_eq!("default", invalid.fallbacks["de-AT"]);
+}
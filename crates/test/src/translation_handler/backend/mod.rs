@@ -1,4 +1,14 @@
 // Tests for the translation_handler backend module
 
 #[cfg(test)]
-pub mod xml;
\ No newline at end of file
+pub mod xml;
+#[cfg(test)]
+pub mod remover_tests;
+#[cfg(test)]
+pub mod designer_tests;
+#[cfg(test)]
+pub mod discovery_tests;
+#[cfg(test)]
+pub mod getter_tests;
+#[cfg(test)]
+pub mod usage_tests;
@@ -0,0 +1,81 @@
+use std::fs;
+use translation_handler::backend::remover::RemovedKeys;
+use translation_handler::TranslationHandler;
+
+fn resx(keys: &[&str]) -> String {
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n");
+    for key in keys {
+        content.push_str(&format!(
+            "  <data name=\"{}\" xml:space=\"preserve\">\n    <value>{}</value>\n  </data>\n",
+            key, key
+        ));
+    }
+    content.push_str("</root>\n");
+    content
+}
+
+#[tokio::test]
+async fn keys_are_removed_from_every_satellite() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let neutral = temp_dir.path().join("Resources.resx");
+    let german = temp_dir.path().join("Resources.de-DE.resx");
+    let french = temp_dir.path().join("Resources.fr-FR.resx");
+    fs::write(&neutral, resx(&["Cancel", "Obsolete", "Save"])).unwrap();
+    fs::write(&german, resx(&["Obsolete", "Save"])).unwrap();
+    fs::write(&french, resx(&["Save"])).unwrap();
+    fs::write(
+        temp_dir.path().join("Other.de-DE.resx"),
+        resx(&["Obsolete"]),
+    )
+    .unwrap();
+
+    let summary = TranslationHandler::remove_backend_key(
+        neutral.to_string_lossy().to_string(),
+        vec!["Obsolete".to_string(), "Cancel".to_string()],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            RemovedKeys {
+                file: neutral.clone(),
+                keys: vec!["Cancel".to_string(), "Obsolete".to_string()],
+            },
+            RemovedKeys {
+                file: german.clone(),
+                keys: vec!["Obsolete".to_string()],
+            },
+        ],
+        summary
+    );
+    assert_eq!(resx(&["Save"]), fs::read_to_string(&neutral).unwrap());
+    assert_eq!(resx(&["Save"]), fs::read_to_string(&german).unwrap());
+    assert_eq!(resx(&["Save"]), fs::read_to_string(&french).unwrap());
+    assert_eq!(
+        resx(&["Obsolete"]),
+        fs::read_to_string(temp_dir.path().join("Other.de-DE.resx")).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn unreadable_files_fail_the_removal_before_anything_is_written() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let neutral = temp_dir.path().join("Resources.resx");
+    fs::write(&neutral, resx(&["Save"])).unwrap();
+    fs::write(
+        temp_dir.path().join("Resources.de-DE.resx"),
+        "<root><data name=\"Save\"></root>",
+    )
+    .unwrap();
+
+    let error = TranslationHandler::remove_backend_key(
+        neutral.to_string_lossy().to_string(),
+        vec!["Save".to_string()],
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error.to_string().contains("Resources.de-DE.resx"));
+    assert_eq!(resx(&["Save"]), fs::read_to_string(&neutral).unwrap());
+}
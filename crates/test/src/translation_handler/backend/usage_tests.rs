@@ -0,0 +1,108 @@
+use local_storage::stores::translation_store::SourceLocation;
+use std::fs;
+use std::path::Path;
+use translation_handler::TranslationHandler;
+
+const RESX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Welcome" xml:space="preserve">
+    <value>Welcome</value>
+  </data>
+  <data name="Errors.Required" xml:space="preserve">
+    <value>Required</value>
+  </data>
+  <data name="Unused" xml:space="preserve">
+    <value>Nobody reads this</value>
+  </data>
+</root>
+"#;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[tokio::test]
+async fn backend_keys_are_used_by_the_sources_of_their_project() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(
+        root,
+        "Web/Web.csproj",
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web"></Project>"#,
+    );
+    write(root, "Web/Resources/Strings.resx", RESX);
+    write(
+        root,
+        "Web/Resources/Strings.Designer.cs",
+        "internal static string Unused => ResourceManager.GetString(\"Unused\", resourceCulture);\n",
+    );
+    write(
+        root,
+        "Web/Pages/Index.cshtml",
+        "@inject IStringLocalizer<Strings> Localizer\n<h1>@Localizer[\"Welcome\"]</h1>\n",
+    );
+    write(
+        root,
+        "Web/Models/Login.cs",
+        "[Required(ErrorMessageResourceType = typeof(Strings), ErrorMessageResourceName = nameof(Strings.Errors_Required))]\nvar hint = Strings.Hint;\n",
+    );
+    // Another project's sources don't count
+    write(
+        root,
+        "Admin/Admin.csproj",
+        r#"<Project Sdk="Microsoft.NET.Sdk"></Project>"#,
+    );
+    write(root, "Admin/Report.cs", "var text = Strings.Unused;\n");
+
+    let resx = root.join("Web/Resources/Strings.resx");
+    let entries = TranslationHandler::get_backend_translations(&resx.to_string_lossy())
+        .await
+        .unwrap();
+    let entry = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+
+    assert!(entry("Welcome").in_use);
+    assert_eq!(
+        vec![SourceLocation {
+            file: Path::new("Pages")
+                .join("Index.cshtml")
+                .to_string_lossy()
+                .to_string(),
+            line: 2,
+            column: 16,
+        }],
+        entry("Welcome").usages
+    );
+    assert!(entry("Errors.Required").in_use);
+    assert_eq!(1, entry("Errors.Required").usages.len());
+    assert!(!entry("Unused").in_use);
+    assert!(!entry("Unused").possibly_used);
+    assert!(entry("Unused").usages.is_empty());
+
+    // Later listings see edited sources
+    write(
+        root,
+        "Web/Pages/Index.cshtml",
+        "@inject IStringLocalizer<Strings> Localizer\n<h1>@Localizer[\"Unused\"]</h1>\n",
+    );
+    let entries = TranslationHandler::get_backend_translations(&resx.to_string_lossy())
+        .await
+        .unwrap();
+    let entry = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+    assert!(entry("Unused").in_use);
+    assert!(!entry("Welcome").in_use);
+
+    // and keys added to the resx file since
+    let resx_with_hint = RESX.replace(
+        "</root>",
+        "  <data name=\"Hint\" xml:space=\"preserve\">\n    <value>Hint</value>\n  </data>\n</root>",
+    );
+    fs::write(&resx, resx_with_hint).unwrap();
+    let entries = TranslationHandler::get_backend_translations(&resx.to_string_lossy())
+        .await
+        .unwrap();
+    let entry = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+    assert!(entry("Hint").in_use);
+    assert_eq!(2, entry("Hint").usages[0].line);
+}
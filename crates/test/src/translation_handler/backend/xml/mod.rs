@@ -1,4 +1,8 @@
 // Tests for the translation_handler backend xml module
 
 #[cfg(test)]
-pub mod reader_tests;
\ No newline at end of file
+pub mod reader_tests;
+#[cfg(test)]
+pub mod updater_tests;
+#[cfg(test)]
+pub mod writer_tests;
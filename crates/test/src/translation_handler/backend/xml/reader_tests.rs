@@ -12,7 +12,7 @@ fn name_attributes_and_value_tags_are_read_correctly() {
         </data>
         "#;
 
-    let response = XmlHandler::read_name_attributes_and_value_tags(xml);
+    let response = XmlHandler::read_name_attributes_and_value_tags(xml).unwrap();
 
     assert_eq!(2, response.len());
     //*
@@ -51,4 +51,70 @@ fn get_resources() {
     assert_eq!("Quotes\\QuoteResources.en-US.resx", response[0]);
     assert_eq!("Quotes\\QuoteResources.resx", response[1]);
     assert_eq!("Sms\\SmsTextResources.resx", response[2]);
-}
\ No newline at end of file
+}
+#[test]
+fn resources_keep_cdata_entities_line_breaks_and_empty_values() {
+    let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n<root>\r\n  \
+        <data name=\"Terms\" xml:space=\"preserve\">\r\n    \
+        <value>Read the <![CDATA[<b>terms</b>]]> &amp; agree\r\nto them</value>\r\n  \
+        </data>\r\n  \
+        <data name=\"Empty\" xml:space=\"preserve\">\r\n    <value />\r\n  </data>\r\n  \
+        <data name=\"Blank\">\r\n    <value>   </value>\r\n  </data>\r\n  \
+        <data name=\"Space\" xml:space=\"preserve\">\r\n    <value> </value>\r\n  </data>\r\n  \
+        <data name=\"NoValue\" />\r\n  \
+        <data name=\"Logo\" type=\"System.Resources.ResXFileRef, System.Windows.Forms\">\r\n    \
+        <value>logo.png;System.Drawing.Bitmap, System.Drawing</value>\r\n  </data>\r\n\
+        </root>\r\n";
+
+    let resources = XmlHandler::read_resources(xml).unwrap();
+    let values: Vec<(&str, &str, bool)> = resources
+        .iter()
+        .map(|resource| {
+            (
+                resource.name.as_str(),
+                resource.value.as_str(),
+                resource.is_editable(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("Terms", "Read the <b>terms</b> & agree\nto them", true),
+            ("Empty", "", true),
+            ("Blank", "", true),
+            ("Space", " ", true),
+            ("NoValue", "", true),
+            (
+                "Logo",
+                "logo.png;System.Drawing.Bitmap, System.Drawing",
+                false
+            ),
+        ],
+        values
+    );
+
+    let strings = XmlHandler::read_name_attributes_and_value_tags(xml).unwrap();
+    assert_eq!(5, strings.len());
+    assert!(!strings.contains_key("Logo"));
+}
+
+#[test]
+fn malformed_resx_files_report_line_and_column() {
+    let unclosed = "<root>\n  <data name=\"A\">\n    <value>a</value>\n  </daat>\n</root>\n";
+    let error = XmlHandler::read_resources(unclosed).unwrap_err();
+    assert_eq!((4, 3), (error.line, error.column));
+
+    let nameless = "<root>\n  <data>\n    <value>a</value>\n  </data>\n</root>\n";
+    let error = XmlHandler::read_resources(nameless).unwrap_err();
+    assert_eq!(
+        "<data> has no name attribute at line 2, column 3",
+        error.to_string()
+    );
+
+    let truncated = "<root>\n  <data name=\"A\">\n    <value>a</value>\n";
+    let error = XmlHandler::read_resources(truncated).unwrap_err();
+    assert_eq!("<data> is not closed", error.message);
+
+    let entity = "<root>\n  <data name=\"A\"><value>&nbsp;</value></data>\n</root>\n";
+    assert_eq!(2, XmlHandler::read_resources(entity).unwrap_err().line);
+}
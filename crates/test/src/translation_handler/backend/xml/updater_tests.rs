@@ -0,0 +1,107 @@
+use std::fs;
+use translation_handler::backend::setter::language_file;
+use translation_handler::backend::xml::XmlHandler;
+
+const RESX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Button_Save" xml:space="preserve">
+    <value>Save</value>
+    <comment>Button label</comment>
+  </data>
+  <data name="Label_Name" xml:space="preserve">
+    <value>Name</value>
+  </data>
+</root>
+"#;
+
+fn resx_file() -> (tempfile::TempDir, String) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("Resources.resx");
+    fs::write(&path, RESX).unwrap();
+    (temp_dir, path.to_string_lossy().to_string())
+}
+
+#[test]
+fn comments_are_replaced_added_and_removed() {
+    let (_temp_dir, path) = resx_file();
+    XmlHandler::update_comment(
+        path.clone(),
+        "Button_Save".to_string(),
+        "Button label, max 20 chars".to_string(),
+    )
+    .unwrap();
+    XmlHandler::update_comment(
+        path.clone(),
+        "Label_Name".to_string(),
+        "Form field <label>".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Button_Save" xml:space="preserve">
+    <value>Save</value>
+    <comment>Button label, max 20 chars</comment>
+  </data>
+  <data name="Label_Name" xml:space="preserve">
+    <value>Name</value>
+    <comment>Form field &lt;label&gt;</comment>
+  </data>
+</root>
+"#,
+        fs::read_to_string(&path).unwrap()
+    );
+
+    XmlHandler::update_comment(path.clone(), "Button_Save".to_string(), String::new()).unwrap();
+    let comments = XmlHandler::read_comments(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(None, comments.get("Button_Save"));
+    assert_eq!(
+        Some(&"Form field <label>".to_string()),
+        comments.get("Label_Name")
+    );
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("    <value>Save</value>\n  </data>"));
+}
+
+#[test]
+fn missing_keys_are_added_to_new_satellite_files() {
+    let (_temp_dir, path) = resx_file();
+    let satellite = language_file(&path, "de-DE").unwrap();
+    assert!(satellite.ends_with("Resources.de-DE.resx"));
+    assert_eq!(
+        std::path::PathBuf::from(&path),
+        language_file(&path, "default").unwrap()
+    );
+
+    XmlHandler::create_resx_file(&satellite).unwrap();
+    let satellite_path = satellite.to_string_lossy().to_string();
+    assert!(!XmlHandler::update_value(
+        satellite_path.clone(),
+        "Button_Save".to_string(),
+        "Speichern".to_string()
+    )
+    .unwrap());
+    XmlHandler::write_key_value(
+        satellite_path.clone(),
+        "Button_Save".to_string(),
+        "Speichern & schließen".to_string(),
+        None,
+    )
+    .unwrap();
+    assert!(
+        XmlHandler::update_value(path.clone(), "Button_Save".to_string(), "Save".to_string())
+            .unwrap()
+    );
+
+    let values =
+        XmlHandler::read_name_attributes_and_value_tags(&fs::read_to_string(&satellite).unwrap())
+            .unwrap();
+    assert_eq!(
+        Some(&"Speichern & schließen".to_string()),
+        values["Button_Save"].get("default")
+    );
+    assert!(fs::read_to_string(&satellite).unwrap().starts_with(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<root>\n  <resheader name=\"resmimetype\">"
+    ));
+}
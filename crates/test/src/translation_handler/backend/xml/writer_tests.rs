@@ -0,0 +1,64 @@
+use std::fs;
+use translation_handler::backend::xml::writer::Placement;
+use translation_handler::backend::xml::XmlHandler;
+
+const SORTED: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n<root>\r\n\t<data name=\"Apple\" xml:space=\"preserve\">\r\n\t\t<value>Apple</value>\r\n\t</data>\r\n\t<data name=\"cherry\" xml:space=\"preserve\">\r\n\t\t<value>Cherry</value>\r\n\t</data>\r\n</root>\r\n";
+
+fn resx_file(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("Resources.resx");
+    fs::write(&path, content).unwrap();
+    (temp_dir, path)
+}
+
+#[test]
+fn inserted_data_is_escaped_and_matches_the_file_layout() {
+    let (_temp_dir, path) = resx_file(SORTED);
+    assert!(XmlHandler::is_sorted(SORTED));
+    XmlHandler::insert_data(
+        &path,
+        "Banana & \"Co\"",
+        "Fish & <Chips>",
+        Some("Menu item"),
+        Placement::Sorted,
+    )
+    .unwrap();
+    XmlHandler::insert_data(&path, "Zucchini", "Zucchini", None, Placement::End).unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n<root>\r\n\t<data name=\"Apple\" xml:space=\"preserve\">\r\n\t\t<value>Apple</value>\r\n\t</data>\r\n\t<data name=\"Banana &amp; &quot;Co&quot;\" xml:space=\"preserve\">\r\n\t\t<value>Fish &amp; &lt;Chips&gt;</value>\r\n\t\t<comment>Menu item</comment>\r\n\t</data>\r\n\t<data name=\"cherry\" xml:space=\"preserve\">\r\n\t\t<value>Cherry</value>\r\n\t</data>\r\n\t<data name=\"Zucchini\" xml:space=\"preserve\">\r\n\t\t<value>Zucchini</value>\r\n\t</data>\r\n</root>\r\n",
+        content
+    );
+    let values = XmlHandler::read_name_attributes_and_value_tags(&content).unwrap();
+    assert_eq!(
+        Some(&"Fish & <Chips>".to_string()),
+        values["Banana & \"Co\""].get("default")
+    );
+}
+
+#[test]
+fn duplicate_keys_are_rejected() {
+    let (_temp_dir, path) = resx_file(SORTED);
+    let error =
+        XmlHandler::insert_data(&path, "cherry", "Kirsche", None, Placement::End).unwrap_err();
+    assert_eq!(std::io::ErrorKind::AlreadyExists, error.kind());
+    assert_eq!(SORTED, fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn first_data_goes_before_the_end_of_root() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("Resources.de-DE.resx");
+    XmlHandler::create_resx_file(&path).unwrap();
+    XmlHandler::write_key_value(
+        path.to_string_lossy().to_string(),
+        "Greeting".to_string(),
+        "Hallo".to_string(),
+        None,
+    )
+    .unwrap();
+    assert!(fs::read_to_string(&path).unwrap().ends_with(
+        "  </resheader>\n  <data name=\"Greeting\" xml:space=\"preserve\">\n    <value>Hallo</value>\n  </data>\n</root>\n"
+    ));
+}
@@ -0,0 +1,323 @@
+use db::prisma::{location, settings};
+use std::fs;
+use std::path::Path;
+use translation_handler::exchange::gettext::{self, Catalog};
+use translation_handler::exchange::spreadsheet::{self, read_matrix};
+use translation_handler::exchange::xliff::{self, XliffDocument};
+use translation_handler::exchange::{apply_import, plan_import};
+use translation_handler::format::{KeyValue, ReactFormat, ResxFormat, TranslationFormat};
+
+const MESSAGES_TS: &str = "import { defineLocales } from '@/lib/i18n';
+import locales from './locales/locales';
+
+export default defineLocales({
+  title: 'home.title',
+  subtitle: 'home.subtitle',
+}, locales);
+";
+
+const EN_GB: &str = "{\n\t\"home.title\": \"Home\",\n\t\"home.subtitle\": \"Welcome\"\n}\n";
+const DE_DE: &str = "{\n\t\"home.title\": \"Startseite\",\n\t\"home.subtitle\": \"\"\n}\n";
+const FR_FR: &str = "{\n\t\"home.title\": \"Accueil\",\n\t\"home.subtitle\": \"Bienvenue\"\n}\n";
+
+const STRINGS_RESX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Subtitle" xml:space="preserve">
+    <value>Welcome</value>
+  </data>
+  <data name="Title" xml:space="preserve">
+    <value>Home</value>
+  </data>
+</root>
+"#;
+
+const STRINGS_FR_FR_RESX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Title" xml:space="preserve">
+    <value>Accueil</value>
+  </data>
+</root>
+"#;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn read(root: &Path, path: &str) -> String {
+    fs::read_to_string(root.join(path)).unwrap()
+}
+
+fn react_location(root: &Path) -> location::Data {
+    write(root, "messages.ts", MESSAGES_TS);
+    write(root, "locales/en-GB.json", EN_GB);
+    write(root, "locales/de-DE.json", DE_DE);
+    write(root, "locales/fr-FR.json", FR_FR);
+    location::Data {
+        id: 1,
+        tag: "FE".to_string(),
+        name: "web".to_string(),
+        path: root.to_string_lossy().to_string(),
+        is_favourite: false,
+        num_of_keys: 2,
+        num_of_untranslated_keys: 1,
+        added_at: String::new(),
+        json_layout: "flat".to_string(),
+        messages_file: "messages.ts".to_string(),
+        locales_directory: "locales".to_string(),
+        locale_file_pattern: "{lang}.json".to_string(),
+        languages_file: "locales/locales.ts".to_string(),
+        source_language: None,
+    }
+}
+
+fn resx_location(root: &Path) -> location::Data {
+    write(
+        root,
+        "Web/Web.csproj",
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <ItemGroup>
+    <EmbeddedResource Update="Resources\Strings.resx">
+      <Generator>ResXFileCodeGenerator</Generator>
+    </EmbeddedResource>
+  </ItemGroup>
+</Project>
+"#,
+    );
+    write(root, "Web/Resources/Strings.resx", STRINGS_RESX);
+    write(
+        root,
+        "Web/Resources/Strings.de-DE.resx",
+        &STRINGS_RESX
+            .replace("Welcome", "Willkommen")
+            .replace("Home", "Startseite"),
+    );
+    write(root, "Web/Resources/Strings.fr-FR.resx", STRINGS_FR_FR_RESX);
+    location::Data {
+        id: 2,
+        tag: "BE".to_string(),
+        name: "Web.Resources.Strings".to_string(),
+        path: root
+            .join("Web/Resources/Strings.resx")
+            .to_string_lossy()
+            .to_string(),
+        is_favourite: false,
+        num_of_keys: 2,
+        num_of_untranslated_keys: 0,
+        added_at: String::new(),
+        json_layout: String::new(),
+        messages_file: String::new(),
+        locales_directory: String::new(),
+        locale_file_pattern: String::new(),
+        languages_file: String::new(),
+        source_language: None,
+    }
+}
+
+/// Settings that retranslate a key whenever only its source value is updated.
+fn retranslating_settings() -> settings::Data {
+    settings::Data {
+        id: 1,
+        nav_open: false,
+        theme: "light".to_string(),
+        notifications_enabled: false,
+        toast_rich_colors: true,
+        notification_file_changes: false,
+        notification_finished_translation: false,
+        finished_scan: false,
+        translate_new_strings: true,
+        translate_updated_strings: true,
+        default_language: "en-GB".to_string(),
+        translation_command: String::new(),
+        home_default_size_nav: 4,
+        home_default_size_home: 96,
+        home_nav_collapsed: true,
+        home_collapsed_nav_size: 4,
+    }
+}
+
+#[tokio::test]
+async fn spreadsheet_import_of_source_values_leaves_other_languages_alone() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    let location = react_location(root);
+    let settings = retranslating_settings();
+    write(
+        root,
+        "web.csv",
+        "ts_key,json_key,in_use,en-GB,de-DE\ntitle,home.title,true,Start,Startseite\nsubtitle,home.subtitle,true,Welcome,Willkommen\n",
+    );
+
+    let format = ReactFormat;
+    let entries = format.list(&location).await.unwrap();
+    let values =
+        spreadsheet::imported_values(&read_matrix(&root.join("web.csv")).unwrap()).unwrap();
+    let report = plan_import(&entries, "en-GB", values);
+    assert_eq!(2, report.changed.len());
+
+    apply_import(&format, &location, &settings, &entries, &report.changed)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        "{\n\t\"home.title\": \"Start\",\n\t\"home.subtitle\": \"Welcome\"\n}\n",
+        read(root, "locales/en-GB.json")
+    );
+    assert_eq!(
+        "{\n\t\"home.title\": \"Startseite\",\n\t\"home.subtitle\": \"Willkommen\"\n}\n",
+        read(root, "locales/de-DE.json")
+    );
+    assert_eq!(FR_FR, read(root, "locales/fr-FR.json"));
+}
+
+#[tokio::test]
+async fn gettext_import_writes_only_the_catalog_language() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    let location = react_location(root);
+    let settings = retranslating_settings();
+    write(
+        root,
+        "web.de-DE.po",
+        "msgid \"\"\nmsgstr \"\"\n\"Language: de-DE\\n\"\n\nmsgctxt \"subtitle\"\nmsgid \"Welcome\"\nmsgstr \"Willkommen\"\n",
+    );
+
+    let format = ReactFormat;
+    let entries = format.list(&location).await.unwrap();
+    let catalog = Catalog::read(&root.join("web.de-DE.po")).unwrap();
+    let language = catalog.language().unwrap();
+    let report = plan_import(
+        &entries,
+        "en-GB",
+        gettext::imported_values(&catalog, &language),
+    );
+    assert_eq!(1, report.changed.len());
+
+    apply_import(&format, &location, &settings, &entries, &report.changed)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        "{\n\t\"home.title\": \"Startseite\",\n\t\"home.subtitle\": \"Willkommen\"\n}\n",
+        read(root, "locales/de-DE.json")
+    );
+    assert_eq!(EN_GB, read(root, "locales/en-GB.json"));
+    assert_eq!(FR_FR, read(root, "locales/fr-FR.json"));
+}
+
+#[tokio::test]
+async fn xliff_import_skips_units_whose_source_changed() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    let location = react_location(root);
+    let settings = retranslating_settings();
+    write(
+        root,
+        "web.fr-FR.xlf",
+        r#"<?xml version="1.0"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file original="web" source-language="en-GB" target-language="fr-FR" datatype="plaintext">
+    <body>
+      <trans-unit id="title">
+        <source>Home</source>
+        <target state="translated">Page d'accueil</target>
+      </trans-unit>
+      <trans-unit id="subtitle">
+        <source>Hello</source>
+        <target state="translated">Salut</target>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>"#,
+    );
+
+    let format = ReactFormat;
+    let entries = format.list(&location).await.unwrap();
+    let document = XliffDocument::read(&root.join("web.fr-FR.xlf")).unwrap();
+    let report = plan_import(
+        &entries,
+        "en-GB",
+        xliff::imported_values(&document, "fr-FR"),
+    );
+    assert_eq!(1, report.changed.len());
+    assert_eq!("subtitle", report.conflicted[0].key);
+
+    apply_import(&format, &location, &settings, &entries, &report.changed)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        "{\n\t\"home.title\": \"Page d'accueil\",\n\t\"home.subtitle\": \"Bienvenue\"\n}\n",
+        read(root, "locales/fr-FR.json")
+    );
+    assert_eq!(EN_GB, read(root, "locales/en-GB.json"));
+    assert_eq!(DE_DE, read(root, "locales/de-DE.json"));
+}
+
+#[tokio::test]
+async fn resx_import_writes_values_into_each_culture_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    let location = resx_location(root);
+    let settings = retranslating_settings();
+    write(
+        root,
+        "web.csv",
+        "ts_key,json_key,in_use,default,de-DE,fr-FR\nTitle,Title,true,Start,Startseite,Accueil\nSubtitle,Subtitle,true,Welcome,,Bienvenue\n",
+    );
+
+    let format = ResxFormat;
+    let entries = format.list(&location).await.unwrap();
+    let values =
+        spreadsheet::imported_values(&read_matrix(&root.join("web.csv")).unwrap()).unwrap();
+    let report = plan_import(&entries, "default", values);
+    assert_eq!(2, report.changed.len());
+
+    apply_import(&format, &location, &settings, &entries, &report.changed)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        STRINGS_RESX.replace("Home", "Start"),
+        read(root, "Web/Resources/Strings.resx")
+    );
+    assert!(read(root, "Web/Resources/Strings.Designer.cs")
+        .contains("Looks up a localized string similar to Start."));
+    assert_eq!(
+        STRINGS_RESX
+            .replace("Welcome", "Willkommen")
+            .replace("Home", "Startseite"),
+        read(root, "Web/Resources/Strings.de-DE.resx")
+    );
+    assert_eq!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <data name="Subtitle" xml:space="preserve">
+    <value>Bienvenue</value>
+  </data>
+  <data name="Title" xml:space="preserve">
+    <value>Accueil</value>
+  </data>
+</root>
+"#,
+        read(root, "Web/Resources/Strings.fr-FR.resx")
+    );
+
+    // Values are written as they are, so an empty one doesn't remove the key
+    format
+        .set_values(
+            &location,
+            &settings,
+            "de-DE",
+            vec![KeyValue {
+                ts_key: "Subtitle".to_string(),
+                json_key: "Subtitle".to_string(),
+                value: String::new(),
+            }],
+        )
+        .await
+        .unwrap();
+    assert!(read(root, "Web/Resources/Strings.de-DE.resx")
+        .contains("<data name=\"Subtitle\" xml:space=\"preserve\">\n    <value></value>"));
+}
@@ -0,0 +1,4 @@
+// Tests for the translation_handler exchange module
+
+#[cfg(test)]
+pub mod import_tests;
@@ -0,0 +1,121 @@
+use std::fs;
+use translation_handler::checker::{check_entries, MessageSyntax, Problem};
+use translation_handler::fluent::{
+    add_language, get_languages, get_translations, is_location, remove_keys, set_value,
+    source_language,
+};
+
+const ENGLISH: &str = "## Inbox
+
+# $count is the number of unread emails
+emails = { $count ->
+    [one] You have one unread email.
+   *[other] You have { $count } unread emails.
+}
+-brand-name = Mailer
+login-input = Login
+    .placeholder = { $user }@example.com
+";
+
+fn locales_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let english = temp_dir.path().join("en-US");
+    fs::create_dir_all(&english).unwrap();
+    fs::write(english.join("inbox.ftl"), ENGLISH).unwrap();
+    let german = temp_dir.path().join("de");
+    fs::create_dir_all(&german).unwrap();
+    fs::write(
+        german.join("inbox.ftl"),
+        "-brand-name = Mailer\nlogin-input = Anmelden\n    .placeholder = { $name }@example.com\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+#[test]
+fn messages_terms_and_attributes_become_entries() {
+    let temp_dir = locales_directory();
+    assert!(is_location(temp_dir.path()));
+    assert_eq!(vec!["de", "en-US"], get_languages(temp_dir.path()));
+    assert_eq!("en-US", source_language(temp_dir.path(), "en"));
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(
+        vec!["-brand-name", "login-input", "login-input.placeholder", "emails"],
+        keys
+    );
+    assert_eq!("", entries[3].translations["de"]);
+
+    let issues = check_entries(&entries, "en-US", MessageSyntax::Fluent);
+    assert_eq!(1, issues.len());
+    assert_eq!("login-input.placeholder", issues[0].key);
+    assert_eq!(
+        Problem::Renamed {
+            from: "{ $user }".to_string(),
+            to: "{ $name }".to_string()
+        },
+        issues[0].problem
+    );
+}
+
+#[test]
+fn edits_keep_select_expressions_and_comments() {
+    let temp_dir = locales_directory();
+    set_value(temp_dir.path(), "en-US", "en-US", "-brand-name", "Mail").unwrap();
+    set_value(
+        temp_dir.path(),
+        "de",
+        "en-US",
+        "emails",
+        "{ $count ->\n    [one] Eine ungelesene E-Mail.\n   *[other] { $count } ungelesene E-Mails.\n}",
+    )
+    .unwrap();
+    set_value(temp_dir.path(), "de", "en-US", "login-input", "").unwrap();
+    remove_keys(temp_dir.path(), &["login-input.placeholder".to_string()]).unwrap();
+
+    assert_eq!(
+        "## Inbox
+
+# $count is the number of unread emails
+emails = { $count ->
+    [one] You have one unread email.
+   *[other] You have { $count } unread emails.
+}
+-brand-name = Mail
+login-input = Login
+",
+        fs::read_to_string(temp_dir.path().join("en-US/inbox.ftl")).unwrap()
+    );
+    assert_eq!(
+        "-brand-name = Mailer
+emails = { $count ->
+        [one] Eine ungelesene E-Mail.
+       *[other] { $count } ungelesene E-Mails.
+    }
+",
+        fs::read_to_string(temp_dir.path().join("de/inbox.ftl")).unwrap()
+    );
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let emails = entries.iter().find(|entry| entry.key == "emails").unwrap();
+    assert_eq!(
+        "{ $count ->\n    [one] Eine ungelesene E-Mail.\n   *[other] { $count } ungelesene E-Mails.\n}",
+        emails.translations["de"]
+    );
+}
+
+#[test]
+fn added_language_gets_the_same_files() {
+    let temp_dir = locales_directory();
+    add_language(temp_dir.path(), "fr").unwrap();
+    assert!(add_language(temp_dir.path(), "de").is_err());
+    assert_eq!(
+        "",
+        fs::read_to_string(temp_dir.path().join("fr/inbox.ftl")).unwrap()
+    );
+    set_value(temp_dir.path(), "fr", "en-US", "login-input", "Connexion").unwrap();
+    assert_eq!(
+        "login-input = Connexion\n",
+        fs::read_to_string(temp_dir.path().join("fr/inbox.ftl")).unwrap()
+    );
+}
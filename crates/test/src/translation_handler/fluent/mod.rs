@@ -0,0 +1,4 @@
+// Tests for the translation_handler fluent module
+
+#[cfg(test)]
+pub mod fluent_tests;
@@ -0,0 +1,4 @@
+// Tests for the translation_handler format module
+
+#[cfg(test)]
+pub mod registry_tests;
@@ -0,0 +1,92 @@
+use translation_handler::backend::getter::get_backend_languages;
+use translation_handler::backend::setter::add_backend_language;
+use translation_handler::checker::MessageSyntax;
+use translation_handler::format::FormatRegistry;
+use translation_handler::frontend::layout::FrontendLayout;
+use translation_handler::frontend::setter::add_frontend_language;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn default_registry_knows_frontend_and_backend_locations() {
+    let registry = FormatRegistry::default();
+
+    assert_eq!(MessageSyntax::Icu, registry.get("FE").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::DotNet, registry.get("BE").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Printf, registry.get("ANDROID").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Printf, registry.get("IOS").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Icu, registry.get("YAML").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Fluent, registry.get("FLUENT").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Icu, registry.get("ARB").unwrap().message_syntax());
+    assert_eq!(MessageSyntax::Icu, registry.get("PROPERTIES").unwrap().message_syntax());
+    assert!(registry.get("XX").is_none());
+}
+
+#[test]
+fn default_registry_detects_locations_by_their_files() {
+    let registry = FormatRegistry::default();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let write = |path: &str, content: &str| {
+        let path = temp_dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    };
+    write("res/values/strings.xml", "<resources></resources>");
+    write("config/locales/en.yml", "en:\n  hello: Hello\n");
+    write("l10n/en/main.ftl", "hello = Hello\n");
+    fs::create_dir_all(temp_dir.path().join("empty")).unwrap();
+
+    let detect = |path: &str| registry.detect(&temp_dir.path().join(path));
+    assert_eq!(Some("ANDROID"), detect("res"));
+    assert_eq!(Some("YAML"), detect("config/locales"));
+    assert_eq!(Some("FLUENT"), detect("l10n"));
+    assert_eq!(None, detect("empty"));
+    assert_eq!(None, registry.detect(Path::new("/does/not/exist")));
+}
+
+#[test]
+fn backend_languages_come_from_satellite_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let neutral = temp_dir.path().join("Resources.resx");
+    fs::write(&neutral, "<root></root>").unwrap();
+    fs::write(temp_dir.path().join("Resources.de-DE.resx"), "<root></root>").unwrap();
+    fs::write(temp_dir.path().join("Other.fr-FR.resx"), "<root></root>").unwrap();
+    let path = neutral.to_string_lossy().to_string();
+
+    let satellite = add_backend_language(&path, "nl-NL").unwrap();
+
+    assert!(fs::read_to_string(satellite)
+        .unwrap()
+        .contains("text/microsoft-resx"));
+    assert_eq!(
+        vec!["default", "de-DE", "nl-NL"],
+        get_backend_languages(&path)
+    );
+    assert!(add_backend_language(&path, "nl-NL").is_err());
+}
+
+#[test]
+fn frontend_language_gets_a_locale_file_and_a_languages_entry() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let locales = temp_dir.path().join("locales");
+    fs::create_dir_all(&locales).unwrap();
+    fs::write(locales.join("en-GB.json"), "{\n\t\"hello\": \"Hello\"\n}\n").unwrap();
+    fs::write(
+        locales.join("locales.ts"),
+        "export const locales = {\n  'en-GB': 'English',\n};\n",
+    )
+    .unwrap();
+    let path = temp_dir.path().to_string_lossy().to_string();
+
+    add_frontend_language(&path, "de-DE", &FrontendLayout::default()).unwrap();
+
+    assert_eq!(
+        "{\n}\n",
+        fs::read_to_string(locales.join("de-DE.json")).unwrap()
+    );
+    assert_eq!(
+        "export const locales = {\n  'en-GB': 'English',\n  'de-DE': 'de-DE',\n};\n",
+        fs::read_to_string(locales.join("locales.ts")).unwrap()
+    );
+    assert!(add_frontend_language(&path, "de-DE", &FrontendLayout::default()).is_err());
+}
@@ -309,7 +309,11 @@ fn test_remove_key_from_messages_ts_no_locales() {
     let result =
         remove_key_from_messages_ts(file_path.to_string_lossy().to_string(), keys_to_remove);
 
-    assert!(result.is_err());
+    assert!(result.is_ok());
+    assert_eq!(
+        "export default defineLocales({\n});",
+        read_file_content(&file_path)
+    );
 }
 
 #[test]
@@ -3,4 +3,8 @@
 #[cfg(test)]
 pub mod remover_tests;
 #[cfg(test)]
-pub mod getter_tests;
\ No newline at end of file
+pub mod getter_tests;
+#[cfg(test)]
+pub mod usage_tests;
+#[cfg(test)]
+pub mod setter_tests;
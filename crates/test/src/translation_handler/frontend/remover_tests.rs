@@ -284,6 +284,7 @@ fn test_read_lang_files_in_locales_core_logic() {
             value: json_key.clone(),
             translations,
             in_use: true,
+            ..Default::default()
         };
         
         translation_entries.push(entry);
@@ -380,6 +381,7 @@ fn test_read_lang_files_in_locales_edge_cases() {
         value: "testValue".to_string(),
         translations,
         in_use: true, // Initially true
+        ..Default::default()
     };
     
     // Check if all translations are empty
@@ -391,6 +393,64 @@ fn test_read_lang_files_in_locales_edge_cases() {
     assert!(!should_be_in_use);
 }
 
+#[test]
+fn test_read_lang_files_in_locales_groups_plural_forms() {
+    let temp_dir = setup_test_dir();
+    let path = temp_dir.path().to_string_lossy().to_string();
+
+    fs::create_dir_all(temp_dir.path().join("locales")).expect("Failed to create directories");
+    create_file(
+        &temp_dir.path().join("locales"),
+        "en-GB.json",
+        r#"{ "items_one": "{{count}} item", "items_other": "{{count}} items" }"#,
+    )
+    .unwrap();
+    create_file(
+        &temp_dir.path().join("locales"),
+        "pl-PL.json",
+        r#"{ "items_one": "{{count}} element", "items_other": "{{count}} elementu" }"#,
+    )
+    .unwrap();
+
+    let mut keys = HashMap::new();
+    keys.insert("items".to_string(), "items".to_string());
+    let entries =
+        TranslationHandler::read_lang_files_in_locales(&path, keys, &Default::default()).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert!(entry.in_use);
+    assert_eq!(entry.translations.get("en-GB").unwrap(), "{{count}} items");
+    assert_eq!(entry.plurals["pl-PL"]["one"], "{{count}} element");
+    assert!(!entry.missing_plural_forms.contains_key("en-GB"));
+    assert_eq!(
+        entry.missing_plural_forms["pl-PL"],
+        vec!["few".to_string(), "many".to_string()]
+    );
+}
+
+#[test]
+fn test_read_lang_files_in_locales_reports_malformed_json() {
+    let temp_dir = setup_test_dir();
+    let path = temp_dir.path().to_string_lossy().to_string();
+
+    fs::create_dir_all(temp_dir.path().join("locales")).expect("Failed to create directories");
+    create_file(
+        &temp_dir.path().join("locales"),
+        "en-GB.json",
+        r#"{ "hello": "Hello" "bye": "Bye" }"#,
+    )
+    .unwrap();
+
+    let mut keys = HashMap::new();
+    keys.insert("hello".to_string(), "hello".to_string());
+    let error = TranslationHandler::read_lang_files_in_locales(&path, keys, &Default::default())
+        .unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("en-GB.json"));
+}
+
 #[tokio::test]
 async fn test_get_frontend_translations_integration() {
     // This test verifies that get_frontend_translations correctly combines
@@ -416,6 +476,7 @@ async fn test_get_frontend_translations_integration() {
         value: "hello".to_string(),
         translations: en_translations,
         in_use: true,
+        ..Default::default()
     };
     
     let goodbye_entry = TranslationEntry {
@@ -423,6 +484,7 @@ async fn test_get_frontend_translations_integration() {
         value: "goodbye".to_string(),
         translations: fr_translations,
         in_use: true,
+        ..Default::default()
     };
     
     let expected_entries = vec![hello_entry, goodbye_entry];
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::fs;
+use translation_handler::frontend::json::JsonLayout;
+use translation_handler::frontend::setter::write_plural_forms;
+use translation_handler::plural::PluralCategory;
+
+#[test]
+fn write_plural_forms_drops_variants_of_earlier_edits() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let file = temp_dir.path().join("pl-PL.json");
+    fs::write(
+        &file,
+        r#"{
+  "items_one": "{{count}} element",
+  "items_few": "{{count}} elementy",
+  "items_other": "{{count}} elementu",
+  "other": "Other"
+}
+"#,
+    )
+    .unwrap();
+
+    let forms = HashMap::from([
+        (PluralCategory::One, "{{count}} plik".to_string()),
+        (PluralCategory::Other, "{{count}} plików".to_string()),
+    ]);
+    write_plural_forms(&file, "items", &forms, JsonLayout::Flat).unwrap();
+
+    let content = fs::read_to_string(&file).unwrap();
+    assert!(!content.contains("items_few"));
+    assert!(content.contains(r#""items_one": "{{count}} plik""#));
+    assert!(content.contains(r#""items_other": "{{count}} plików""#));
+    assert!(content.contains(r#""other": "Other""#));
+}
@@ -0,0 +1,113 @@
+use local_storage::stores::translation_store::SourceLocation;
+use std::fs;
+use std::path::Path;
+use translation_handler::frontend::usage::scan_usages;
+use translation_handler::TranslationHandler;
+
+const MESSAGES_TS: &str = "import { defineLocales } from '@/lib/i18n';
+import locales from './locales/locales';
+
+export default defineLocales({
+  title: 'home.title',
+  subtitle: 'home.subtitle',
+  legacy: 'home.legacy',
+}, locales);
+";
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn location() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "messages.ts", MESSAGES_TS);
+    write(
+        root,
+        "locales/en.json",
+        r#"{ "home.title": "Home", "home.subtitle": "", "home.legacy": "Old" }"#,
+    );
+    write(
+        root,
+        "components/header.tsx",
+        "import messages from '../messages';\n\nexport const Header = () => (\n  <h1>{t(messages.title)}</h1>\n);\n",
+    );
+    write(
+        root,
+        "components/intro.js",
+        "import m from '../messages.ts';\nconst { subtitle } = m;\n",
+    );
+    write(
+        root,
+        "node_modules/pkg/index.js",
+        "import messages from '../../messages';\nmessages.legacy;\n",
+    );
+    temp_dir
+}
+
+#[tokio::test]
+async fn in_use_follows_references_in_the_sources() {
+    let temp_dir = location();
+    let report = scan_usages(temp_dir.path(), &temp_dir.path().join("messages.ts")).unwrap();
+    let title = &report.keys["title"];
+    assert_eq!(1, title.len());
+    assert_eq!(temp_dir.path().join("components/header.tsx"), title[0].file);
+    assert_eq!((4, 19), (title[0].line, title[0].column));
+    assert_eq!(1, report.count("subtitle"));
+    assert_eq!(0, report.count("legacy"));
+
+    let path = temp_dir.path().to_string_lossy().to_string();
+    let entries = TranslationHandler::get_frontend_translations(&path, &Default::default())
+        .await
+        .unwrap();
+    let in_use = |key: &str| {
+        let entry = entries.iter().find(|entry| entry.key == key).unwrap();
+        (entry.in_use, entry.possibly_used)
+    };
+    // Empty values don't make a referenced key unused
+    assert_eq!((true, false), in_use("subtitle"));
+    assert_eq!((true, false), in_use("title"));
+    assert_eq!((false, false), in_use("legacy"));
+    let title = entries.iter().find(|entry| entry.key == "title").unwrap();
+    assert_eq!(
+        vec![SourceLocation {
+            file: "components/header.tsx".to_string(),
+            line: 4,
+            column: 19,
+        }],
+        title.usages
+    );
+
+    write(
+        temp_dir.path(),
+        "components/list.tsx",
+        "import messages from '../messages';\nexport const label = (key) => t(messages[key]);\n",
+    );
+    let entries = TranslationHandler::get_frontend_translations(&path, &Default::default())
+        .await
+        .unwrap();
+    let legacy = entries.iter().find(|entry| entry.key == "legacy").unwrap();
+    assert!(!legacy.in_use && legacy.possibly_used);
+}
+
+#[tokio::test]
+async fn later_scans_see_edited_and_deleted_sources() {
+    let temp_dir = location();
+    let root = temp_dir.path();
+    let messages_file = root.join("messages.ts");
+    let report = scan_usages(root, &messages_file).unwrap();
+    assert!(report.is_used("title") && report.is_used("subtitle"));
+
+    write(
+        root,
+        "components/header.tsx",
+        "import { messages } from '../messages';\n\nexport const Header = () => <h1>{t(messages.legacy)}</h1>;\n",
+    );
+    fs::remove_file(root.join("components/intro.js")).unwrap();
+    let report = scan_usages(root, &messages_file).unwrap();
+    assert!(report.is_used("legacy"));
+    assert!(!report.is_used("title"));
+    assert!(!report.is_used("subtitle"));
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::fs;
+use translation_handler::mobile::android::{
+    add_language, get_languages, get_translations, remove_names, strings_file, write_values,
+};
+use translation_handler::mobile::{MobilePlatform, MobileValue};
+use translation_handler::plural::PluralCategory;
+
+fn resource_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let values = temp_dir.path().join("values");
+    fs::create_dir_all(&values).unwrap();
+    fs::write(
+        values.join("strings.xml"),
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string name="app_name" translatable="false">Notes</string>
+    <string name="save">Save</string>
+    <plurals name="notes">
+        <item quantity="one">%d note</item>
+        <item quantity="other">%d notes</item>
+    </plurals>
+</resources>
+"#,
+    )
+    .unwrap();
+    let german = temp_dir.path().join("values-de");
+    fs::create_dir_all(&german).unwrap();
+    fs::write(
+        german.join("strings.xml"),
+        "<resources>\n  <string name=\"save\">Speichern</string>\n</resources>\n",
+    )
+    .unwrap();
+    fs::create_dir_all(temp_dir.path().join("values-night")).unwrap();
+    temp_dir
+}
+
+#[test]
+fn res_directory_is_read_as_entries() {
+    let temp_dir = resource_directory();
+    assert_eq!(
+        Some(MobilePlatform::Android),
+        MobilePlatform::detect(temp_dir.path())
+    );
+    assert_eq!(vec!["default", "de"], get_languages(temp_dir.path()));
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(vec!["save", "notes"], keys);
+    assert_eq!("Speichern", entries[0].translations["de"]);
+    assert_eq!("%d notes", entries[1].translations["default"]);
+    assert_eq!("", entries[1].translations["de"]);
+    assert_eq!("%d note", entries[1].plurals["default"]["one"]);
+}
+
+#[test]
+fn values_are_written_per_language() {
+    let temp_dir = resource_directory();
+    add_language(temp_dir.path(), "pt-BR").unwrap();
+    let notes = MobileValue::Plural(BTreeMap::from([
+        (PluralCategory::One, "%d nota".to_string()),
+        (PluralCategory::Other, "%d notas".to_string()),
+    ]));
+    write_values(
+        &strings_file(temp_dir.path(), "pt-BR"),
+        &[("notes".to_string(), notes)],
+    )
+    .unwrap();
+    assert!(remove_names(&strings_file(temp_dir.path(), "de"), &["save".to_string()]).unwrap());
+
+    assert_eq!(
+        vec!["default", "de", "pt-BR"],
+        get_languages(temp_dir.path())
+    );
+    assert_eq!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n    <plurals name=\"notes\">\n        <item quantity=\"one\">%d nota</item>\n        <item quantity=\"other\">%d notas</item>\n    </plurals>\n</resources>\n",
+        fs::read_to_string(temp_dir.path().join("values-pt-rBR/strings.xml")).unwrap()
+    );
+    assert_eq!(
+        "<resources>\n</resources>\n",
+        fs::read_to_string(temp_dir.path().join("values-de/strings.xml")).unwrap()
+    );
+}
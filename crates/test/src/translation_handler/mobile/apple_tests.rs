@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fs;
+use translation_handler::mobile::apple::{
+    add_language, development_language, get_languages, get_translations, remove_keys, set_values,
+};
+use translation_handler::mobile::{MobilePlatform, MobileValue};
+use translation_handler::plural::PluralCategory;
+
+const STRINGSDICT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>notes</key>
+	<dict>
+		<key>NSStringLocalizedFormatKey</key>
+		<string>%#@count@</string>
+		<key>count</key>
+		<dict>
+			<key>NSStringFormatSpecTypeKey</key>
+			<string>NSStringPluralRuleType</string>
+			<key>NSStringFormatValueTypeKey</key>
+			<string>d</string>
+			<key>one</key>
+			<string>%d note</string>
+			<key>other</key>
+			<string>%d notes</string>
+		</dict>
+	</dict>
+</dict>
+</plist>
+"#;
+
+fn lproj_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let english = temp_dir.path().join("en.lproj");
+    fs::create_dir_all(&english).unwrap();
+    fs::write(
+        english.join("Localizable.strings"),
+        "/* Save button */\n\"save\" = \"Save\";\n",
+    )
+    .unwrap();
+    fs::write(english.join("Localizable.stringsdict"), STRINGSDICT).unwrap();
+    let german = temp_dir.path().join("de.lproj");
+    fs::create_dir_all(&german).unwrap();
+    fs::write(
+        german.join("Localizable.strings"),
+        "\"save\" = \"Sichern\";\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+#[test]
+fn lproj_folders_are_read_with_stringsdict_plurals() {
+    let temp_dir = lproj_directory();
+    assert_eq!(
+        Some(MobilePlatform::Apple),
+        MobilePlatform::detect(temp_dir.path())
+    );
+    assert_eq!(
+        "en",
+        development_language(temp_dir.path(), "en-US").unwrap()
+    );
+    assert_eq!(vec!["de", "en"], get_languages(temp_dir.path()).unwrap());
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let save = entries.iter().find(|entry| entry.key == "save").unwrap();
+    assert_eq!("Sichern", save.translations["de"]);
+    let notes = entries.iter().find(|entry| entry.key == "notes").unwrap();
+    assert_eq!("%d notes", notes.translations["en"]);
+    assert_eq!("%d note", notes.plurals["en"]["one"]);
+}
+
+#[test]
+fn plural_values_go_to_the_stringsdict() {
+    let temp_dir = lproj_directory();
+    let forms = BTreeMap::from([
+        (PluralCategory::One, "%d Notiz".to_string()),
+        (PluralCategory::Other, "%d Notizen".to_string()),
+    ]);
+    set_values(
+        temp_dir.path(),
+        "de",
+        &[("notes".to_string(), MobileValue::Plural(forms))],
+    )
+    .unwrap();
+    remove_keys(temp_dir.path(), &["save".to_string()]).unwrap();
+    add_language(temp_dir.path(), "fr").unwrap();
+
+    assert_eq!(
+        "",
+        fs::read_to_string(temp_dir.path().join("de.lproj/Localizable.strings")).unwrap()
+    );
+    assert_eq!(
+        STRINGSDICT
+            .replace("%d note<", "%d Notiz<")
+            .replace("%d notes<", "%d Notizen<"),
+        fs::read_to_string(temp_dir.path().join("de.lproj/Localizable.stringsdict")).unwrap()
+    );
+    let entries = get_translations(temp_dir.path()).unwrap();
+    assert_eq!(1, entries.len());
+    assert_eq!("", entries[0].translations["fr"]);
+    assert!(entries[0].missing_plural_forms.get("de").is_none());
+}
+
+#[test]
+fn string_catalog_keys_are_their_own_source_text() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let catalog = temp_dir.path().join("Localizable.xcstrings");
+    fs::write(
+        &catalog,
+        "{\n  \"sourceLanguage\" : \"en\",\n  \"strings\" : {\n    \"Done\" : {\n\n    }\n  },\n  \"version\" : \"1.0\"\n}\n",
+    )
+    .unwrap();
+    assert_eq!(
+        Some(MobilePlatform::Apple),
+        MobilePlatform::detect(&catalog)
+    );
+
+    set_values(
+        &catalog,
+        "de",
+        &[("Done".to_string(), MobileValue::Text("Fertig".to_string()))],
+    )
+    .unwrap();
+
+    let entries = get_translations(&catalog).unwrap();
+    assert_eq!("Done", entries[0].translations["en"]);
+    assert_eq!("Fertig", entries[0].translations["de"]);
+    assert!(add_language(&catalog, "fr").is_err());
+}
@@ -0,0 +1,6 @@
+// Tests for the translation_handler mobile module
+
+#[cfg(test)]
+pub mod android_tests;
+#[cfg(test)]
+pub mod apple_tests;
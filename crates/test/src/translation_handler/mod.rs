@@ -3,4 +3,18 @@
 #[cfg(test)]
 pub mod frontend;
 #[cfg(test)]
-pub mod backend;
\ No newline at end of file
+pub mod backend;
+#[cfg(test)]
+pub mod format;
+#[cfg(test)]
+pub mod exchange;
+#[cfg(test)]
+pub mod mobile;
+#[cfg(test)]
+pub mod yaml;
+#[cfg(test)]
+pub mod fluent;
+#[cfg(test)]
+pub mod arb;
+#[cfg(test)]
+pub mod properties;
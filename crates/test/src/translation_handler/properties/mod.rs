@@ -0,0 +1,4 @@
+// Tests for the translation_handler properties module
+
+#[cfg(test)]
+pub mod properties_tests;
@@ -0,0 +1,87 @@
+use std::fs;
+use translation_handler::checker::{check_entries, MessageSyntax};
+use translation_handler::properties::{
+    add_language, get_languages, get_translations, is_location, remove_keys, set_value,
+    source_language,
+};
+
+const BASE: &str = "# Messages of the web shop
+
+# Shown after login
+welcome = Welcome, {0}!
+cart.items = You have {0} items \\
+    in your cart.
+";
+
+fn bundle_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(temp_dir.path().join("messages.properties"), BASE).unwrap();
+    fs::write(
+        temp_dir.path().join("messages_de.properties"),
+        "welcome = Willkommen, {0}!\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("messages_pt_BR.properties"),
+        "welcome = Bem-vindo, {0}!\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+#[test]
+fn bundle_files_are_read_by_language() {
+    let temp_dir = bundle_directory();
+    assert!(is_location(temp_dir.path()));
+    assert_eq!(
+        vec!["de", "default", "pt_BR"],
+        get_languages(temp_dir.path())
+    );
+    assert_eq!("default", source_language(temp_dir.path(), "en"));
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(vec!["welcome", "cart.items"], keys);
+    assert_eq!(
+        "You have {0} items in your cart.",
+        entries[1].translations["default"]
+    );
+    assert_eq!("", entries[1].translations["de"]);
+    assert_eq!("Willkommen, {0}!", entries[0].translations["de"]);
+    assert!(check_entries(&entries[..1], "default", MessageSyntax::Icu).is_empty());
+}
+
+#[test]
+fn edits_keep_comments_and_create_missing_files() {
+    let temp_dir = bundle_directory();
+    set_value(temp_dir.path(), "default", "welcome", "Hello, {0}!").unwrap();
+    set_value(temp_dir.path(), "de", "cart.items", "{0} Artikel im Korb").unwrap();
+    set_value(temp_dir.path(), "fr", "welcome", "Bienvenue, {0} !").unwrap();
+    remove_keys(temp_dir.path(), &["cart.items".to_string()]).unwrap();
+
+    assert_eq!(
+        "# Messages of the web shop\n\n# Shown after login\nwelcome = Hello, {0}!\n",
+        fs::read_to_string(temp_dir.path().join("messages.properties")).unwrap()
+    );
+    assert_eq!(
+        "welcome = Willkommen, {0}!\n",
+        fs::read_to_string(temp_dir.path().join("messages_de.properties")).unwrap()
+    );
+    assert_eq!(
+        "welcome=Bienvenue, {0} !\n",
+        fs::read_to_string(temp_dir.path().join("messages_fr.properties")).unwrap()
+    );
+}
+
+#[test]
+fn languages_are_added_for_every_bundle() {
+    let temp_dir = bundle_directory();
+    fs::write(temp_dir.path().join("errors.properties"), "").unwrap();
+    add_language(temp_dir.path(), "zh-Hant").unwrap();
+    assert!(temp_dir
+        .path()
+        .join("messages_zh_Hant.properties")
+        .is_file());
+    assert!(temp_dir.path().join("errors_zh_Hant.properties").is_file());
+    assert!(add_language(temp_dir.path(), "de").is_err());
+}
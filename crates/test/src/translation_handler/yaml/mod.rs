@@ -0,0 +1,4 @@
+// Tests for the translation_handler yaml module
+
+#[cfg(test)]
+pub mod yaml_tests;
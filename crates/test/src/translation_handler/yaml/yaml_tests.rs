@@ -0,0 +1,99 @@
+use std::fs;
+use translation_handler::yaml::{
+    add_language, get_languages, get_translations, is_location, remove_keys, set_value,
+    source_language,
+};
+
+const ENGLISH: &str = "# Application strings
+en:
+  hello: Hello
+  # Shown on the dashboard
+  dashboard:
+    title: 'Dashboard'
+    welcome: \"Welcome, %{name}\"
+";
+
+fn locales_directory() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(temp_dir.path().join("en.yml"), ENGLISH).unwrap();
+    fs::write(
+        temp_dir.path().join("de.yml"),
+        "de:\n  dashboard:\n    title: Übersicht\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("README.md"), "# Locales\n").unwrap();
+    temp_dir
+}
+
+#[test]
+fn locale_files_are_read_as_dotted_keys() {
+    let temp_dir = locales_directory();
+    assert!(is_location(temp_dir.path()));
+    assert_eq!(vec!["de", "en"], get_languages(temp_dir.path()));
+    assert_eq!("en", source_language(temp_dir.path(), "en-US"));
+
+    let entries = get_translations(temp_dir.path()).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(vec!["dashboard.title", "hello", "dashboard.welcome"], keys);
+    assert_eq!("Übersicht", entries[0].translations["de"]);
+    assert_eq!("Dashboard", entries[0].translations["en"]);
+    assert_eq!("", entries[1].translations["de"]);
+    assert_eq!("Welcome, %{name}", entries[2].translations["en"]);
+}
+
+#[test]
+fn writes_keep_comments_and_key_order() {
+    let temp_dir = locales_directory();
+    set_value(temp_dir.path(), "en", "dashboard.title", "Overview").unwrap();
+    set_value(temp_dir.path(), "en", "errors.messages.blank", "can't be blank").unwrap();
+    set_value(temp_dir.path(), "de", "hello", "Hallo").unwrap();
+    assert!(set_value(temp_dir.path(), "en", "dashboard", "Dashboard").is_err());
+    remove_keys(temp_dir.path(), &["dashboard.welcome".to_string()]).unwrap();
+
+    assert_eq!(
+        "# Application strings
+en:
+  hello: Hello
+  # Shown on the dashboard
+  dashboard:
+    title: 'Overview'
+  errors:
+    messages:
+      blank: can't be blank
+",
+        fs::read_to_string(temp_dir.path().join("en.yml")).unwrap()
+    );
+    assert_eq!(
+        "de:\n  dashboard:\n    title: Übersicht\n  hello: Hallo\n",
+        fs::read_to_string(temp_dir.path().join("de.yml")).unwrap()
+    );
+
+    remove_keys(temp_dir.path(), &["dashboard.title".to_string()]).unwrap();
+    assert_eq!(
+        "de:\n  hello: Hallo\n",
+        fs::read_to_string(temp_dir.path().join("de.yml")).unwrap()
+    );
+}
+
+#[test]
+fn added_language_gets_its_root_key() {
+    let temp_dir = locales_directory();
+    add_language(temp_dir.path(), "no").unwrap();
+    assert!(add_language(temp_dir.path(), "de").is_err());
+    assert_eq!(
+        "'no':\n",
+        fs::read_to_string(temp_dir.path().join("no.yml")).unwrap()
+    );
+
+    set_value(temp_dir.path(), "no", "dashboard.title", "Oversikt").unwrap();
+    set_value(temp_dir.path(), "fr", "hello", "Bonjour").unwrap();
+    assert_eq!(
+        "'no':\n  dashboard:\n    title: Oversikt\n",
+        fs::read_to_string(temp_dir.path().join("no.yml")).unwrap()
+    );
+    assert_eq!(
+        "fr:\n  hello: Bonjour\n",
+        fs::read_to_string(temp_dir.path().join("fr.yml")).unwrap()
+    );
+    assert_eq!(vec!["de", "en", "fr", "no"], get_languages(temp_dir.path()));
+}
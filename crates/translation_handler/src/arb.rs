@@ -0,0 +1,251 @@
+use crate::frontend::json::LocaleFile;
+use crate::locale::split_language_suffix;
+use local_storage::stores::translation_store::TranslationEntry;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tag the location is stored with, which picks its translation format.
+pub const TAG: &str = "ARB";
+
+const LOCALE_KEY: &str = "@@locale";
+/// Prefix of new files when the directory has none to copy it from, as `flutter gen-l10n`
+/// expects by default.
+const DEFAULT_PREFIX: &str = "app";
+
+/// An `<prefix>_<language>.arb` file of a location directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArbFile {
+    pub language: String,
+    pub path: PathBuf,
+}
+
+pub fn arb_files(directory: &Path) -> Vec<ArbFile> {
+    let mut files: Vec<ArbFile> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "arb"))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let (_, language) = split_language_suffix(stem)?;
+            Some(ArbFile {
+                language: language.to_string(),
+                path: path.clone(),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+pub fn is_location(path: &Path) -> bool {
+    path.is_dir() && !arb_files(path).is_empty()
+}
+
+pub fn get_languages(path: &Path) -> Vec<String> {
+    arb_files(path)
+        .into_iter()
+        .map(|file| file.language)
+        .collect()
+}
+
+/// The template language: the default language of the settings, its primary subtag or
+/// English, whichever has a file.
+pub fn source_language(path: &Path, default_language: &str) -> String {
+    let languages = get_languages(path);
+    let underscored = default_language.replace('-', "_");
+    let primary = default_language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default();
+    let language = [default_language, &underscored, primary, "en"]
+        .into_iter()
+        .find(|candidate| languages.iter().any(|language| language == candidate))
+        .or(languages.first().map(String::as_str))
+        .unwrap_or(default_language)
+        .to_string();
+    language
+}
+
+/// Flattens an `@key` metadata object into dotted field paths such as
+/// `placeholders.count.type`.
+fn flatten_metadata(value: &Value, prefix: &str, fields: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(object) => {
+            for (name, child) in object {
+                let path = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten_metadata(child, &path, fields);
+            }
+        }
+        Value::String(text) => {
+            fields.entry(prefix.to_string()).or_insert(text.clone());
+        }
+        other => {
+            fields
+                .entry(prefix.to_string())
+                .or_insert(other.to_string());
+        }
+    }
+}
+
+/// Entries of every language in the order keys first appear. The `@key` metadata becomes
+/// the description and metadata of the entry.
+pub fn get_translations(path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    let files = arb_files(path);
+    let mut entries: Vec<TranslationEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut metadata: Vec<(String, Value)> = Vec::new();
+    for file in &files {
+        let arb = LocaleFile::read(&file.path)?;
+        for (key, value) in arb.data {
+            if let Some(name) = key.strip_prefix('@') {
+                if !name.starts_with('@') {
+                    metadata.push((name.to_string(), value));
+                }
+                continue;
+            }
+            let Value::String(text) = value else {
+                continue;
+            };
+            let position = *positions.entry(key.clone()).or_insert_with(|| {
+                entries.push(TranslationEntry {
+                    key: key.clone(),
+                    value: key.clone(),
+                    in_use: true,
+                    ..Default::default()
+                });
+                entries.len() - 1
+            });
+            entries[position]
+                .translations
+                .insert(file.language.clone(), text);
+        }
+    }
+    for (key, value) in metadata {
+        let Some(entry) = positions.get(&key).map(|position| &mut entries[*position]) else {
+            continue;
+        };
+        if entry.description.is_none() {
+            entry.description = value
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+        if let Value::Object(mut fields) = value {
+            fields.shift_remove("description");
+            flatten_metadata(&Value::Object(fields), "", &mut entry.metadata);
+        }
+    }
+    for entry in &mut entries {
+        for file in &files {
+            entry.translations.entry(file.language.clone()).or_default();
+        }
+    }
+    Ok(entries)
+}
+
+pub fn contains_key(path: &Path, key: &str) -> Result<bool, io::Error> {
+    for file in arb_files(path) {
+        if LocaleFile::read(&file.path)?.data.contains_key(key) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// A new file named like the existing ones, with the `@@locale` key Flutter reads.
+fn new_file(path: &Path, language: &str) -> Result<(PathBuf, LocaleFile), io::Error> {
+    let prefix = arb_files(path)
+        .first()
+        .and_then(|file| {
+            let stem = file.path.file_stem()?.to_str()?;
+            split_language_suffix(stem).map(|(prefix, _)| prefix.to_string())
+        })
+        .unwrap_or_else(|| DEFAULT_PREFIX.to_string());
+    let stem = format!("{}_{}", prefix, language);
+    if split_language_suffix(&stem) != Some((prefix.as_str(), language)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} is not a language code ARB file names can have",
+                language
+            ),
+        ));
+    }
+    let file = path.join(format!("{}.arb", stem));
+    let content = format!(
+        "{{\n  \"{}\": {}\n}}\n",
+        LOCALE_KEY,
+        Value::String(language.to_string())
+    );
+    Ok((file, LocaleFile::parse(&content)?))
+}
+
+/// Sets the value of `key` in the file of `language`, keeping the key in its place. New keys
+/// are added at the end, and a missing file is created.
+pub fn set_value(path: &Path, language: &str, key: &str, value: &str) -> Result<(), io::Error> {
+    if key.starts_with('@') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{}` is a metadata key", key),
+        ));
+    }
+    let (file, mut arb) = match arb_files(path)
+        .into_iter()
+        .find(|file| file.language == language)
+    {
+        Some(file) => {
+            let arb = LocaleFile::read(&file.path)?;
+            (file.path, arb)
+        }
+        None => new_file(path, language)?,
+    };
+    match arb.data.get_mut(key) {
+        Some(existing) => *existing = Value::String(value.to_string()),
+        None => {
+            arb.data
+                .insert(key.to_string(), Value::String(value.to_string()));
+        }
+    }
+    arb.write(&file)
+}
+
+/// Removes keys with their `@key` metadata from every file.
+pub fn remove_keys(path: &Path, keys: &[String]) -> Result<(), io::Error> {
+    for file in arb_files(path) {
+        let mut arb = LocaleFile::read(&file.path)?;
+        let mut removed = false;
+        for key in keys {
+            removed |= arb.data.shift_remove(key).is_some();
+            removed |= arb.data.shift_remove(&format!("@{}", key)).is_some();
+        }
+        if removed {
+            arb.write(&file.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the file of `language`, written with underscores as in `app_pt_BR.arb`.
+pub fn add_language(path: &Path, language: &str) -> Result<(), io::Error> {
+    let language = &language.replace('-', "_");
+    if get_languages(path)
+        .iter()
+        .any(|existing| existing == language)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has a file for {}", path.display(), language),
+        ));
+    }
+    let (file, arb) = new_file(path, language)?;
+    arb.write(&file)
+}
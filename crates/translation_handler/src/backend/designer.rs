@@ -0,0 +1,378 @@
+use crate::backend::discovery::normalize;
+use crate::backend::xml::reader::{ItemAction, ResourceItem, ResxResource};
+use crate::backend::xml::XmlHandler;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Comments of longer values are cut off the way ResXFileCodeGenerator does.
+const MAX_COMMENT_LENGTH: usize = 512;
+
+/// Accessibility of a generated resource class and its properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessModifier {
+    Public,
+    Internal,
+}
+
+impl AccessModifier {
+    /// The access modifier of a Visual Studio custom tool, or `None` for other tools.
+    pub fn from_generator(generator: &str) -> Option<Self> {
+        match generator.trim() {
+            "PublicResXFileCodeGenerator" => Some(AccessModifier::Public),
+            "ResXFileCodeGenerator" => Some(AccessModifier::Internal),
+            _ => None,
+        }
+    }
+
+    fn keyword(self) -> &'static str {
+        match self {
+            AccessModifier::Public => "public",
+            AccessModifier::Internal => "internal",
+        }
+    }
+}
+
+/// What the class generated for a resx file is called and where its resources are embedded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignerClass {
+    pub namespace: String,
+    pub class_name: String,
+    /// Manifest name of the embedded resources, such as `MyApp.Properties.Resources`
+    pub resource_name: String,
+    pub access: AccessModifier,
+}
+
+/// Turns a resource name into a C# identifier the way StronglyTypedResourceBuilder does:
+/// characters that can't be in an identifier become `_`, and a leading digit gets a `_` prefix.
+pub fn identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier
+        .chars()
+        .next()
+        .map_or(true, |c| c.is_ascii_digit())
+    {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+/// A namespace made of identifiers, as folder names can have spaces or dashes.
+fn namespace_identifier(namespace: &str) -> String {
+    namespace
+        .split('.')
+        .filter(|part| !part.is_empty())
+        .map(identifier)
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+fn escape_comment(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The summary lines of a string property, with every line of the value on its own `///` line.
+fn string_summary(value: &str, newline: &str, indent: &str) -> String {
+    let mut text: String = value.chars().take(MAX_COMMENT_LENGTH).collect();
+    if value.chars().count() > MAX_COMMENT_LENGTH {
+        text.push_str(" [rest of string was truncated]");
+    }
+    let text = escape_comment(&text).replace("\r\n", "\n");
+    let lines: Vec<&str> = text.split('\n').collect();
+    format!(
+        "{indent}///   Looks up a localized string similar to {}.",
+        lines.join(&format!("{newline}{indent}///"))
+    )
+}
+
+/// The C# type of a resource that is not a string, from its assembly-qualified type name.
+fn type_keyword(type_name: &str) -> String {
+    let name = type_name.split(',').next().unwrap_or_default().trim();
+    match name {
+        "System.Byte[]" => "byte[]".to_string(),
+        "System.String" => "string".to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// The source of a `*.Designer.cs` file, as ResXFileCodeGenerator writes it. Properties are
+/// sorted by name and names that are not valid or clash after conversion are left out, as the
+/// generator does.
+pub fn generate_designer(
+    class: &DesignerClass,
+    resources: &[ResxResource],
+    newline: &str,
+) -> String {
+    let access = class.access.keyword();
+    let class_name = &class.class_name;
+    let mut resources: Vec<&ResxResource> = resources
+        .iter()
+        .filter(|resource| !resource.name.is_empty())
+        .collect();
+    resources.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let mut taken: Vec<String> = vec!["ResourceManager".to_string(), "Culture".to_string()];
+
+    let mut lines: Vec<String> = vec![
+        "//------------------------------------------------------------------------------".into(),
+        "// <auto-generated>".into(),
+        "//     This code was generated by a tool.".into(),
+        "//     Runtime Version:4.0.30319.42000".into(),
+        "//".into(),
+        "//     Changes to this file may cause incorrect behavior and will be lost if".into(),
+        "//     the code is regenerated.".into(),
+        "// </auto-generated>".into(),
+        "//------------------------------------------------------------------------------".into(),
+        String::new(),
+    ];
+    let body_indent = if class.namespace.is_empty() {
+        ""
+    } else {
+        "    "
+    };
+    if !class.namespace.is_empty() {
+        lines.push(format!("namespace {} {{", class.namespace));
+    }
+    let i1 = body_indent;
+    let i2 = format!("{}    ", body_indent);
+    let i3 = format!("{}        ", body_indent);
+    let i4 = format!("{}            ", body_indent);
+    let i5 = format!("{}                ", body_indent);
+    lines.extend([
+        format!("{i1}using System;"),
+        i1.to_string(),
+        i1.to_string(),
+        format!("{i1}/// <summary>"),
+        format!("{i1}///   A strongly-typed resource class, for looking up localized strings, etc."),
+        format!("{i1}/// </summary>"),
+        format!("{i1}// This class was auto-generated by the StronglyTypedResourceBuilder"),
+        format!("{i1}// class via a tool like ResGen or Visual Studio."),
+        format!("{i1}// To add or remove a member, edit your .ResX file then rerun ResGen"),
+        format!("{i1}// with the /str option, or rebuild your VS project."),
+        format!("{i1}[global::System.CodeDom.Compiler.GeneratedCodeAttribute(\"System.Resources.Tools.StronglyTypedResourceBuilder\", \"17.0.0.0\")]"),
+        format!("{i1}[global::System.Diagnostics.DebuggerNonUserCodeAttribute()]"),
+        format!("{i1}[global::System.Runtime.CompilerServices.CompilerGeneratedAttribute()]"),
+        format!("{i1}{access} class {class_name} {{"),
+        i2.to_string(),
+        format!("{i2}private static global::System.Resources.ResourceManager resourceMan;"),
+        i2.to_string(),
+        format!("{i2}private static global::System.Globalization.CultureInfo resourceCulture;"),
+        i2.to_string(),
+        format!("{i2}[global::System.Diagnostics.CodeAnalysis.SuppressMessageAttribute(\"Microsoft.Performance\", \"CA1811:AvoidUncalledPrivateCode\")]"),
+        format!("{i2}internal {class_name}() {{"),
+        format!("{i2}}}"),
+        i2.to_string(),
+        format!("{i2}/// <summary>"),
+        format!("{i2}///   Returns the cached ResourceManager instance used by this class."),
+        format!("{i2}/// </summary>"),
+        format!("{i2}[global::System.ComponentModel.EditorBrowsableAttribute(global::System.ComponentModel.EditorBrowsableState.Advanced)]"),
+        format!("{i2}{access} static global::System.Resources.ResourceManager ResourceManager {{"),
+        format!("{i3}get {{"),
+        format!("{i4}if (object.ReferenceEquals(resourceMan, null)) {{"),
+        format!("{i5}global::System.Resources.ResourceManager temp = new global::System.Resources.ResourceManager(\"{}\", typeof({class_name}).Assembly);", class.resource_name),
+        format!("{i5}resourceMan = temp;"),
+        format!("{i4}}}"),
+        format!("{i4}return resourceMan;"),
+        format!("{i3}}}"),
+        format!("{i2}}}"),
+        i2.to_string(),
+        format!("{i2}/// <summary>"),
+        format!("{i2}///   Overrides the current thread's CurrentUICulture property for all"),
+        format!("{i2}///   resource lookups using this strongly typed resource class."),
+        format!("{i2}/// </summary>"),
+        format!("{i2}[global::System.ComponentModel.EditorBrowsableAttribute(global::System.ComponentModel.EditorBrowsableState.Advanced)]"),
+        format!("{i2}{access} static global::System.Globalization.CultureInfo Culture {{"),
+        format!("{i3}get {{"),
+        format!("{i4}return resourceCulture;"),
+        format!("{i3}}}"),
+        format!("{i3}set {{"),
+        format!("{i4}resourceCulture = value;"),
+        format!("{i3}}}"),
+        format!("{i2}}}"),
+    ]);
+
+    for resource in resources {
+        let property = identifier(&resource.name);
+        if taken.contains(&property) || property == *class_name {
+            continue;
+        }
+        taken.push(property.clone());
+        let name = resource.name.replace('\\', "\\\\").replace('"', "\\\"");
+        lines.push(i2.to_string());
+        lines.push(format!("{i2}/// <summary>"));
+        match resource.type_name.as_deref().map(type_keyword) {
+            Some(type_keyword) if type_keyword != "string" => {
+                lines.push(format!(
+                    "{i2}///   Looks up a localized resource of type {}.",
+                    type_keyword
+                ));
+                lines.push(format!("{i2}/// </summary>"));
+                lines.push(format!("{i2}{access} static {type_keyword} {property} {{"));
+                lines.push(format!("{i3}get {{"));
+                lines.push(format!(
+                    "{i4}object obj = ResourceManager.GetObject(\"{name}\", resourceCulture);"
+                ));
+                lines.push(format!("{i4}return (({type_keyword})(obj));"));
+            }
+            _ => {
+                lines.push(string_summary(&resource.value, newline, &i2));
+                lines.push(format!("{i2}/// </summary>"));
+                lines.push(format!("{i2}{access} static string {property} {{"));
+                lines.push(format!("{i3}get {{"));
+                lines.push(format!(
+                    "{i4}return ResourceManager.GetString(\"{name}\", resourceCulture);"
+                ));
+            }
+        }
+        lines.push(format!("{i3}}}"));
+        lines.push(format!("{i2}}}"));
+    }
+    lines.push(format!("{i1}}}"));
+    if !class.namespace.is_empty() {
+        lines.push("}".to_string());
+    }
+    let mut source = lines.join(newline);
+    source.push_str(newline);
+    source
+}
+
+/// Folder names between the csproj file and the resx file, which namespaces and embedded
+/// resource names include.
+fn relative_folders(project_directory: &Path, resx_path: &Path) -> Vec<String> {
+    resx_path
+        .parent()
+        .and_then(|parent| parent.strip_prefix(project_directory).ok())
+        .map(|relative| {
+            relative
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The `<RootNamespace>` of a csproj file, or the project name when it has none.
+pub(crate) fn root_namespace(project: &Path, project_xml: &str) -> String {
+    XmlHandler::get_root_namespace(project_xml).unwrap_or_else(|| {
+        project
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    })
+}
+
+/// Folder names an embedded resource name includes: those between the csproj file and the
+/// resx file, or those of the item's `Link` for files that live outside of the project folder.
+pub(crate) fn resource_folders(
+    project_directory: &Path,
+    item: &ResourceItem,
+    resx_path: &Path,
+) -> Vec<String> {
+    // Linked files are named after the folders they are shown in, not where they live
+    let logical_path = match &item.link {
+        Some(link) => project_directory.join(link.replace('\\', "/")),
+        None => resx_path.to_path_buf(),
+    };
+    relative_folders(project_directory, &logical_path)
+}
+
+/// The csproj file in `resx_path`'s folder or above it that embeds the resx file, with the
+/// item that does.
+fn owning_project(resx_path: &Path) -> Result<Option<(PathBuf, ResourceItem)>, io::Error> {
+    for directory in resx_path.ancestors().skip(1) {
+        let Ok(dir_entries) = fs::read_dir(directory) else {
+            continue;
+        };
+        let mut projects: Vec<PathBuf> = dir_entries
+            .flatten()
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csproj"))
+            .collect();
+        projects.sort();
+        for project in projects {
+            let xml = fs::read_to_string(&project)?;
+            let item = XmlHandler::get_resource_items(&xml)
+                .into_iter()
+                .find(|item| {
+                    item.action != ItemAction::Remove
+                        && normalize(&directory.join(item.path.replace('\\', "/")))
+                            == normalize(resx_path)
+                });
+            if let Some(item) = item {
+                return Ok(Some((project, item)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Regenerates the `*.Designer.cs` file of a neutral resx file when the csproj that embeds it
+/// uses a ResXFileCodeGenerator custom tool. Returns the path of the written file, or `None`
+/// when the resx file has no code generator.
+pub fn regenerate_designer(resx_path: &Path) -> Result<Option<PathBuf>, io::Error> {
+    let Some((project, item)) = owning_project(resx_path)? else {
+        return Ok(None);
+    };
+    let Some(access) = item
+        .generator
+        .as_deref()
+        .and_then(AccessModifier::from_generator)
+    else {
+        return Ok(None);
+    };
+    let project_directory = project.parent().unwrap_or(Path::new(""));
+    let project_xml = fs::read_to_string(&project)?;
+    let root_namespace = root_namespace(&project, &project_xml);
+    let stem = resx_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut resource_name = vec![root_namespace.clone()];
+    resource_name.extend(resource_folders(project_directory, &item, resx_path));
+    let namespace = match &item.custom_tool_namespace {
+        Some(namespace) => namespace.clone(),
+        None => namespace_identifier(&resource_name.join(".")),
+    };
+    resource_name.push(stem.clone());
+    let class = DesignerClass {
+        namespace,
+        class_name: identifier(&stem),
+        resource_name: resource_name
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<String>>()
+            .join("."),
+        access,
+    };
+
+    let designer = resx_path.with_file_name(
+        item.last_gen_output
+            .unwrap_or_else(|| format!("{}.Designer.cs", stem)),
+    );
+    // Keep the line endings of the existing file, which Visual Studio writes as CRLF
+    let newline = match fs::read_to_string(&designer) {
+        Ok(existing) if !existing.contains("\r\n") => "\n",
+        _ => "\r\n",
+    };
+    let resources = XmlHandler::read_resources(&fs::read_to_string(resx_path)?)?;
+    fs::write(&designer, generate_designer(&class, &resources, newline))?;
+    Ok(Some(designer))
+}
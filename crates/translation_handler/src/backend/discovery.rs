@@ -0,0 +1,312 @@
+use crate::backend::designer::{resource_folders, root_namespace};
+use crate::backend::xml::reader::{ItemAction, ResourceItem};
+use crate::backend::xml::XmlHandler;
+use crate::locale::split_culture_suffix;
+use glob::glob;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Folders that are never searched for projects or resx files: build output, and
+/// `node_modules` of projects that ship a frontend. Hidden folders are skipped as well.
+const EXCLUDED_FOLDERS: [&str; 3] = ["bin", "obj", "node_modules"];
+
+/// A neutral resx file with the culture satellites next to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceGroup {
+    pub neutral: PathBuf,
+    /// Satellite files by culture, `de-DE` for `Strings.de-DE.resx`
+    pub satellites: BTreeMap<String, PathBuf>,
+    /// The csproj file that embeds the neutral file, when the files were found through one
+    pub project: Option<PathBuf>,
+}
+
+/// Resolves `.` and `..` without touching the file system, so paths written relative to
+/// different project files compare equal.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// An MSBuild path, which uses `\` on every platform, relative to `directory`.
+fn msbuild_path(directory: &Path, path: &str) -> PathBuf {
+    normalize(&directory.join(path.trim().replace('\\', "/")))
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// Files with `extension` in `directory` and its subfolders.
+pub(crate) fn find_files(
+    directory: &Path,
+    extension: &str,
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for dir_entry in fs::read_dir(directory)? {
+        let path = dir_entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if !name.starts_with('.') && !EXCLUDED_FOLDERS.contains(&name.as_str()) {
+                find_files(&path, extension, files)?;
+            }
+        } else if has_extension(&path, extension) {
+            files.push(normalize(&path));
+        }
+    }
+    Ok(())
+}
+
+/// The files an item's `Include` or `Remove` value stands for: `;` separated paths that may
+/// use the `*`, `?` and `**` wildcards.
+fn expand_item(directory: &Path, value: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for part in value.split(';').filter(|part| !part.trim().is_empty()) {
+        if part.contains('*') || part.contains('?') {
+            let mut pattern = format!(
+                "{}/{}",
+                glob::Pattern::escape(&directory.to_string_lossy()),
+                part.trim().replace('\\', "/")
+            );
+            // MSBuild's `Folder/**` stands for the files below it, glob's for the folders
+            if pattern.ends_with("**") {
+                pattern.push_str("/*");
+            }
+            files.extend(
+                glob(&pattern)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|path| path.is_file())
+                    .map(|path| normalize(&path)),
+            );
+        } else {
+            let path = msbuild_path(directory, part);
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// The csproj files of a `.sln` file, in the order the solution lists them.
+pub fn solution_projects(solution: &Path) -> io::Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(solution)?;
+    let directory = solution.parent().unwrap_or(Path::new(""));
+    // Project("{type}") = "Name", "src\Name\Name.csproj", "{id}"
+    let projects = content
+        .lines()
+        .filter(|line| line.trim_start().starts_with("Project("))
+        .filter_map(|line| line.split('"').nth(5))
+        .filter(|path| path.to_lowercase().ends_with(".csproj"))
+        .map(|path| msbuild_path(directory, path))
+        .filter(|path| path.is_file())
+        .collect();
+    Ok(projects)
+}
+
+/// The csproj files behind a location path: the project itself, the projects of a solution, or
+/// every project below a directory.
+pub fn project_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        let mut projects = Vec::new();
+        find_files(path, "csproj", &mut projects)?;
+        projects.sort();
+        Ok(projects)
+    } else if has_extension(path, "sln") {
+        solution_projects(path)
+    } else if has_extension(path, "csproj") {
+        Ok(vec![normalize(path)])
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// The resx files a project embeds. SDK-style projects embed every resx file below them unless
+/// `EnableDefaultEmbeddedResourceItems` is off; `Include` and `Remove` items are applied on top
+/// in the order MSBuild evaluates them. `Update` items only carry metadata.
+pub fn project_resources(project: &Path) -> io::Result<Vec<PathBuf>> {
+    let xml = fs::read_to_string(project)?;
+    let directory = project.parent().unwrap_or(Path::new(""));
+    let is_disabled = |property: &str| {
+        XmlHandler::get_property(&xml, property)
+            .is_some_and(|value| value.eq_ignore_ascii_case("false"))
+    };
+    let mut files = BTreeSet::new();
+    if XmlHandler::is_sdk_project(&xml)
+        && !is_disabled("EnableDefaultItems")
+        && !is_disabled("EnableDefaultEmbeddedResourceItems")
+    {
+        let mut found = Vec::new();
+        find_files(directory, "resx", &mut found)?;
+        files.extend(found);
+    }
+    for item in XmlHandler::get_resource_items(&xml) {
+        match item.action {
+            ItemAction::Include => files.extend(
+                expand_item(directory, &item.path)
+                    .into_iter()
+                    .filter(|path| has_extension(path, "resx")),
+            ),
+            ItemAction::Remove => {
+                for path in expand_item(directory, &item.path) {
+                    files.remove(&path);
+                }
+            }
+            ItemAction::Update => (),
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// The neutral file and culture of a satellite file, `Strings.resx` and `de` for
+/// `Strings.de.resx`, when the neutral file is one of `files`.
+fn satellite_of(file: &Path, files: &BTreeSet<PathBuf>) -> Option<(PathBuf, String)> {
+    let stem = file.file_stem()?.to_string_lossy();
+    let (base, culture) = split_culture_suffix(&stem)?;
+    let neutral = file.with_file_name(format!("{}.resx", base));
+    files
+        .contains(&neutral)
+        .then(|| (neutral, culture.to_string()))
+}
+
+/// Groups resx files into neutral files and their satellites. Files with a dot in their name
+/// are neutral unless the part after the last dot is a culture and the file named like the part
+/// before it exists.
+pub fn group_resources(files: impl IntoIterator<Item = PathBuf>) -> Vec<ResourceGroup> {
+    let files: BTreeSet<PathBuf> = files.into_iter().collect();
+    let mut groups: BTreeMap<PathBuf, ResourceGroup> = BTreeMap::new();
+    let mut satellites = Vec::new();
+    for file in &files {
+        match satellite_of(file, &files) {
+            Some((neutral, culture)) => satellites.push((neutral, culture, file.clone())),
+            None => {
+                groups.insert(
+                    file.clone(),
+                    ResourceGroup {
+                        neutral: file.clone(),
+                        satellites: BTreeMap::new(),
+                        project: None,
+                    },
+                );
+            }
+        }
+    }
+    for (neutral, culture, file) in satellites {
+        if let Some(group) = groups.get_mut(&neutral) {
+            group.satellites.insert(culture, file);
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// The csproj file in `resx_path`'s folder or above it that embeds the resx file, explicitly
+/// or through the default items of an SDK-style project.
+pub fn resource_project(resx_path: &Path) -> io::Result<Option<PathBuf>> {
+    let resx_path = normalize(resx_path);
+    for directory in resx_path.ancestors().skip(1) {
+        let Ok(dir_entries) = fs::read_dir(directory) else {
+            continue;
+        };
+        let mut projects: Vec<PathBuf> = dir_entries
+            .flatten()
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| has_extension(path, "csproj"))
+            .collect();
+        projects.sort();
+        for project in projects {
+            if project_resources(&project)?.contains(&resx_path) {
+                return Ok(Some(project));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Name of a neutral resx file the way `project` embeds it, without the `.resources` suffix:
+/// the root namespace, the folders between the project and the file, and the file stem, joined
+/// by dots (`Web.Resources.Strings`). Linked files are named after the folders of their `Link`
+/// and a `LogicalName` replaces the whole name, as in the generated class and the compiled
+/// assembly. The project is found with [`resource_project`] when not given; files no project
+/// embeds are named after their folder.
+pub fn resource_name(resx_path: &Path, project: Option<&Path>) -> io::Result<String> {
+    let resx_path = normalize(resx_path);
+    let project = match project {
+        Some(project) => Some(normalize(project)),
+        None => resource_project(&resx_path)?,
+    };
+    let stem = resx_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some(project) = project else {
+        let folder = resx_path.parent().and_then(Path::file_name);
+        return Ok(folder
+            .map(|folder| format!("{}.{}", folder.to_string_lossy(), stem))
+            .unwrap_or(stem));
+    };
+    let xml = fs::read_to_string(&project)?;
+    let directory = project.parent().unwrap_or(Path::new(""));
+    // Metadata of later items naming the file replaces that of earlier ones, as in MSBuild
+    let mut item = ResourceItem::default();
+    for named in XmlHandler::get_resource_items(&xml) {
+        if named.action == ItemAction::Remove
+            || !expand_item(directory, &named.path).contains(&resx_path)
+        {
+            continue;
+        }
+        item.link = named.link.or(item.link);
+        item.logical_name = named.logical_name.or(item.logical_name);
+    }
+    if let Some(logical_name) = item.logical_name {
+        let name = logical_name
+            .strip_suffix(".resources")
+            .unwrap_or(&logical_name);
+        return Ok(name.to_string());
+    }
+    let mut parts = vec![root_namespace(&project, &xml)];
+    parts.extend(resource_folders(directory, &item, &resx_path));
+    parts.push(stem);
+    Ok(parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>()
+        .join("."))
+}
+
+/// Every neutral resx file, with its satellites, of the projects behind a `.csproj` or `.sln`
+/// file or a directory.
+pub fn discover_resources(path: &Path) -> io::Result<Vec<ResourceGroup>> {
+    // The first project that embeds a file, for files shared by several projects
+    let mut projects: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    for project in project_files(path)? {
+        for file in project_resources(&project)? {
+            projects.entry(file).or_insert_with(|| project.clone());
+        }
+    }
+    let mut groups = group_resources(projects.keys().cloned());
+    for group in &mut groups {
+        group.project = projects.get(&group.neutral).cloned();
+    }
+    Ok(groups)
+}
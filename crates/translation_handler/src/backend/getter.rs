@@ -1,102 +1,187 @@
+use crate::backend::discovery::discover_resources;
+use crate::backend::setter::language_file;
+use crate::backend::usage::{scan_usages, source_root, ResourceClass};
+use crate::backend::xml::reader::ResxResource;
 use crate::backend::xml::XmlHandler;
+use crate::locale::{culture_fallbacks, is_culture_name};
 use crate::TranslationHandler;
 
 use glob::glob;
 use local_storage::stores::translation_store::TranslationEntry;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
 
+/// Every `<data>` element of a resx file. Errors name the file and the line and column of the
+/// problem.
+pub fn read_location_resources(location_path: &Path) -> Result<Vec<ResxResource>, io::Error> {
+    let file_error =
+        |error: &dyn std::fmt::Display| format!("{}: {}", location_path.display(), error);
+    let xml =
+        read_to_string(location_path).map_err(|e| io::Error::new(e.kind(), file_error(&e)))?;
+    XmlHandler::read_resources(&xml)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, file_error(&e)))
+}
+
 pub fn get_translations_from_location(
     location_path: &str,
-) -> BTreeMap<String, BTreeMap<String, String>> {
-    let xml = read_to_string(location_path).expect("failed to read file");
-    let response = XmlHandler::read_name_attributes_and_value_tags(&xml);
-    response
+) -> Result<BTreeMap<String, BTreeMap<String, String>>, io::Error> {
+    let xml = read_to_string(location_path)?;
+    let response = XmlHandler::read_name_attributes_and_value_tags(&xml).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", location_path, e),
+        )
+    })?;
+    Ok(response)
+}
+
+/// Translator notes of the keys of a resx file, from their `<comment>` elements.
+pub fn get_comments_from_location(
+    location_path: &str,
+) -> Result<BTreeMap<String, String>, io::Error> {
+    let xml = read_to_string(location_path)?;
+    XmlHandler::read_comments(&xml).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", location_path, e),
+        )
+    })
 }
 
-pub fn get_resources_from_csproj(path: &str) -> Option<Vec<String>> {
+/// The neutral resx files of a csproj file, including those an SDK-style project embeds
+/// without listing them. `None` for paths that are not csproj files.
+pub fn get_resources_from_csproj(path: &str) -> Result<Option<Vec<String>>, io::Error> {
     if !path.ends_with(".csproj") {
-        return None;
+        return Ok(None);
     }
-    let xml = read_to_string(path).expect("failed to read file");
-    let mut response = XmlHandler::get_resources(&xml, path);
-    response.retain(|file| {
-        let path = std::path::Path::new(file);
-        let filename = path
-            .file_stem()
-            .and_then(|name| name.to_str())
-            .unwrap_or("");
-        path.extension().map_or(false, |ext| ext == "resx")
-            && !filename.contains('.')
-            && path.exists()
-    });
-    Some(response)
+    let groups = discover_resources(Path::new(path))?;
+    let response = groups
+        .into_iter()
+        .map(|group| group.neutral.to_string_lossy().to_string())
+        .collect();
+    Ok(Some(response))
 }
 
-impl TranslationHandler {
-    pub async fn get_backend_translations(path: &str) -> Vec<TranslationEntry> {
-        let keys = get_translations_from_location(path);
-        let path = Path::new(path);
-        let parent_folder = path.parent().unwrap();
-        let translations = Self::get_locale_translations(
-            parent_folder.to_str().unwrap().to_string(),
-            path.file_stem().unwrap().to_str().unwrap().to_string(),
-            keys.clone(),
-        )
-        .await;
-        let translation_entries = translations
-            .iter()
-            .map(|(key, value)| {
-                let values: HashMap<String, String> = value.clone().into_iter().collect();
-                TranslationEntry {
-                    key: key.to_owned(),
-                    value: key.to_owned(),
-                    translations: values,
-                    in_use: true,
-                }
-            })
-            .collect();
-        translation_entries
+/// Languages of a neutral resx file: `default` for the file itself, then the culture of every
+/// satellite file next to it (`Resources.de-DE.resx`).
+pub fn get_backend_languages(path: &str) -> Vec<String> {
+    let path = Path::new(path);
+    let mut languages = vec!["default".to_string()];
+    let (Some(parent), Some(stem)) = (path.parent(), path.file_stem()) else {
+        return languages;
+    };
+    let prefix = format!("{}.", stem.to_string_lossy());
+    let mut cultures: Vec<String> = glob(&format!(
+        "{}/*.resx",
+        glob::Pattern::escape(&parent.to_string_lossy())
+    ))
+    .into_iter()
+    .flatten()
+    .flatten()
+    .filter_map(|file| {
+        let file_stem = file.file_stem()?.to_string_lossy().to_string();
+        let culture = file_stem.strip_prefix(&prefix)?;
+        is_culture_name(culture).then(|| culture.to_string())
+    })
+    .collect();
+    cultures.sort();
+    languages.extend(cultures);
+    languages
+}
+
+/// Metadata of resources that aren't strings, which the editor shows but doesn't let change.
+fn resource_metadata(resource: &ResxResource) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if resource.is_editable() {
+        return metadata;
+    }
+    metadata.insert("editable".to_string(), "false".to_string());
+    if let Some(type_name) = &resource.type_name {
+        metadata.insert("type".to_string(), type_name.clone());
     }
+    if let Some(mimetype) = &resource.mimetype {
+        metadata.insert("mimetype".to_string(), mimetype.clone());
+    }
+    metadata
+}
 
-    async fn get_locale_translations(
-        parent_folder: String,
-        main_file: String,
-        keys: BTreeMap<String, BTreeMap<String, String>>,
-    ) -> BTreeMap<String, BTreeMap<String, String>> {
-        let main_file = format!("{}.resx", main_file);
-        let resx_files = glob(format!("{}/*.resx", parent_folder).as_str())
-            .expect("Failed to read glob pattern");
-        let mut keys = keys.clone();
+/// Fills in the value every language of an entry resolves to the way ResourceManager looks it
+/// up: the culture's own file, then its parent cultures (`de-AT`, `de`), then the neutral file.
+/// Languages that only get a value from a parent are listed in `fallbacks`.
+fn resolve_fallbacks(entry: &mut TranslationEntry, languages: &[String]) {
+    for language in languages {
+        let chain = match language.as_str() {
+            "default" => Vec::new(),
+            culture => culture_fallbacks(culture),
+        };
+        let source = chain
+            .into_iter()
+            .chain(std::iter::once("default".to_string()))
+            .find_map(|culture| {
+                // Culture names are compared without regard to case, as in .NET
+                entry
+                    .translations
+                    .iter()
+                    .find(|(language, _)| language.eq_ignore_ascii_case(&culture))
+                    .map(|(language, value)| (language.clone(), value.clone()))
+            });
+        let Some((source, value)) = source else {
+            continue;
+        };
+        if &source != language {
+            entry.fallbacks.insert(language.clone(), source);
+        }
+        entry.effective_values.insert(language.clone(), value);
+    }
+}
 
-        for entry in resx_files {
-            match entry {
-                Ok(path) => {
-                    if let Some(filename) = path.file_stem() {
-                        if let Some(file_name_str) = filename.to_str() {
-                            if !file_name_str.ends_with(&main_file) {
-                                let language_code: Vec<&str> = file_name_str.split(".").collect();
-                                let file_translation_entries =
-                                    get_translations_from_location(path.clone().to_str().unwrap());
-                                if language_code.len() > 1 {
-                                    file_translation_entries.iter().for_each(|(key, value)| {
-                                        if let Some(inner_map) = keys.get_mut(key) {
-                                            inner_map.insert(
-                                                language_code[1].to_string(),
-                                                value.get("default").unwrap().to_owned(),
-                                            );
-                                        }
-                                    })
-                                }
-                            }
-                        }
-                    }
+impl TranslationHandler {
+    /// Entries of the neutral resx file at `path` with the value of every satellite file that
+    /// has the key, and the value each culture falls back to when it doesn't. Resources that
+    /// aren't strings come with `editable` set to `false` in their metadata. `in_use` and
+    /// `usages` come from the C# and Razor files of the project that embeds the file.
+    pub async fn get_backend_translations(path: &str) -> Result<Vec<TranslationEntry>, io::Error> {
+        let mut resources = read_location_resources(Path::new(path))?;
+        resources.sort_by(|a, b| a.name.cmp(&b.name));
+        resources.dedup_by(|a, b| a.name == b.name);
+        let mut translation_entries: Vec<TranslationEntry> = resources
+            .into_iter()
+            .map(|resource| TranslationEntry {
+                key: resource.name.clone(),
+                value: resource.name.clone(),
+                metadata: resource_metadata(&resource),
+                translations: HashMap::from([("default".to_string(), resource.value)]),
+                description: resource.comment,
+                ..Default::default()
+            })
+            .collect();
+        let languages = get_backend_languages(path);
+        for language in languages.iter().skip(1) {
+            let mut values: HashMap<String, String> =
+                read_location_resources(&language_file(path, language)?)?
+                    .into_iter()
+                    .map(|resource| (resource.name, resource.value))
+                    .collect();
+            for entry in translation_entries.iter_mut() {
+                if let Some(value) = values.remove(&entry.key) {
+                    entry.translations.insert(language.clone(), value);
                 }
-                Err(_) => {}
             }
         }
-
-        keys
+        let keys: Vec<String> = translation_entries
+            .iter()
+            .map(|entry| entry.key.clone())
+            .collect();
+        let root = source_root(Path::new(path))?;
+        let usages = scan_usages(&root, &ResourceClass::new(Path::new(path), &keys))?;
+        for entry in translation_entries.iter_mut() {
+            resolve_fallbacks(entry, &languages);
+            entry.in_use = usages.is_used(&entry.key);
+            entry.possibly_used = usages.is_possibly_used(&entry.key);
+            entry.usages = usages.locations(&entry.key, &root);
+        }
+        Ok(translation_entries)
     }
 }
@@ -1,5 +1,8 @@
+pub mod designer;
+pub mod discovery;
 pub mod getter;
-mod remover;
+pub mod remover;
 pub mod setter;
 mod updater;
+pub mod usage;
 pub mod xml;
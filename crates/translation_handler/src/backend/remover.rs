@@ -1,9 +1,61 @@
+use crate::backend::designer::regenerate_designer;
+use crate::backend::getter::get_backend_languages;
+use crate::backend::setter::language_file;
 use crate::backend::xml::XmlHandler;
 use crate::TranslationHandler;
-use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Keys that were removed from one resx file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedKeys {
+    pub file: PathBuf,
+    pub keys: Vec<String>,
+}
+
+fn file_error(file: &Path, error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!("{}: {}", file.display(), error),
+    )
+}
 
 impl TranslationHandler {
-    pub async fn remove_backend_key(path: String, keys: Vec<String>) -> Result<(), Box<dyn Error>> {
-        XmlHandler::remove_key_value(path, keys)
+    /// Removes keys from the neutral resx file and every satellite file of it. Every file is
+    /// read and checked for write access first, so a file that can't be changed fails the
+    /// removal before any file is written.
+    pub async fn remove_backend_key(
+        path: String,
+        keys: Vec<String>,
+    ) -> Result<Vec<RemovedKeys>, io::Error> {
+        let mut changes = Vec::new();
+        for language in get_backend_languages(&path) {
+            let file = language_file(&path, &language)?;
+            let xml = fs::read_to_string(&file).map_err(|e| file_error(&file, e))?;
+            let (content, removed) =
+                XmlHandler::remove_data(&xml, &keys).map_err(|e| file_error(&file, e))?;
+            if !removed.is_empty() {
+                changes.push((file, content, removed));
+            }
+        }
+        for (file, _, _) in &changes {
+            OpenOptions::new()
+                .write(true)
+                .open(file)
+                .map_err(|e| file_error(file, e))?;
+        }
+        let mut summary = Vec::new();
+        for (file, content, removed) in changes {
+            fs::write(&file, content).map_err(|e| file_error(&file, e))?;
+            summary.push(RemovedKeys {
+                file,
+                keys: removed,
+            });
+        }
+        if !summary.is_empty() {
+            regenerate_designer(Path::new(&path))?;
+        }
+        Ok(summary)
     }
 }
@@ -1,34 +1,84 @@
+use crate::backend::designer::regenerate_designer;
+use crate::backend::xml::writer::Placement;
 use crate::backend::xml::XmlHandler;
 use crate::TranslationHandler;
 use db::prisma::settings;
 use local_storage::stores::translation_store::TranslationEntry;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 impl TranslationHandler {
+    /// Adds a key to the neutral resx file and to the satellite file of every culture in
+    /// `translations`, creating satellites that don't exist yet. Files whose elements are sorted
+    /// stay sorted. Nothing is written when any of the files already has the key.
     pub async fn add_new_backend_key(
         path: String,
         key: String,
         value: String,
+        description: Option<String>,
+        translations: HashMap<String, String>,
         settings: settings::Data,
     ) -> Result<Vec<TranslationEntry>, std::io::Error> {
-        XmlHandler::write_key_value(path.clone(), key, value)
-            .expect("Failed to add new key_value pair");
-        let translations = XmlHandler::read_name_attributes_and_value_tags(path.clone().as_str());
-        let translation_entries = translations
-            .iter()
-            .map(|(key, value)| {
-                let values: HashMap<String, String> = value.clone().into_iter().collect();
-                TranslationEntry {
-                    key: key.to_owned(),
-                    value: key.to_owned(),
-                    translations: values,
-                    in_use: true,
-                }
-            })
-            .collect();
-        Ok(translation_entries)
+        let mut files = vec![(PathBuf::from(&path), value, description)];
+        for (language, value) in translations {
+            if language != "default" && !value.is_empty() {
+                files.push((language_file(&path, &language)?, value, None));
+            }
+        }
+        for (file, _, _) in &files {
+            let has_key = file.exists()
+                && XmlHandler::read_data_names(&fs::read_to_string(file)?).contains(&key);
+            if has_key {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("{} already has the key {}", file.display(), key),
+                ));
+            }
+        }
+        for (file, value, comment) in files {
+            if !file.exists() {
+                XmlHandler::create_resx_file(&file)?;
+            }
+            let placement = if XmlHandler::is_sorted(&fs::read_to_string(&file)?) {
+                Placement::Sorted
+            } else {
+                Placement::End
+            };
+            XmlHandler::insert_data(&file, &key, &value, comment.as_deref(), placement)?;
+        }
+        regenerate_designer(Path::new(&path))?;
+        Self::get_backend_translations(&path).await
+    }
+}
+
+/// The resx file of `language`: the neutral file at `path` for `default`, or the satellite file
+/// next to it such as `Resources.de-DE.resx`.
+pub fn language_file(path: &str, language: &str) -> Result<PathBuf, std::io::Error> {
+    let neutral = Path::new(path);
+    if language == "default" {
+        return Ok(neutral.to_path_buf());
+    }
+    let stem = neutral.file_stem().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is not a resx file", path),
+        )
+    })?;
+    Ok(neutral.with_file_name(format!("{}.{}.resx", stem.to_string_lossy(), language)))
+}
+
+/// Creates the satellite resx file of `language` next to the neutral file at `path`.
+pub fn add_backend_language(path: &str, language: &str) -> Result<PathBuf, std::io::Error> {
+    let satellite = language_file(path, language)?;
+    if satellite.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", satellite.display()),
+        ));
     }
+    XmlHandler::create_resx_file(&satellite)?;
+    Ok(satellite)
 }
 
 fn get_translation_files(path: String) -> Vec<PathBuf> {
@@ -1,16 +1,95 @@
+use crate::backend::designer::regenerate_designer;
+use crate::backend::getter::read_location_resources;
+use crate::backend::setter::language_file;
+use crate::backend::xml::reader::ResxResource;
 use crate::backend::xml::XmlHandler;
 use crate::frontend::updater::UpdatedKeyValues;
 use crate::TranslationHandler;
 use db::prisma::settings;
 use std::error::Error;
+use std::io;
+use std::path::Path;
+
+fn not_editable(resource: &ResxResource) -> String {
+    format!(
+        "{} is a {} resource and can't be edited as text",
+        resource.name,
+        resource
+            .type_name
+            .as_deref()
+            .or(resource.mimetype.as_deref())
+            .unwrap_or_default()
+    )
+}
 
 impl TranslationHandler {
+    /// Writes the value of every language into its resx file, creating satellite files that
+    /// don't exist yet. An empty value removes the key from a satellite file, so the culture
+    /// falls back to the neutral value.
     pub async fn update_backend_key(
         path: String,
         values: UpdatedKeyValues,
         _settings: settings::Data,
     ) -> Result<(), Box<dyn Error>> {
-        XmlHandler::update_value(path, values.ts_key, values.json_key)?;
+        let resource = read_location_resources(Path::new(&path))?
+            .into_iter()
+            .find(|resource| resource.name == values.ts_key);
+        if let Some(resource) = resource.filter(|resource| !resource.is_editable()) {
+            return Err(not_editable(&resource).into());
+        }
+        for (language, value) in &values.translation_values {
+            let file = language_file(&path, language)?;
+            let file_path = file.to_string_lossy().to_string();
+            if language != "default" && value.is_empty() {
+                if file.exists() {
+                    XmlHandler::remove_key_value(file_path, vec![values.ts_key.clone()])?;
+                }
+                continue;
+            }
+            if !file.exists() {
+                XmlHandler::create_resx_file(&file)?;
+            }
+            if !XmlHandler::update_value(file_path.clone(), values.ts_key.clone(), value.clone())? {
+                XmlHandler::write_key_value(file_path, values.ts_key.clone(), value.clone(), None)?;
+            }
+        }
+        if let Some(description) = values.description {
+            XmlHandler::update_comment(path.clone(), values.ts_key, description)?;
+        }
+        // The generated properties document the neutral value
+        if values.translation_values.contains_key("default") {
+            regenerate_designer(Path::new(&path))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the values of one language into its resx file as they are, reading and writing
+    /// it once and creating a satellite file that doesn't exist yet. Nothing is written when a
+    /// key is a resource that can't be edited as text.
+    pub fn set_backend_values(
+        path: &str,
+        language: &str,
+        values: &[(String, String)],
+    ) -> Result<(), io::Error> {
+        let resources = read_location_resources(Path::new(path))?;
+        let not_text = resources.iter().find(|resource| {
+            !resource.is_editable() && values.iter().any(|(key, _)| *key == resource.name)
+        });
+        if let Some(resource) = not_text {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                not_editable(resource),
+            ));
+        }
+        let file = language_file(path, language)?;
+        if !file.exists() {
+            XmlHandler::create_resx_file(&file)?;
+        }
+        XmlHandler::set_values(&file, values)?;
+        // The generated properties document the neutral value
+        if language == "default" {
+            regenerate_designer(Path::new(path))?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,596 @@
+use crate::backend::designer::identifier;
+use crate::backend::discovery::{find_files, normalize, resource_project};
+use crate::frontend::usage::{position, scanner_id, SourceCache, Usage, UsageReport};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::SystemTime;
+
+/// Extensions of the source files searched for references to resx keys.
+const SOURCE_EXTENSIONS: [&str; 3] = ["cs", "cshtml", "razor"];
+
+/// Interfaces whose indexer looks a key up, as in `localizer["Key"]`.
+const LOCALIZER_TYPES: [&str; 3] = ["IStringLocalizer", "IHtmlLocalizer", "IViewLocalizer"];
+
+/// Methods that look a key up by name, on a ResourceManager or a localizer.
+const LOOKUP_METHODS: [&str; 4] = ["GetString", "GetObject", "GetStream", "GetHtml"];
+
+/// Named arguments of data annotation attributes that hold a resource name, as in
+/// `[Display(ResourceType = typeof(Strings), Name = "Email")]`.
+const ANNOTATION_ARGUMENTS: [&str; 6] = [
+    "Name",
+    "ShortName",
+    "Description",
+    "Prompt",
+    "GroupName",
+    "ErrorMessageResourceName",
+];
+
+/// The names C# code reaches the keys of a resx file by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceClass {
+    /// Class generated for the file, `Strings` for `Strings.resx`
+    pub class_name: String,
+    /// Type argument of the localizers that read the file, `HomeController` for
+    /// `Controllers.HomeController.resx`
+    pub localizer_type: String,
+    /// Resource names by the property the generated class has for them
+    pub properties: HashMap<String, String>,
+    /// The generated class itself, which names every key and isn't searched
+    pub designer_file: PathBuf,
+}
+
+impl ResourceClass {
+    pub fn new(resx_path: &Path, keys: &[String]) -> Self {
+        let stem = resx_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        ResourceClass {
+            class_name: identifier(&stem),
+            localizer_type: stem.rsplit('.').next().unwrap_or_default().to_string(),
+            properties: keys
+                .iter()
+                .map(|key| (identifier(key), key.clone()))
+                .collect(),
+            designer_file: normalize(&resx_path.with_extension("Designer.cs")),
+        }
+    }
+
+    /// Identifies the resource class in the cache of source file scans. Keys aren't part of
+    /// it, as the scan of a file records members by name and is matched against the keys later.
+    fn scanner_id(&self) -> u64 {
+        scanner_id((&self.designer_file, &self.class_name, &self.localizer_type))
+    }
+}
+
+/// The references of one source file, with the members of the generated class not yet
+/// matched against the keys of the resx file.
+#[derive(Debug, Default)]
+struct SourceReferences {
+    /// References by resource name, from localizers, lookups and data annotations
+    keys: UsageReport,
+    /// Members of the generated class, as in `Strings.Some_Key`, by member name
+    members: BTreeMap<String, Vec<Usage>>,
+}
+
+impl SourceReferences {
+    /// Adds the references to the keys of `class` to `report`.
+    fn resolve(&self, class: &ResourceClass, report: &mut UsageReport) {
+        let mut resolved = self.keys.clone();
+        for (member, usages) in &self.members {
+            if let Some(key) = class.properties.get(member) {
+                resolved
+                    .keys
+                    .entry(key.clone())
+                    .or_default()
+                    .extend(usages.iter().cloned());
+            }
+        }
+        for usages in resolved.keys.values_mut() {
+            usages.sort_by_key(|usage| (usage.line, usage.column));
+        }
+        report.merge(&resolved);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// Splits C# or Razor source into identifiers, string literals and punctuation with their
+/// offsets. Markup is tokenized like code, except that apostrophes don't start a character
+/// literal, as they quote attributes and show up in text. Code in interpolated string holes is
+/// tokenized like any other code.
+fn tokenize(source: &str, is_markup: bool) -> Vec<(Token, usize)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let char_at = |index: usize| chars.get(index).map(|(_, c)| *c);
+    let mut tokens = Vec::new();
+    // One entry per open `{`, with whether the interpolated string a hole's `}` returns to is
+    // verbatim
+    let mut braces: Vec<Option<bool>> = Vec::new();
+    // Inside the text of an interpolated string, with whether it is verbatim
+    let mut interpolated: Option<bool> = None;
+    let mut index = 0;
+
+    while let Some(c) = char_at(index) {
+        let offset = chars[index].0;
+        if let Some(verbatim) = interpolated {
+            match c {
+                '"' if verbatim && char_at(index + 1) == Some('"') => index += 1,
+                '"' => interpolated = None,
+                '\\' if !verbatim => index += 1,
+                '\n' if !verbatim => interpolated = None,
+                '{' if char_at(index + 1) == Some('{') => index += 1,
+                '{' => {
+                    braces.push(Some(verbatim));
+                    interpolated = None;
+                }
+                _ => (),
+            }
+            index += 1;
+            continue;
+        }
+        let prefix = (0..2)
+            .take_while(|ahead| matches!(char_at(index + ahead), Some('$' | '@')))
+            .count();
+        if char_at(index + prefix) == Some('"') {
+            let is_interpolated = (0..prefix).any(|ahead| char_at(index + ahead) == Some('$'));
+            let verbatim = (0..prefix).any(|ahead| char_at(index + ahead) == Some('@'));
+            index += prefix;
+            let quotes = (0..)
+                .take_while(|ahead| char_at(index + ahead) == Some('"'))
+                .count();
+            if quotes >= 3 && !verbatim {
+                // Raw string literal, which ends at the same number of quotes
+                index += quotes;
+                while char_at(index).is_some()
+                    && !(0..quotes).all(|ahead| char_at(index + ahead) == Some('"'))
+                {
+                    index += 1;
+                }
+                index += quotes;
+                continue;
+            }
+            index += 1;
+            if is_interpolated {
+                interpolated = Some(verbatim);
+                continue;
+            }
+            let mut value = String::new();
+            while let Some(next) = char_at(index) {
+                index += 1;
+                match next {
+                    '"' if verbatim && char_at(index) == Some('"') => {
+                        value.push('"');
+                        index += 1;
+                    }
+                    '"' => break,
+                    '\\' if !verbatim => {
+                        value.extend(char_at(index));
+                        index += 1;
+                    }
+                    '\n' if !verbatim => break,
+                    next => value.push(next),
+                }
+            }
+            tokens.push((Token::Str(value), offset));
+        } else if c == '/' && char_at(index + 1) == Some('/') {
+            while char_at(index).is_some_and(|c| c != '\n') {
+                index += 1;
+            }
+        } else if (c == '/' || c == '@') && char_at(index + 1) == Some('*') {
+            // Block comments and Razor's `@* *@`
+            let end = if c == '/' { '/' } else { '@' };
+            index += 2;
+            while char_at(index).is_some()
+                && !(char_at(index) == Some('*') && char_at(index + 1) == Some(end))
+            {
+                index += 1;
+            }
+            index += 2;
+        } else if c == '\'' && !is_markup {
+            index += 1;
+            while let Some(next) = char_at(index) {
+                index += 1;
+                match next {
+                    '\\' => index += 1,
+                    '\'' | '\n' => break,
+                    _ => (),
+                }
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut ident = String::new();
+            while let Some(next) = char_at(index).filter(|c| c.is_alphanumeric() || *c == '_') {
+                ident.push(next);
+                index += 1;
+            }
+            tokens.push((Token::Ident(ident), offset));
+        } else if c.is_ascii_digit() {
+            while char_at(index).is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            {
+                index += 1;
+            }
+        } else {
+            index += 1;
+            match c {
+                c if c.is_whitespace() => (),
+                '{' => {
+                    braces.push(None);
+                    tokens.push((Token::Punct(c), offset));
+                }
+                '}' => match braces.pop() {
+                    Some(Some(verbatim)) => interpolated = Some(verbatim),
+                    _ => tokens.push((Token::Punct(c), offset)),
+                },
+                c => tokens.push((Token::Punct(c), offset)),
+            }
+        }
+    }
+    tokens
+}
+
+fn is_ident(token: Option<&(Token, usize)>, name: &str) -> bool {
+    matches!(token, Some((Token::Ident(ident), _)) if ident == name)
+}
+
+/// Names of the localizers in a file that read `class`: fields, parameters, properties and
+/// `@inject` directives typed `IStringLocalizer<T>`, `IHtmlLocalizer<T>` or `IViewLocalizer`.
+/// Localizers without a type argument may read any file.
+fn localizer_bindings(tokens: &[(Token, usize)], class: &ResourceClass) -> Vec<String> {
+    let mut bindings = Vec::new();
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        let Token::Ident(name) = token else {
+            continue;
+        };
+        if !LOCALIZER_TYPES.contains(&name.as_str()) {
+            continue;
+        }
+        let mut next = index + 1;
+        let mut type_argument = None;
+        if tokens.get(next).map(|(token, _)| token) == Some(&Token::Punct('<')) {
+            let mut depth = 0;
+            for (token, _) in &tokens[next..] {
+                next += 1;
+                match token {
+                    Token::Punct('<') => depth += 1,
+                    Token::Punct('>') => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    // `T` of `Namespace.T`
+                    Token::Ident(ident) if depth == 1 => type_argument = Some(ident.clone()),
+                    _ => (),
+                }
+            }
+        }
+        if tokens.get(next).map(|(token, _)| token) == Some(&Token::Punct('?')) {
+            next += 1;
+        }
+        if let Some((Token::Ident(binding), _)) = tokens.get(next) {
+            if type_argument.map_or(true, |argument| argument == class.localizer_type) {
+                bindings.push(binding.clone());
+            }
+        }
+    }
+    bindings
+}
+
+/// The range of the argument list of the call or attribute the token at `index` is an
+/// argument of, without its parentheses.
+fn enclosing_arguments(tokens: &[(Token, usize)], index: usize) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    let open = (0..index).rev().find(|&before| {
+        match tokens[before].0 {
+            Token::Punct(')') => depth += 1,
+            Token::Punct('(') if depth == 0 => return true,
+            Token::Punct('(') => depth -= 1,
+            _ => (),
+        }
+        false
+    })?;
+    let mut depth = 0;
+    let close = (open..tokens.len()).find(|&after| {
+        match tokens[after].0 {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth -= 1,
+            _ => (),
+        }
+        depth == 0
+    })?;
+    Some((open + 1, close))
+}
+
+/// Adds the references to the keys of `class` in one source file to `report`. Keys are
+/// recorded by resource name, so `Strings.Some_Key` counts for `Some.Key`.
+pub fn scan_source(source: &str, file: &Path, class: &ResourceClass, report: &mut UsageReport) {
+    scan_references(source, file, class).resolve(class, report);
+}
+
+/// The references in one source file to the generated class of `class` and to the localizers
+/// that read it. Lookups by name only count on `<Class>.ResourceManager` and on localizers, as
+/// plenty of other types have a `GetString` method.
+fn scan_references(source: &str, file: &Path, class: &ResourceClass) -> SourceReferences {
+    let is_markup = file
+        .extension()
+        .is_some_and(|ext| ext == "cshtml" || ext == "razor");
+    let tokens = tokenize(source, is_markup);
+    let localizers = localizer_bindings(&tokens, class);
+    let usage = |offset: usize| {
+        let (line, column) = position(source, offset);
+        Usage {
+            file: file.to_path_buf(),
+            line,
+            column,
+        }
+    };
+    let punct = |index: usize| match tokens.get(index) {
+        Some((Token::Punct(c), _)) => Some(*c),
+        _ => None,
+    };
+    let record = |references: &mut BTreeMap<String, Vec<Usage>>, key: &str, offset: usize| {
+        references
+            .entry(key.to_string())
+            .or_default()
+            .push(usage(offset))
+    };
+    let mut references = SourceReferences::default();
+
+    for (index, (token, offset)) in tokens.iter().enumerate() {
+        let Token::Ident(name) = token else {
+            continue;
+        };
+        let previous = index.checked_sub(1).and_then(punct);
+
+        if *name == class.class_name {
+            // typeof(Strings) hands the whole class over, unless it is the resource type of a
+            // data annotation that names its key
+            if index >= 2 && previous == Some('(') && is_ident(tokens.get(index - 2), "typeof") {
+                let Some((start, end)) = enclosing_arguments(&tokens, index - 2) else {
+                    references.keys.dynamic.push(usage(*offset));
+                    continue;
+                };
+                let mut is_annotation = false;
+                for argument in start..end {
+                    let Some((Token::Ident(argument_name), _)) = tokens.get(argument) else {
+                        continue;
+                    };
+                    if !ANNOTATION_ARGUMENTS.contains(&argument_name.as_str())
+                        || punct(argument + 1) != Some('=')
+                    {
+                        continue;
+                    }
+                    is_annotation = true;
+                    if let Some((Token::Str(key), key_offset)) = tokens.get(argument + 2) {
+                        record(&mut references.keys.keys, key, *key_offset);
+                    }
+                }
+                if !is_annotation {
+                    references.keys.dynamic.push(usage(*offset));
+                }
+                continue;
+            }
+            if punct(index + 1) != Some('.') {
+                continue;
+            }
+            match tokens.get(index + 2) {
+                // Strings.ResourceManager.GetString("Key")
+                Some((Token::Ident(member), _)) if member == "ResourceManager" => {
+                    let is_lookup = punct(index + 3) == Some('.')
+                        && matches!(tokens.get(index + 4),
+                            Some((Token::Ident(method), _)) if LOOKUP_METHODS.contains(&method.as_str()))
+                        && punct(index + 5) == Some('(');
+                    match (tokens.get(index + 6), punct(index + 7)) {
+                        (Some((Token::Str(key), key_offset)), Some(')' | ',')) if is_lookup => {
+                            record(&mut references.keys.keys, key, *key_offset)
+                        }
+                        _ => references.keys.dynamic.push(usage(*offset)),
+                    }
+                }
+                // Strings.Key and nameof(Strings.Key)
+                Some((Token::Ident(member), member_offset)) => {
+                    record(&mut references.members, member, *member_offset)
+                }
+                _ => (),
+            }
+        } else if localizers.contains(name) {
+            match punct(index + 1) {
+                // localizer["Key"] and localizer["Key", arguments]
+                Some('[') => match (tokens.get(index + 2), punct(index + 3)) {
+                    (Some((Token::Str(key), key_offset)), Some(']' | ',')) => {
+                        record(&mut references.keys.keys, key, *key_offset)
+                    }
+                    _ => references.keys.dynamic.push(usage(*offset)),
+                },
+                // localizer.GetString("Key"); other members such as GetAllStrings() can
+                // reach any key
+                Some('.') => {
+                    let is_lookup = matches!(tokens.get(index + 2),
+                        Some((Token::Ident(method), _)) if LOOKUP_METHODS.contains(&method.as_str()))
+                        && punct(index + 3) == Some('(');
+                    match (tokens.get(index + 4), punct(index + 5)) {
+                        (Some((Token::Str(key), key_offset)), Some(')' | ',')) if is_lookup => {
+                            record(&mut references.keys.keys, key, *key_offset)
+                        }
+                        _ => references.keys.dynamic.push(usage(*offset)),
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    references
+}
+
+/// The folder searched for references to the keys of a resx file: the folder of the csproj
+/// that embeds it, or the resx file's own folder when no project does. The project found for a
+/// file is remembered until the project file changes.
+pub fn source_root(resx_path: &Path) -> io::Result<PathBuf> {
+    static PROJECTS: OnceLock<Mutex<HashMap<PathBuf, (PathBuf, SystemTime)>>> = OnceLock::new();
+    let projects = || {
+        PROJECTS
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    };
+    let modified = |project: &Path| fs::metadata(project).and_then(|metadata| metadata.modified());
+    let resx_path = normalize(resx_path);
+
+    let cached = projects().get(&resx_path).cloned();
+    let project = match cached {
+        Some((project, read_at)) if modified(&project).ok() == Some(read_at) => Some(project),
+        _ => {
+            let project = resource_project(&resx_path)?;
+            match project.as_ref().map(|project| (project, modified(project))) {
+                Some((project, Ok(read_at))) => {
+                    projects().insert(resx_path.clone(), (project.clone(), read_at));
+                }
+                _ => {
+                    projects().remove(&resx_path);
+                }
+            }
+            project
+        }
+    };
+    let root = project
+        .as_deref()
+        .unwrap_or(&resx_path)
+        .parent()
+        .unwrap_or(Path::new(""));
+    Ok(root.to_path_buf())
+}
+
+/// Scans the `.cs`, `.cshtml` and `.razor` files below `directory` for references to the keys
+/// of `class`. Files that aren't UTF-8 are skipped, and files that did not change since the
+/// last scan of the same resx file are not read again, even when its keys changed.
+pub fn scan_usages(directory: &Path, class: &ResourceClass) -> io::Result<UsageReport> {
+    static CACHE: OnceLock<SourceCache<SourceReferences>> = OnceLock::new();
+    let mut files = Vec::new();
+    for extension in SOURCE_EXTENSIONS {
+        find_files(directory, extension, &mut files)?;
+    }
+    files.sort();
+    files.retain(|file| *file != class.designer_file);
+    let mut report = UsageReport::default();
+    let scanned = CACHE.get_or_init(SourceCache::default).scan(
+        class.scanner_id(),
+        &files,
+        |source, file| scan_references(source, file, class),
+    )?;
+    for references in scanned {
+        references.resolve(class, &mut report);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(source: &str) -> UsageReport {
+        let keys = ["Title", "Errors.Required", "Email", "Greeting", "Footer"];
+        let class = ResourceClass::new(
+            Path::new("/app/Resources/Strings.resx"),
+            &keys.map(String::from),
+        );
+        let mut report = UsageReport::default();
+        scan_source(
+            source,
+            Path::new("/app/Pages/Index.cshtml"),
+            &class,
+            &mut report,
+        );
+        report
+    }
+
+    fn lines(report: &UsageReport, key: &str) -> Vec<usize> {
+        report.keys[key].iter().map(|usage| usage.line).collect()
+    }
+
+    #[test]
+    fn generated_class_and_resource_manager_usages_are_counted() {
+        let source = r#"using App.Resources;
+
+public class LoginModel
+{
+    [Display(ResourceType = typeof(Strings), Name = "Email")]
+    public string Email { get; set; }
+
+    // Strings.Footer is commented out
+    public string Title => Strings.Title + "Strings.Footer";
+    public string Required => App.Resources.Strings.Errors_Required;
+    public string Name => nameof(Strings.Title);
+    public string Greeting => Strings.ResourceManager.GetString("Greeting", culture);
+    public string Other => $@"{Strings.Title} ""quoted"" {{Strings.Footer}}";
+}
+"#;
+        let report = scan(source);
+        assert_eq!(
+            vec!["Email", "Errors.Required", "Greeting", "Title"],
+            report.keys.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![9, 11, 13], lines(&report, "Title"));
+        assert_eq!(36, report.keys["Title"][0].column);
+        assert_eq!(vec![12], lines(&report, "Greeting"));
+        assert!(report.dynamic.is_empty());
+        assert!(!report.is_possibly_used("Footer"));
+    }
+
+    #[test]
+    fn localizers_of_the_resource_type_are_counted() {
+        let source = r#"@inject IStringLocalizer<Strings> Localizer
+@inject IHtmlLocalizer<SharedResource> Shared
+@* @Localizer["Footer"] *@
+<h1 title='@Localizer["Title"]'>Don't @Localizer["Greeting", user.Name]</h1>
+<p>@Shared["Email"]</p>
+<p>@Localizer.GetString("Errors.Required")</p>
+"#;
+        let report = scan(source);
+        assert_eq!(
+            vec!["Errors.Required", "Greeting", "Title"],
+            report.keys.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(1, report.count("Errors.Required"));
+        assert!(report.dynamic.is_empty());
+    }
+
+    #[test]
+    fn computed_lookups_make_every_key_possibly_used() {
+        let source = r#"public class Labels(IStringLocalizer<Strings> localizer)
+{
+    public string Label(string key) => localizer[key];
+    public string Other(string key) => Strings.ResourceManager.GetString(key);
+    public object All => localizer.GetAllStrings();
+    public ResourceManager Manager => new ResourceManager(typeof(Strings));
+    public string Text(byte[] bytes) => Encoding.UTF8.GetString(bytes);
+}
+"#;
+        let report = scan(source);
+        let dynamic: Vec<usize> = report.dynamic.iter().map(|usage| usage.line).collect();
+        assert_eq!(vec![3, 4, 5, 6], dynamic);
+        assert!(report.is_possibly_used("Footer"));
+    }
+
+    #[test]
+    fn lookups_on_other_receivers_are_not_counted() {
+        let source = r#"public class Settings(ResourceManager manager, IConfiguration config)
+{
+    public string Footer => manager.GetString("Footer");
+    public string Title => config.GetSection("App").GetString("Title");
+    public string Email => Other.ResourceManager.GetString("Email");
+    public string Greeting => Strings.ResourceManager.GetString("Greeting");
+}
+"#;
+        let report = scan(source);
+        assert_eq!(vec!["Greeting"], report.keys.keys().collect::<Vec<_>>());
+        assert!(report.dynamic.is_empty());
+        assert!(!report.is_possibly_used("Footer"));
+    }
+}
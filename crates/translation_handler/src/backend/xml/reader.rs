@@ -3,55 +3,403 @@ use quick_xml::events::Event;
 use quick_xml::name::QName;
 use quick_xml::Reader;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
 use std::path::{Path, PathBuf};
 
+/// A `<data>` element of a resx file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResxResource {
+    pub name: String,
+    pub value: String,
+    pub comment: Option<String>,
+    /// Assembly-qualified type of resources that are not strings, such as images
+    pub type_name: Option<String>,
+    pub mimetype: Option<String>,
+}
+
+impl ResxResource {
+    /// Whether the value is a plain string. Serialized objects (`mimetype`) and typed resources
+    /// such as `ResXFileRef` images can't be edited as text.
+    pub fn is_editable(&self) -> bool {
+        self.mimetype.is_none()
+            && self
+                .type_name
+                .as_deref()
+                .map_or(true, |type_name| type_name.starts_with("System.String"))
+    }
+}
+
+/// A resx file that can't be read, with the position of the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResxError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ResxError {
+    fn at(source: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map(|line| line.chars().count())
+            .unwrap_or(0)
+            + 1;
+        Self {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for ResxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ResxError {}
+
+impl From<ResxError> for io::Error {
+    fn from(error: ResxError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+    }
+}
+
+/// Whether an `<EmbeddedResource>` item adds files, changes the metadata of files added
+/// elsewhere (by the SDK's default globs) or takes files out again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemAction {
+    Include,
+    #[default]
+    Update,
+    Remove,
+}
+
+/// An `<EmbeddedResource>` item of a csproj file with the code generator settings Visual Studio
+/// keeps on it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceItem {
+    /// The `Update`, `Include` or `Remove` path, relative to the csproj file. May hold
+    /// wildcards and several paths separated by `;`.
+    pub path: String,
+    pub action: ItemAction,
+    /// Path the file is shown at in the project when it lives outside of the project folder
+    pub link: Option<String>,
+    /// Manifest resource name that replaces the one built from the path, as in
+    /// `Shop.Texts.resources`
+    pub logical_name: Option<String>,
+    pub generator: Option<String>,
+    pub last_gen_output: Option<String>,
+    pub custom_tool_namespace: Option<String>,
+}
+
 impl XmlHandler {
+    /// The string resources of a resx file, keyed by name, with their value under `default`.
     pub fn read_name_attributes_and_value_tags(
         input_string: &str,
-    ) -> BTreeMap<String, BTreeMap<String, String>> {
+    ) -> Result<BTreeMap<String, BTreeMap<String, String>>, ResxError> {
+        let return_values = Self::read_resources(input_string)?
+            .into_iter()
+            .filter(|resource| resource.is_editable())
+            .map(|resource| {
+                let inner_map = BTreeMap::from([("default".to_string(), resource.value)]);
+                (resource.name, inner_map)
+            })
+            .collect();
+        Ok(return_values)
+    }
+
+    /// Names of the `<data>` elements in the order they are written.
+    pub fn read_data_names(input_string: &str) -> Vec<String> {
         let mut reader = Reader::from_str(input_string);
-        reader.trim_text(true);
-        let mut return_values: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let mut names = Vec::new();
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) if e.name() == QName(b"data") => {
+                    names.extend(
+                        e.attributes()
+                            .filter_map(|a| a.ok())
+                            .filter(|attr| attr.key == QName(b"name"))
+                            .filter_map(|attr| attr.decode_and_unescape_value(&reader).ok())
+                            .map(|name| name.to_string()),
+                    );
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+                _ => (),
+            }
+            buf.clear();
+        }
+        names
+    }
+
+    /// Reads the `<comment>` of every `<data>` element that has one, keyed by its name.
+    pub fn read_comments(input_string: &str) -> Result<BTreeMap<String, String>, ResxError> {
+        let comments = Self::read_resources(input_string)?
+            .into_iter()
+            .filter_map(|resource| Some((resource.name, resource.comment?)))
+            .collect();
+        Ok(comments)
+    }
 
+    /// Every `<data>` element with its value, comment and type, in the order they are written.
+    /// Values joined from text, entities and CDATA sections keep their line breaks; whitespace
+    /// alone only counts with `xml:space="preserve"`, as in ResXResourceReader. Empty values are
+    /// read as empty strings.
+    pub fn read_resources(input_string: &str) -> Result<Vec<ResxResource>, ResxError> {
+        let mut reader = Reader::from_str(input_string);
+        let mut resources: Vec<ResxResource> = Vec::new();
+        let mut open_elements: Vec<Vec<u8>> = Vec::new();
+        let mut current: Option<(ResxResource, bool)> = None;
+        let mut text: Option<String> = None;
+
+        loop {
+            let position = reader.buffer_position();
+            let event = reader
+                .read_event()
+                .map_err(|e| ResxError::at(input_string, position, e.to_string()))?;
+            if let Event::Start(ref e) = event {
+                open_elements.push(e.name().as_ref().to_vec());
+            }
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) if e.name() == QName(b"data") => {
+                    let mut resource = ResxResource::default();
+                    let mut name = None;
+                    let mut preserve = false;
+                    for attr in e.attributes() {
+                        let attr =
+                            attr.map_err(|e| ResxError::at(input_string, position, e.to_string()))?;
+                        let value = attr
+                            .decode_and_unescape_value(&reader)
+                            .map_err(|e| ResxError::at(input_string, position, e.to_string()))?
+                            .to_string();
+                        match attr.key {
+                            QName(b"name") => name = Some(value),
+                            QName(b"type") => resource.type_name = Some(value),
+                            QName(b"mimetype") => resource.mimetype = Some(value),
+                            QName(b"xml:space") => preserve = value == "preserve",
+                            _ => (),
+                        }
+                    }
+                    resource.name = name.ok_or_else(|| {
+                        ResxError::at(input_string, position, "<data> has no name attribute")
+                    })?;
+                    if let Event::Empty(_) = event {
+                        resources.push(resource);
+                    } else {
+                        current = Some((resource, preserve));
+                    }
+                }
+                Event::Start(ref e) if current.is_some() => {
+                    if matches!(e.name().as_ref(), b"value" | b"comment") {
+                        text = Some(String::new());
+                    }
+                }
+                Event::Empty(ref e) if e.name() == QName(b"comment") => {
+                    if let Some((resource, _)) = current.as_mut() {
+                        resource.comment = Some(String::new());
+                    }
+                }
+                Event::Text(ref e) if text.is_some() => {
+                    let unescaped = e
+                        .unescape()
+                        .map_err(|e| ResxError::at(input_string, position, e.to_string()))?;
+                    text.get_or_insert_with(String::new)
+                        .push_str(&unescaped.replace("\r\n", "\n"));
+                }
+                Event::CData(ref e) if text.is_some() => {
+                    let cdata = String::from_utf8_lossy(e).replace("\r\n", "\n");
+                    text.get_or_insert_with(String::new).push_str(&cdata);
+                }
+                // The reader rejects end tags that don't match the open element
+                Event::End(ref e) => {
+                    open_elements.pop();
+                    match e.name().as_ref() {
+                        b"data" => resources.extend(current.take().map(|(resource, _)| resource)),
+                        child @ (b"value" | b"comment") => {
+                            if let (Some((resource, preserve)), Some(text)) =
+                                (current.as_mut(), text.take())
+                            {
+                                let text = if *preserve || !text.trim().is_empty() {
+                                    text
+                                } else {
+                                    String::new()
+                                };
+                                if child == b"value" {
+                                    resource.value = text;
+                                } else {
+                                    resource.comment = Some(text);
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                Event::Eof => {
+                    if let Some(name) = open_elements.last() {
+                        return Err(ResxError::at(
+                            input_string,
+                            position,
+                            format!("<{}> is not closed", String::from_utf8_lossy(name)),
+                        ));
+                    }
+                    break;
+                }
+                _ => (),
+            }
+        }
+        Ok(resources)
+    }
+
+    /// The `<EmbeddedResource>` items of a csproj file with their generator settings.
+    pub fn get_resource_items(input_string: &str) -> Vec<ResourceItem> {
+        let mut reader = Reader::from_str(input_string);
+        reader.trim_text(true);
+        let mut items: Vec<ResourceItem> = Vec::new();
         let mut buf = Vec::new();
-        let mut inside_data = false;
-        let mut attribute_name = String::new();
-        let mut inside_name = false;
+        let mut current: Option<ResourceItem> = None;
+        let mut child: Option<Vec<u8>> = None;
 
         loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) if e.name() == QName(b"data") => {
+            let event = match reader.read_event_into(&mut buf) {
+                Ok(event) => event,
+                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            };
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e)
+                    if e.name() == QName(b"EmbeddedResource") =>
+                {
+                    let mut item: Option<ResourceItem> = None;
+                    let mut link = None;
+                    let mut logical_name = None;
                     for attr in e.attributes().filter_map(|a| a.ok()) {
-                        if attr.key == QName(b"name") {
-                            let name_value = attr.decode_and_unescape_value(&reader).unwrap();
-                            attribute_name = name_value.to_string();
-                            inside_data = true;
+                        let action = match attr.key {
+                            QName(b"Include") => ItemAction::Include,
+                            QName(b"Update") => ItemAction::Update,
+                            QName(b"Remove") => ItemAction::Remove,
+                            QName(b"Link") => {
+                                link = attr.decode_and_unescape_value(&reader).ok();
+                                continue;
+                            }
+                            QName(b"LogicalName") => {
+                                logical_name = attr.decode_and_unescape_value(&reader).ok();
+                                continue;
+                            }
+                            _ => continue,
+                        };
+                        if let Ok(path) = attr.decode_and_unescape_value(&reader) {
+                            item = Some(ResourceItem {
+                                path: path.to_string(),
+                                action,
+                                ..Default::default()
+                            });
                         }
                     }
+                    if let Some(item) = item.as_mut() {
+                        item.link = link.map(|link| link.to_string());
+                        item.logical_name = logical_name.map(|name| name.to_string());
+                    }
+                    if let Event::Empty(_) = event {
+                        items.extend(item);
+                    } else {
+                        current = item;
+                    }
                 }
-                Ok(Event::Start(ref e)) if e.name() == QName(b"value") && inside_data => {
-                    inside_name = true;
+                Event::Start(ref e) if current.is_some() => {
+                    child = Some(e.name().as_ref().to_vec());
                 }
-                Ok(Event::End(ref e)) if e.name() == QName(b"value") && inside_data => {
-                    inside_name = false;
+                Event::Text(ref e) if child.is_some() => {
+                    let text = e
+                        .unescape()
+                        .map(|text| text.to_string())
+                        .unwrap_or_default();
+                    if let Some(item) = current.as_mut() {
+                        match child.as_deref() {
+                            Some(b"Generator") => item.generator = Some(text),
+                            Some(b"LastGenOutput") => item.last_gen_output = Some(text),
+                            Some(b"CustomToolNamespace") => item.custom_tool_namespace = Some(text),
+                            Some(b"Link") => item.link = Some(text),
+                            Some(b"LogicalName") => item.logical_name = Some(text),
+                            _ => (),
+                        }
+                    }
                 }
-                Ok(Event::End(ref e)) if e.name() == QName(b"data") => {
-                    inside_data = false;
+                Event::End(ref e) if e.name() == QName(b"EmbeddedResource") => {
+                    items.extend(current.take());
+                    child = None;
                 }
-                Ok(Event::Text(e)) if inside_name => {
-                    let value_content = e.unescape().unwrap();
-                    let mut inner_map: BTreeMap<String, String> = BTreeMap::new();
-                    inner_map.insert("default".to_string(), value_content.to_string());
-                    return_values.insert(attribute_name, inner_map);
-                    attribute_name = String::new();
+                Event::End(_) => child = None,
+                Event::Eof => break,
+                _ => (),
+            }
+            buf.clear();
+        }
+        items
+    }
+
+    /// The `<RootNamespace>` of a csproj file, which generated classes and embedded resource
+    /// names start with.
+    pub fn get_root_namespace(input_string: &str) -> Option<String> {
+        Self::get_property(input_string, "RootNamespace")
+    }
+
+    /// The first value of an MSBuild property, such as `<RootNamespace>`, in a project file.
+    pub fn get_property(input_string: &str, property: &str) -> Option<String> {
+        let mut reader = Reader::from_str(input_string);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+        let mut inside = false;
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => inside = e.name() == QName(property.as_bytes()),
+                Ok(Event::Text(e)) if inside => {
+                    return e.unescape().ok().map(|text| text.trim().to_string());
                 }
-                Ok(Event::Eof) => break,
-                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+                Ok(Event::End(_)) => inside = false,
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => (),
+            }
+            buf.clear();
+        }
+    }
+
+    /// Whether a project file is SDK-style (`<Project Sdk="...">`, `<Sdk Name="..." />` or
+    /// `<Import Sdk="..." />`), which embeds every resx file below it without listing them.
+    pub fn is_sdk_project(input_string: &str) -> bool {
+        let mut reader = Reader::from_str(input_string);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let attribute = match e.name() {
+                        QName(b"Project") | QName(b"Import") => Some(QName(b"Sdk")),
+                        QName(b"Sdk") => Some(QName(b"Name")),
+                        _ => None,
+                    };
+                    let has_sdk = attribute.is_some_and(|attribute| {
+                        e.attributes()
+                            .filter_map(|a| a.ok())
+                            .any(|attr| attr.key == attribute)
+                    });
+                    if has_sdk {
+                        return true;
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => return false,
                 _ => (),
             }
             buf.clear();
         }
-        return_values
     }
 
     pub fn get_resources(input_string: &str, original_path: &str) -> Vec<String> {
@@ -105,7 +453,7 @@ mod tests {
             </data>
             "#;
 
-        let response = super::XmlHandler::read_name_attributes_and_value_tags(xml);
+        let response = super::XmlHandler::read_name_attributes_and_value_tags(xml).unwrap();
 
         assert_eq!(2, response.len());
         //*
@@ -120,6 +468,27 @@ mod tests {
         assert_eq!(None, response.get("Wrong Input"));
     }
 
+    #[test]
+    fn comments_are_read_by_name() {
+        let xml = r#"
+            <data name="Button_Save" xml:space="preserve">
+                <value>Save</value>
+                <comment>Button label, max 20 chars &amp; no period</comment>
+            </data>
+            <data name="Label_Name" xml:space="preserve">
+                <value>Name</value>
+            </data>
+            "#;
+
+        let comments = super::XmlHandler::read_comments(xml).unwrap();
+
+        assert_eq!(1, comments.len());
+        assert_eq!(
+            Some(&"Button label, max 20 chars & no period".to_string()),
+            comments.get("Button_Save")
+        );
+    }
+
     #[test]
     fn get_resources() {
         let xml = r#"
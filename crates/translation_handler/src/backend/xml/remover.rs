@@ -1,85 +1,80 @@
 use crate::backend::xml::XmlHandler;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesText, Event};
 use quick_xml::name::QName;
 use quick_xml::{Reader, Writer};
-use std::fs::{File, OpenOptions};
-
-use std::io::{BufReader, BufWriter, Cursor, Write};
+use std::fs;
+use std::io::Cursor;
 
 impl XmlHandler {
-    pub fn remove_key_value(
-        file_path: String,
-        keys: Vec<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let file = File::open(file_path.clone())?;
-        let file_reader = BufReader::new(file);
-
-        let mut reader = Reader::from_reader(file_reader);
+    /// Removes the `<data>` elements named in `keys`, with the whitespace before them, from
+    /// resx content. Returns the new content and the names that were removed.
+    pub fn remove_data(
+        input_string: &str,
+        keys: &[String],
+    ) -> Result<(String, Vec<String>), quick_xml::Error> {
+        let mut reader = Reader::from_str(input_string);
         reader.trim_text(false);
-
         let mut writer = Writer::new(Cursor::new(Vec::new()));
-        let mut buf = Vec::new();
-
-        let mut inside_searched_tag = false;
-        let mut tag_depth = 0;
+        let mut removed = Vec::new();
+        // Whitespace is held back until it is known whether a removed element follows
+        let mut pending_text: Option<BytesText> = None;
 
-        while let Ok(event) = reader.read_event_into(&mut buf) {
-            match event {
-                Event::Start(ref e) if e.name() == QName(b"data") => {
-                    let mut is_target_tag = false;
-                    for attr in e.attributes().filter_map(|a| a.ok()) {
-                        if attr.key == QName(b"name") {
-                            if let Ok(attr_value) = std::str::from_utf8(&attr.value) {
-                                if keys.contains(&attr_value.to_string()) {
-                                    is_target_tag = true;
-                                    inside_searched_tag = true;
-                                    tag_depth = 1; // Start tracking depth
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    if !is_target_tag {
-                        writer.write_event(&event)?;
+        loop {
+            match reader.read_event()? {
+                Event::Text(e) if e.iter().all(u8::is_ascii_whitespace) => {
+                    if let Some(text) = pending_text.replace(e) {
+                        writer.write_event(Event::Text(text))?;
                     }
                 }
-                Event::End(ref e) if e.name() == QName(b"data") => {
-                    if inside_searched_tag {
-                        tag_depth -= 1;
-                        if tag_depth == 0 {
-                            inside_searched_tag = false;
+                Event::Start(e) if e.name() == QName(b"data") => {
+                    let name = e
+                        .attributes()
+                        .filter_map(|a| a.ok())
+                        .find(|attr| attr.key == QName(b"name"))
+                        .and_then(|attr| attr.decode_and_unescape_value(&reader).ok())
+                        .map(|name| name.to_string());
+                    match name.filter(|name| keys.contains(name)) {
+                        Some(name) => {
+                            pending_text = None;
+                            reader.read_to_end(QName(b"data"))?;
+                            removed.push(name);
+                        }
+                        None => {
+                            if let Some(text) = pending_text.take() {
+                                writer.write_event(Event::Text(text))?;
+                            }
+                            writer.write_event(Event::Start(e))?;
                         }
-                    } else {
-                        writer.write_event(&event)?;
                     }
                 }
                 Event::Eof => {
+                    if let Some(text) = pending_text.take() {
+                        writer.write_event(Event::Text(text))?;
+                    }
                     break;
                 }
-                _ => {
-                    if inside_searched_tag {
-                        if let Event::Start(_) = event {
-                            tag_depth += 1;
-                        } else if let Event::End(_) = event {
-                            tag_depth -= 1;
-                        }
-                    } else {
-                        writer.write_event(&event)?;
+                event => {
+                    if let Some(text) = pending_text.take() {
+                        writer.write_event(Event::Text(text))?;
                     }
+                    writer.write_event(event)?;
                 }
             }
-            buf.clear();
         }
 
-        let result = writer.into_inner().into_inner();
-        let file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(file_path.clone())?;
-
-        let mut file_writer = BufWriter::new(file);
-        file_writer.write_all(&*result)?;
+        let content = String::from_utf8_lossy(&writer.into_inner().into_inner()).to_string();
+        Ok((content, removed))
+    }
 
+    pub fn remove_key_value(
+        file_path: String,
+        keys: Vec<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let xml = fs::read_to_string(&file_path)?;
+        let (content, removed) = Self::remove_data(&xml, &keys)?;
+        if !removed.is_empty() {
+            fs::write(&file_path, content)?;
+        }
         Ok(())
     }
 }
@@ -3,21 +3,53 @@ use quick_xml::{
     events::{BytesEnd, BytesStart, BytesText, Event},
     Reader, Writer,
 };
-use std::fs::{File, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufReader, BufWriter, Cursor, Write};
 
 use crate::backend::xml::XmlHandler;
 
+/// Writes `<comment>` with the escaped text, or nothing for an empty comment.
+fn write_comment(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    comment: &str,
+) -> Result<(), quick_xml::Error> {
+    if !comment.is_empty() {
+        writer.write_event(Event::Start(BytesStart::new("comment")))?;
+        writer.write_event(Event::Text(BytesText::new(comment)))?;
+        writer.write_event(Event::End(BytesEnd::new("comment")))?;
+    }
+    Ok(())
+}
+
 impl XmlHandler {
+    /// Replaces the `<value>` of the `<data>` element named `key`. Returns whether the file has
+    /// such an element.
     pub fn update_value(
         file_path: String,
         key: String,
         value: String,
-    ) -> Result<(), quick_xml::Error> {
-        let file = File::open(&file_path)?;
-        let file_reader = BufReader::new(file);
+    ) -> Result<bool, quick_xml::Error> {
+        let input_string = fs::read_to_string(&file_path)?;
+        let (result, value_replaced) = Self::replace_value(&input_string, &key, &value)?;
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(file_path)?;
+        let mut file_writer = BufWriter::new(file);
+        file_writer.write_all(result.as_bytes())?;
+        file_writer.flush()?;
 
-        let mut reader = Reader::from_reader(file_reader);
+        Ok(value_replaced)
+    }
+
+    /// Replaces the `<value>` of the `<data>` element named `key` in resx content. Returns the
+    /// new content and whether it has such an element.
+    pub fn replace_value(
+        input_string: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(String, bool), quick_xml::Error> {
+        let mut reader = Reader::from_reader(input_string.as_bytes());
         reader.trim_text(false);
 
         let mut writer = Writer::new(Cursor::new(Vec::new()));
@@ -51,7 +83,7 @@ impl XmlHandler {
                         if !value_replaced {
                             // Write new <value> tag
                             writer.write_event(Event::Start(BytesStart::new("value")))?;
-                            writer.write_event(Event::Text(BytesText::new(&value)))?;
+                            writer.write_event(Event::Text(BytesText::new(value)))?;
                             writer.write_event(Event::End(BytesEnd::new("value")))?;
                             value_replaced = true;
                         }
@@ -63,7 +95,7 @@ impl XmlHandler {
                     if !value_replaced {
                         // Write new <value> tag instead of the original
                         writer.write_event(Event::Start(BytesStart::new("value")))?;
-                        writer.write_event(Event::Text(BytesText::new(&value)))?;
+                        writer.write_event(Event::Text(BytesText::new(value)))?;
                         writer.write_event(Event::End(BytesEnd::new("value")))?;
                         value_replaced = true;
                     }
@@ -77,8 +109,14 @@ impl XmlHandler {
                         }
                     }
                 }
-                Event::Text(_) | Event::CData(_) if inside_searched_tag && !value_replaced => {
-                    // Skip stray text or CDATA inside <data> before <value>
+                Event::Text(e) if inside_searched_tag && !value_replaced => {
+                    // Skip stray text inside <data> before <value>, but keep the indentation
+                    if e.iter().all(u8::is_ascii_whitespace) {
+                        writer.write_event(Event::Text(e))?;
+                    }
+                }
+                Event::CData(_) if inside_searched_tag && !value_replaced => {
+                    // Skip stray CDATA inside <data> before <value>
                 }
                 Event::Eof => break,
                 Event::Start(e) => {
@@ -102,6 +140,91 @@ impl XmlHandler {
             buf.clear();
         }
 
+        let result = String::from_utf8_lossy(&writer.into_inner().into_inner()).to_string();
+        Ok((result, value_replaced))
+    }
+
+    /// Sets the `<comment>` of the `<data>` element named `key`, adding one after its value
+    /// when it has none. An empty comment removes the element.
+    pub fn update_comment(
+        file_path: String,
+        key: String,
+        comment: String,
+    ) -> Result<(), quick_xml::Error> {
+        let file = File::open(&file_path)?;
+        let file_reader = BufReader::new(file);
+
+        let mut reader = Reader::from_reader(file_reader);
+        reader.trim_text(false);
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        let mut buf = Vec::new();
+
+        let mut inside_searched_tag = false;
+        let mut comment_written = false;
+        // Whitespace before the first child, which a new <comment> is indented with
+        let mut child_indent: Option<String> = None;
+        // Whitespace inside the searched tag is held back, as it belongs to a removed comment
+        let mut pending_text: Option<BytesText> = None;
+
+        loop {
+            let event = reader.read_event_into(&mut buf)?;
+            if inside_searched_tag {
+                match event {
+                    Event::Text(e) if e.iter().all(u8::is_ascii_whitespace) => {
+                        if child_indent.is_none() {
+                            child_indent = Some(String::from_utf8_lossy(&e).to_string());
+                        }
+                        if let Some(text) = pending_text.replace(e.into_owned()) {
+                            writer.write_event(Event::Text(text))?;
+                        }
+                    }
+                    Event::Start(ref e) | Event::Empty(ref e) if e.name() == QName(b"comment") => {
+                        if matches!(event, Event::Start(_)) {
+                            reader.read_to_end_into(QName(b"comment"), &mut Vec::new())?;
+                        }
+                        // A removed comment takes the whitespace before it along
+                        if let Some(text) = pending_text.take().filter(|_| !comment.is_empty()) {
+                            writer.write_event(Event::Text(text))?;
+                        }
+                        write_comment(&mut writer, &comment)?;
+                        comment_written = true;
+                    }
+                    Event::End(e) if e.name() == QName(b"data") => {
+                        if !comment_written && !comment.is_empty() {
+                            let indent = child_indent.take().unwrap_or_default();
+                            writer.write_event(Event::Text(BytesText::from_escaped(indent)))?;
+                            write_comment(&mut writer, &comment)?;
+                        }
+                        if let Some(text) = pending_text.take() {
+                            writer.write_event(Event::Text(text))?;
+                        }
+                        writer.write_event(Event::End(e))?;
+                        inside_searched_tag = false;
+                    }
+                    Event::Eof => break,
+                    event => {
+                        if let Some(text) = pending_text.take() {
+                            writer.write_event(Event::Text(text))?;
+                        }
+                        writer.write_event(event)?;
+                    }
+                }
+            } else {
+                match event {
+                    Event::Start(e) if e.name() == QName(b"data") => {
+                        inside_searched_tag = e.attributes().filter_map(|a| a.ok()).any(|attr| {
+                            attr.key == QName(b"name") && attr.value.as_ref() == key.as_bytes()
+                        });
+                        writer.write_event(Event::Start(e))?;
+                    }
+                    Event::Eof => break,
+                    event => writer.write_event(event)?,
+                }
+            }
+            buf.clear();
+        }
+
         let result = writer.into_inner().into_inner();
         let file = OpenOptions::new()
             .write(true)
@@ -1,61 +1,284 @@
 use crate::backend::xml::XmlHandler;
+use quick_xml::escape::{escape, partial_escape};
+use quick_xml::events::Event;
 use quick_xml::name::QName;
-use quick_xml::{
-    events::{BytesEnd, BytesStart, BytesText, Event},
-    Reader, Writer,
-};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, BufWriter, Cursor, Write};
+use quick_xml::Reader;
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
 
-impl XmlHandler {
-    pub fn write_key_value(
-        file_path: String,
-        key: String,
-        value: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let file = File::open(file_path.clone())?;
-        let file_reader = BufReader::new(file);
-
-        let mut reader = Reader::from_reader(file_reader);
-        reader.trim_text(false);
-
-        let mut writer = Writer::new(Cursor::new(Vec::new()));
-        let mut buf = Vec::new();
-        let data_opening_tag = format!("    <data name={} xml:space=\"preserve\">\n", key);
-        let value_tag = format!("    <value>{}</value>\n", value);
-        let data_closing_tag = "  </data>\n".to_string();
-
-        while let Ok(event) = reader.read_event_into(&mut buf) {
-            match event {
-                Event::End(ref e) if e.name() == QName(b"root") => {
-                    writer.write_event(Event::Text(BytesText::from_escaped(
-                        data_opening_tag.clone(),
-                    )))?;
-                    writer.write_event(Event::Text(BytesText::from_escaped(value_tag.clone())))?;
-                    writer.write_event(Event::Text(BytesText::from_escaped(
-                        data_closing_tag.clone(),
-                    )))?;
-                    writer.write_event(Event::End(e.to_owned()))?;
+/// Header Visual Studio writes into new resx files, without the optional inline schema.
+const RESX_TEMPLATE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <resheader name="resmimetype">
+    <value>text/microsoft-resx</value>
+  </resheader>
+  <resheader name="version">
+    <value>2.0</value>
+  </resheader>
+  <resheader name="reader">
+    <value>System.Resources.ResXResourceReader, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+  </resheader>
+  <resheader name="writer">
+    <value>System.Resources.ResXResourceWriter, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+  </resheader>
+</root>
+"#;
+
+/// Where `XmlHandler::insert_data` puts a new `<data>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// After the last `<data>` element
+    End,
+    /// Before the first `<data>` element whose name sorts after the new one
+    Sorted,
+}
+
+/// A `<data>` element of a resx file and the bytes it spans.
+struct DataElement {
+    name: String,
+    span: Range<usize>,
+}
+
+/// Layout of a resx file that new elements are written to match.
+struct ResxLayout {
+    elements: Vec<DataElement>,
+    /// Position of `</root>`
+    root_end: usize,
+    newline: &'static str,
+    /// Indentation of `<data>` and of its children
+    indent: String,
+    child_indent: String,
+}
+
+/// Names compare ignoring case, as ResXManager and Visual Studio list them.
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+/// Whitespace from the start of the line to `position`, if nothing else is in between.
+fn line_indent(xml: &str, position: usize) -> Option<&str> {
+    let line_start = xml[..position].rfind('\n').map_or(0, |index| index + 1);
+    let indent = &xml[line_start..position];
+    indent.trim().is_empty().then_some(indent)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_layout(xml: &str) -> Result<ResxLayout, io::Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut elements = Vec::new();
+    let mut root_end = None;
+    let mut child_indent = None;
+    let mut open: Option<(String, usize)> = None;
+    loop {
+        let start = reader.buffer_position();
+        let event = reader.read_event().map_err(|e| {
+            invalid(format!(
+                "error at position {}: {}",
+                reader.buffer_position(),
+                e
+            ))
+        })?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) if e.name() == QName(b"data") => {
+                let name = e
+                    .attributes()
+                    .filter_map(|a| a.ok())
+                    .find(|attr| attr.key == QName(b"name"))
+                    .and_then(|attr| attr.decode_and_unescape_value(&reader).ok())
+                    .map(|name| name.to_string())
+                    .unwrap_or_default();
+                if matches!(event, Event::Empty(_)) {
+                    let span = start..reader.buffer_position();
+                    elements.push(DataElement { name, span });
+                } else {
+                    open = Some((name, start));
                 }
-                Event::Eof => {
-                    break;
+            }
+            Event::Text(ref e) if open.is_some() && child_indent.is_none() => {
+                let text = String::from_utf8_lossy(e);
+                if let Some((_, indent)) = text.rsplit_once('\n') {
+                    child_indent = Some(indent.to_string());
                 }
-                _ => {
-                    writer.write_event(&event)?;
+            }
+            Event::End(ref e) if e.name() == QName(b"data") => {
+                if let Some((name, start)) = open.take() {
+                    let span = start..reader.buffer_position();
+                    elements.push(DataElement { name, span });
                 }
             }
-            buf.clear();
+            Event::End(ref e) if e.name() == QName(b"root") => root_end = Some(start),
+            Event::Eof => break,
+            _ => (),
         }
+    }
+    let root_end =
+        root_end.ok_or_else(|| invalid("the file has no </root> element".to_string()))?;
+    let indent = elements
+        .first()
+        .and_then(|element| line_indent(xml, element.span.start))
+        .unwrap_or("  ")
+        .to_string();
+    let child_indent = child_indent
+        .filter(|child| child.len() > indent.len())
+        .unwrap_or_else(|| indent.repeat(2));
+    Ok(ResxLayout {
+        elements,
+        root_end,
+        newline: if xml.contains("\r\n") { "\r\n" } else { "\n" },
+        indent,
+        child_indent,
+    })
+}
 
-        let result = writer.into_inner().into_inner();
-        let file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(file_path.clone())?;
+/// Resx content with a `<data>` element added, indented and line-broken like the elements
+/// already in it. Fails when the content has the key.
+fn insert_block(
+    content: &str,
+    file_path: &Path,
+    key: &str,
+    value: &str,
+    comment: Option<&str>,
+    placement: Placement,
+) -> Result<String, io::Error> {
+    let (bom, xml) = match content.strip_prefix('\u{feff}') {
+        Some(xml) => ("\u{feff}", xml),
+        None => ("", content),
+    };
+    let layout = read_layout(xml)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", file_path.display(), e)))?;
+    if layout.elements.iter().any(|element| element.name == key) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has the key {}", file_path.display(), key),
+        ));
+    }
+
+    let ResxLayout {
+        newline,
+        indent,
+        child_indent,
+        ..
+    } = &layout;
+    let mut block = format!(
+        "{indent}<data name=\"{}\" xml:space=\"preserve\">{newline}\
+         {child_indent}<value>{}</value>{newline}",
+        escape(key),
+        partial_escape(value),
+    );
+    if let Some(comment) = comment.filter(|comment| !comment.is_empty()) {
+        block.push_str(&format!(
+            "{child_indent}<comment>{}</comment>{newline}",
+            partial_escape(comment)
+        ));
+    }
+    block.push_str(&format!("{indent}</data>{newline}"));
+
+    let next = match placement {
+        Placement::Sorted => layout
+            .elements
+            .iter()
+            .find(|element| compare_names(&element.name, key) == Ordering::Greater),
+        Placement::End => None,
+    };
+    // Insert at the start of a line when possible, otherwise break the line first
+    let (position, text) = match (next, layout.elements.last()) {
+        (Some(element), _) => match line_indent(xml, element.span.start) {
+            Some(line) => (element.span.start - line.len(), block),
+            None => (element.span.start, format!("{newline}{block}")),
+        },
+        (None, Some(last)) => {
+            let rest = &xml[last.span.end..];
+            match rest.find('\n') {
+                Some(index) if rest[..index].trim().is_empty() => {
+                    (last.span.end + index + 1, block)
+                }
+                _ => (last.span.end, format!("{newline}{block}")),
+            }
+        }
+        (None, None) => match line_indent(xml, layout.root_end) {
+            Some(line) => (layout.root_end - line.len(), block),
+            None => (layout.root_end, format!("{newline}{block}")),
+        },
+    };
+    let mut updated = String::with_capacity(content.len() + text.len());
+    updated.push_str(bom);
+    updated.push_str(&xml[..position]);
+    updated.push_str(&text);
+    updated.push_str(&xml[position..]);
+    Ok(updated)
+}
+
+impl XmlHandler {
+    /// Writes a resx file without any resources.
+    pub fn create_resx_file(path: &Path) -> Result<(), std::io::Error> {
+        std::fs::write(path, RESX_TEMPLATE)
+    }
 
-        let mut file_writer = BufWriter::new(file);
-        file_writer.write_all(&*result)?;
+    /// Whether the `<data>` elements of a resx file are sorted by name.
+    pub fn is_sorted(input_string: &str) -> bool {
+        let names = Self::read_data_names(input_string);
+        names
+            .windows(2)
+            .all(|pair| compare_names(&pair[0], &pair[1]) != Ordering::Greater)
+    }
+
+    /// Adds a `<data>` element with an escaped value and optional comment, indented and
+    /// line-broken like the elements already in the file. Fails when the file has the key.
+    pub fn insert_data(
+        file_path: &Path,
+        key: &str,
+        value: &str,
+        comment: Option<&str>,
+        placement: Placement,
+    ) -> Result<(), io::Error> {
+        let content = fs::read_to_string(file_path)?;
+        let updated = insert_block(&content, file_path, key, value, comment, placement)?;
+        fs::write(file_path, updated)
+    }
+
+    /// Sets `values` in a resx file with one write, replacing the `<value>` of the elements it
+    /// has and adding elements for the other keys. New elements keep the file sorted when it is.
+    pub fn set_values(file_path: &Path, values: &[(String, String)]) -> Result<(), io::Error> {
+        let mut content = fs::read_to_string(file_path)?;
+        let placement = if Self::is_sorted(&content) {
+            Placement::Sorted
+        } else {
+            Placement::End
+        };
+        for (key, value) in values {
+            let (updated, replaced) = Self::replace_value(&content, key, value)
+                .map_err(|e| invalid(format!("{}: {}", file_path.display(), e)))?;
+            content = if replaced {
+                updated
+            } else {
+                insert_block(&content, file_path, key, value, None, placement)?
+            };
+        }
+        fs::write(file_path, content)
+    }
 
+    /// Adds a `<data>` element, keeping the elements sorted when they already are.
+    pub fn write_key_value(
+        file_path: String,
+        key: String,
+        value: String,
+        comment: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::new(&file_path);
+        let placement = if Self::is_sorted(&fs::read_to_string(path)?) {
+            Placement::Sorted
+        } else {
+            Placement::End
+        };
+        Self::insert_data(path, &key, &value, comment.as_deref(), placement)?;
         Ok(())
     }
 }
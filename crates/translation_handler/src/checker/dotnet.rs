@@ -0,0 +1,77 @@
+use crate::checker::{Placeholder, PlaceholderKind, SyntaxError};
+
+/// Parses a .NET composite format string: `{index[,alignment][:format]}` items with `{{` and
+/// `}}` as escaped braces.
+pub(super) fn parse(source: &str) -> Result<Vec<Placeholder>, SyntaxError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut placeholders = Vec::new();
+    let mut pos = 0;
+    let error = |message: &str, offset: usize| {
+        Err(SyntaxError {
+            message: message.to_string(),
+            offset,
+        })
+    };
+
+    while pos < chars.len() {
+        match chars[pos] {
+            '{' if chars.get(pos + 1) == Some(&'{') => pos += 2,
+            '}' if chars.get(pos + 1) == Some(&'}') => pos += 2,
+            '}' => return error("unmatched `}`", pos),
+            '{' => {
+                let start = pos;
+                pos += 1;
+                let index_start = pos;
+                while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+                    pos += 1;
+                }
+                if pos == index_start {
+                    return error("expected a format item index", pos);
+                }
+                let index: String = chars[index_start..pos].iter().collect();
+                while chars.get(pos).is_some_and(|c| *c == ' ') {
+                    pos += 1;
+                }
+                if chars.get(pos) == Some(&',') {
+                    pos += 1;
+                    while chars.get(pos).is_some_and(|c| *c == ' ') {
+                        pos += 1;
+                    }
+                    if chars.get(pos) == Some(&'-') {
+                        pos += 1;
+                    }
+                    let alignment_start = pos;
+                    while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+                        pos += 1;
+                    }
+                    if pos == alignment_start {
+                        return error("expected an alignment", pos);
+                    }
+                    while chars.get(pos).is_some_and(|c| *c == ' ') {
+                        pos += 1;
+                    }
+                }
+                if chars.get(pos) == Some(&':') {
+                    while chars.get(pos).is_some_and(|c| *c != '}') {
+                        if chars[pos] == '{' {
+                            return error("unexpected `{` in format string", pos);
+                        }
+                        pos += 1;
+                    }
+                }
+                match chars.get(pos) {
+                    Some('}') => pos += 1,
+                    None => return error("unclosed `{`", start),
+                    Some(_) => return error("expected `}` to close the format item", pos),
+                }
+                placeholders.push(Placeholder {
+                    kind: PlaceholderKind::DotNet,
+                    name: index,
+                    argument_type: None,
+                });
+            }
+            _ => pos += 1,
+        }
+    }
+    Ok(placeholders)
+}
@@ -0,0 +1,240 @@
+use crate::checker::{Placeholder, PlaceholderKind, SyntaxError};
+
+/// Parses a Fluent pattern and returns its variable references. Terms, message references
+/// and literals are checked for syntax but are not placeholders, since translations may
+/// reference other terms than the source.
+pub(super) fn parse(source: &str) -> Result<Vec<Placeholder>, SyntaxError> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        placeholders: Vec::new(),
+    };
+    while let Some(c) = parser.peek() {
+        match c {
+            '{' => parser.parse_placeable()?,
+            '}' => return parser.error("unmatched `}`"),
+            _ => parser.pos += 1,
+        }
+    }
+    Ok(parser.placeholders)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    placeholders: Vec<Placeholder>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, SyntaxError> {
+        Err(SyntaxError {
+            message: message.to_string(),
+            offset: self.pos,
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SyntaxError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(&format!("expected `{}`", expected))
+        }
+    }
+
+    fn read_identifier(&mut self) -> Result<String, SyntaxError> {
+        if !self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            return self.error("expected an identifier");
+        }
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn parse_placeable(&mut self) -> Result<(), SyntaxError> {
+        self.pos += 1;
+        self.skip_whitespace();
+        self.parse_inline_expression()?;
+        self.skip_whitespace();
+        if self.peek() == Some('-') && self.peek_at(1) == Some('>') {
+            self.pos += 2;
+            self.parse_variants()?;
+        }
+        self.expect('}')
+    }
+
+    fn parse_inline_expression(&mut self) -> Result<(), SyntaxError> {
+        match self.peek() {
+            Some('{') => self.parse_placeable(),
+            Some('"') => {
+                self.pos += 1;
+                loop {
+                    match self.peek() {
+                        Some('"') => break,
+                        Some('\\') => self.pos += 2,
+                        Some('\n') | None => return self.error("unterminated string literal"),
+                        Some(_) => self.pos += 1,
+                    }
+                }
+                self.pos += 1;
+                Ok(())
+            }
+            Some('$') => {
+                self.pos += 1;
+                let name = self.read_identifier()?;
+                self.placeholders.push(Placeholder {
+                    kind: PlaceholderKind::Fluent,
+                    name,
+                    argument_type: None,
+                });
+                Ok(())
+            }
+            Some(c)
+                if c.is_ascii_digit()
+                    || (c == '-' && self.peek_at(1).is_some_and(|c| c.is_ascii_digit())) =>
+            {
+                self.pos += 1;
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+            Some('-') => {
+                // Term reference with an optional attribute and arguments
+                self.pos += 1;
+                self.read_identifier()?;
+                self.parse_attribute_accessor()?;
+                if self.peek() == Some('(') {
+                    self.parse_arguments()?;
+                }
+                Ok(())
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let identifier = self.read_identifier()?;
+                let is_function = identifier
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-');
+                if is_function && self.peek() == Some('(') {
+                    self.parse_arguments()
+                } else {
+                    self.parse_attribute_accessor()
+                }
+            }
+            _ => self.error("expected an expression"),
+        }
+    }
+
+    fn parse_attribute_accessor(&mut self) -> Result<(), SyntaxError> {
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            self.read_identifier()?;
+        }
+        Ok(())
+    }
+
+    /// `(positional, named: "literal")` of a function call or a parameterized term.
+    fn parse_arguments(&mut self) -> Result<(), SyntaxError> {
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                self.pos += 1;
+                return Ok(());
+            }
+            self.parse_inline_expression()?;
+            self.skip_whitespace();
+            if self.peek() == Some(':') {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.parse_inline_expression()?;
+                self.skip_whitespace();
+            }
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(')') => {}
+                _ => return self.error("expected `,` or `)`"),
+            }
+        }
+    }
+
+    /// Variants of a select expression up to, but not including, its closing brace.
+    fn parse_variants(&mut self) -> Result<(), SyntaxError> {
+        let mut variants = 0;
+        let mut defaults = 0;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') | None => break,
+                Some('*') => {
+                    defaults += 1;
+                    self.pos += 1;
+                }
+                _ => {}
+            }
+            if self.peek() != Some('[') {
+                return self.error("expected a variant key");
+            }
+            self.pos += 1;
+            while self.peek().is_some_and(|c| c != ']' && c != '\n') {
+                self.pos += 1;
+            }
+            self.expect(']')?;
+            variants += 1;
+            self.parse_variant_pattern()?;
+        }
+        if variants == 0 {
+            return self.error("a select expression needs variants");
+        }
+        if defaults != 1 {
+            return self.error("a select expression needs exactly one default variant");
+        }
+        Ok(())
+    }
+
+    /// A variant value, which ends at the next variant key or at the end of the select.
+    fn parse_variant_pattern(&mut self) -> Result<(), SyntaxError> {
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => self.parse_placeable()?,
+                '}' => return Ok(()),
+                '\n' => {
+                    let mut next = self.pos + 1;
+                    while self
+                        .chars
+                        .get(next)
+                        .is_some_and(|c| *c == ' ' || *c == '\t')
+                    {
+                        next += 1;
+                    }
+                    if matches!(self.chars.get(next), Some('[' | '*' | '}')) {
+                        self.pos = next;
+                        return Ok(());
+                    }
+                    self.pos += 1;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        Ok(())
+    }
+}
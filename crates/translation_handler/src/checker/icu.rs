@@ -0,0 +1,322 @@
+use crate::checker::{Placeholder, PlaceholderKind, SyntaxError};
+
+const ARGUMENT_TYPES: [&str; 9] = [
+    "number",
+    "date",
+    "time",
+    "spellout",
+    "ordinal",
+    "duration",
+    "plural",
+    "select",
+    "selectordinal",
+];
+
+/// Parses an ICU MessageFormat string. `{{name}}` interpolations and printf specifiers such as
+/// `%s` or `%1$d` are picked up as placeholders of their own kind alongside ICU arguments.
+pub(super) fn parse(source: &str) -> Result<Vec<Placeholder>, SyntaxError> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        placeholders: Vec::new(),
+    };
+    parser.parse_message(0, false)?;
+    Ok(parser.placeholders)
+}
+
+/// Picks up printf specifiers only, for platforms where braces are plain text.
+pub(super) fn parse_printf(source: &str) -> Vec<Placeholder> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        placeholders: Vec::new(),
+    };
+    while parser.pos < parser.chars.len() {
+        if parser.peek() == Some('%') {
+            parser.parse_printf();
+        } else {
+            parser.pos += 1;
+        }
+    }
+    parser.placeholders
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    placeholders: Vec<Placeholder>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, SyntaxError> {
+        Err(SyntaxError {
+            message: message.to_string(),
+            offset: self.pos,
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Parses message text until the `}` closing a nested message or the end of the input.
+    fn parse_message(&mut self, depth: usize, in_plural: bool) -> Result<(), SyntaxError> {
+        loop {
+            match self.peek() {
+                None if depth > 0 => return self.error("unclosed `{`"),
+                None => return Ok(()),
+                Some('}') if depth > 0 => return Ok(()),
+                Some('}') => return self.error("unmatched `}`"),
+                Some('{') if self.peek_at(1) == Some('{') => self.parse_mustache()?,
+                Some('{') => self.parse_argument(depth)?,
+                Some('\'') => self.skip_quoted(in_plural),
+                Some('%') => self.parse_printf(),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    /// ICU apostrophes only quote when they precede a syntax character, so `don't` stays text.
+    fn skip_quoted(&mut self, in_plural: bool) {
+        match self.peek_at(1) {
+            Some('\'') => self.pos += 2,
+            Some('{') | Some('}') | Some('|') => self.skip_until_closing_quote(),
+            Some('#') if in_plural => self.skip_until_closing_quote(),
+            _ => self.pos += 1,
+        }
+    }
+
+    fn skip_until_closing_quote(&mut self) {
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == '\'' {
+                if self.peek() == Some('\'') {
+                    self.pos += 1;
+                } else {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn parse_mustache(&mut self) -> Result<(), SyntaxError> {
+        let start = self.pos;
+        self.pos += 2;
+        while let Some(c) = self.peek() {
+            if c == '}' && self.peek_at(1) == Some('}') {
+                let inner: String = self.chars[start + 2..self.pos].iter().collect();
+                let name = inner
+                    .split(',')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .trim_start_matches(['-', '#', '/', '&'])
+                    .trim();
+                if name.is_empty() {
+                    return self.error("empty `{{}}` interpolation");
+                }
+                self.placeholders.push(Placeholder {
+                    kind: PlaceholderKind::Mustache,
+                    name: name.to_string(),
+                    argument_type: None,
+                });
+                self.pos += 2;
+                return Ok(());
+            }
+            self.pos += 1;
+        }
+        self.pos = start;
+        self.error("unclosed `{{`")
+    }
+
+    fn parse_printf(&mut self) {
+        let start = self.pos;
+        let mut cursor = self.pos + 1;
+        let at = |index: usize| self.chars.get(index).copied();
+        if at(cursor) == Some('%') {
+            self.pos += 2;
+            return;
+        }
+        // Positional argument such as `%1$s`
+        let digits_start = cursor;
+        while at(cursor).is_some_and(|c| c.is_ascii_digit()) {
+            cursor += 1;
+        }
+        if cursor > digits_start && at(cursor) == Some('$') {
+            cursor += 1;
+        } else {
+            cursor = digits_start;
+        }
+        while at(cursor).is_some_and(|c| matches!(c, '-' | '+' | '0' | '#')) {
+            cursor += 1;
+        }
+        while at(cursor).is_some_and(|c| c.is_ascii_digit()) {
+            cursor += 1;
+        }
+        if at(cursor) == Some('.') {
+            cursor += 1;
+            while at(cursor).is_some_and(|c| c.is_ascii_digit()) {
+                cursor += 1;
+            }
+        }
+        // Length modifiers such as the `ll` in `%lld`
+        while at(cursor).is_some_and(|c| "hlqLzjt".contains(c)) {
+            cursor += 1;
+        }
+        if at(cursor).is_some_and(|c| "sdifuxXeEgGcp@".contains(c)) {
+            cursor += 1;
+            self.placeholders.push(Placeholder {
+                kind: PlaceholderKind::Printf,
+                name: self.chars[start..cursor].iter().collect(),
+                argument_type: None,
+            });
+            self.pos = cursor;
+        } else {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_argument(&mut self, depth: usize) -> Result<(), SyntaxError> {
+        self.pos += 1;
+        self.skip_whitespace();
+        let name = self.read_identifier();
+        if name.is_empty() {
+            return self.error("expected an argument name");
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some('}') => {
+                self.pos += 1;
+                self.placeholders.push(Placeholder {
+                    kind: PlaceholderKind::Icu,
+                    name,
+                    argument_type: None,
+                });
+                return Ok(());
+            }
+            Some(',') => self.pos += 1,
+            None => return self.error("unclosed `{`"),
+            Some(_) => return self.error("expected `,` or `}` after the argument name"),
+        }
+
+        self.skip_whitespace();
+        let type_start = self.pos;
+        let argument_type = self.read_identifier();
+        if !ARGUMENT_TYPES.contains(&argument_type.as_str()) {
+            self.pos = type_start;
+            return self.error("unknown argument type");
+        }
+        self.placeholders.push(Placeholder {
+            kind: PlaceholderKind::Icu,
+            name,
+            argument_type: Some(argument_type.clone()),
+        });
+        self.skip_whitespace();
+        match self.peek() {
+            Some('}') => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(',') => {
+                self.pos += 1;
+                match argument_type.as_str() {
+                    "plural" | "selectordinal" => self.parse_options(depth, true),
+                    "select" => self.parse_options(depth, false),
+                    _ => self.skip_style(),
+                }
+            }
+            None => self.error("unclosed `{`"),
+            Some(_) => self.error("expected `,` or `}` after the argument type"),
+        }
+    }
+
+    fn skip_style(&mut self) -> Result<(), SyntaxError> {
+        let mut nesting = 0;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '{' => nesting += 1,
+                '}' if nesting == 0 => return Ok(()),
+                '}' => nesting -= 1,
+                _ => {}
+            }
+        }
+        self.error("unclosed `{`")
+    }
+
+    fn parse_options(&mut self, depth: usize, plural: bool) -> Result<(), SyntaxError> {
+        let mut selectors: Vec<String> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => break,
+                None => return self.error("unclosed `{`"),
+                _ => {}
+            }
+
+            if plural && self.chars[self.pos..].starts_with(&['o', 'f', 'f', 's', 'e', 't', ':']) {
+                self.pos += 7;
+                self.skip_whitespace();
+                let offset = self.read_identifier();
+                if offset.is_empty() || !offset.chars().all(|c| c.is_ascii_digit()) {
+                    return self.error("expected a number after `offset:`");
+                }
+                continue;
+            }
+
+            let selector = if plural && self.peek() == Some('=') {
+                self.pos += 1;
+                let number = self.read_identifier();
+                if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                    return self.error("expected a number after `=`");
+                }
+                format!("={}", number)
+            } else {
+                self.read_identifier()
+            };
+            if selector.is_empty() {
+                return self.error("expected a selector");
+            }
+            if selectors.contains(&selector) {
+                return self.error("duplicate selector");
+            }
+            selectors.push(selector);
+
+            self.skip_whitespace();
+            if self.peek() != Some('{') {
+                return self.error("expected `{` after the selector");
+            }
+            self.pos += 1;
+            self.parse_message(depth + 1, plural)?;
+            // parse_message stops on the closing `}` of the option
+            self.pos += 1;
+        }
+        if !selectors.iter().any(|selector| selector == "other") {
+            return self.error("missing the `other` option");
+        }
+        self.pos += 1;
+        Ok(())
+    }
+}
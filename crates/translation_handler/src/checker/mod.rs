@@ -0,0 +1,401 @@
+mod dotnet;
+mod fluent;
+mod icu;
+
+use local_storage::stores::translation_store::TranslationEntry;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Which interpolation syntax the values of a location use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageSyntax {
+    /// ICU MessageFormat, plus `{{name}}` and printf interpolation used by JS i18n libraries.
+    Icu,
+    /// .NET composite formatting as used with resx resources.
+    DotNet,
+    /// printf specifiers such as `%1$s` or `%@`, as used by Android and iOS strings.
+    Printf,
+    /// Project Fluent placeables such as `{ $name }` and select expressions.
+    Fluent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlaceholderKind {
+    Icu,
+    Mustache,
+    Printf,
+    DotNet,
+    Fluent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    pub kind: PlaceholderKind,
+    pub name: String,
+    /// ICU argument type such as `number` or `plural`
+    pub argument_type: Option<String>,
+}
+
+impl fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind, &self.argument_type) {
+            (PlaceholderKind::Icu, Some(argument_type)) => {
+                write!(f, "{{{}, {}}}", self.name, argument_type)
+            }
+            (PlaceholderKind::Icu, None) | (PlaceholderKind::DotNet, _) => {
+                write!(f, "{{{}}}", self.name)
+            }
+            (PlaceholderKind::Mustache, _) => write!(f, "{{{{{}}}}}", self.name),
+            (PlaceholderKind::Printf, _) => f.write_str(&self.name),
+            (PlaceholderKind::Fluent, _) => write!(f, "{{ ${} }}", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub message: String,
+    /// Character offset in the value
+    pub offset: usize,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at character {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Problem {
+    Missing {
+        placeholder: String,
+    },
+    Extra {
+        placeholder: String,
+    },
+    Renamed {
+        from: String,
+        to: String,
+    },
+    TypeChanged {
+        placeholder: String,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    SyntaxError {
+        message: String,
+        offset: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+pub struct PlaceholderIssue {
+    pub key: String,
+    pub language: String,
+    pub problem: Problem,
+}
+
+pub fn parse_placeholders(
+    value: &str,
+    syntax: MessageSyntax,
+) -> Result<Vec<Placeholder>, SyntaxError> {
+    match syntax {
+        MessageSyntax::Icu => icu::parse(value),
+        MessageSyntax::DotNet => dotnet::parse(value),
+        MessageSyntax::Printf => Ok(icu::parse_printf(value)),
+        MessageSyntax::Fluent => fluent::parse(value),
+    }
+}
+
+/// Checks every translation of every entry against the value in `source_language`.
+pub fn check_entries(
+    entries: &[TranslationEntry],
+    source_language: &str,
+    syntax: MessageSyntax,
+) -> Vec<PlaceholderIssue> {
+    entries
+        .iter()
+        .flat_map(|entry| check_entry(entry, source_language, syntax))
+        .collect()
+}
+
+pub fn check_entry(
+    entry: &TranslationEntry,
+    source_language: &str,
+    syntax: MessageSyntax,
+) -> Vec<PlaceholderIssue> {
+    let mut issues = Vec::new();
+    let issue = |language: &str, problem: Problem| PlaceholderIssue {
+        key: entry.key.clone(),
+        language: language.to_string(),
+        problem,
+    };
+
+    let source = match entry.translations.get(source_language) {
+        Some(value) if !value.trim().is_empty() => match parse_placeholders(value, syntax) {
+            Ok(placeholders) => Some(placeholders),
+            Err(error) => {
+                issues.push(issue(source_language, syntax_problem(error)));
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let mut languages: Vec<&String> = entry.translations.keys().collect();
+    languages.sort();
+    for language in languages {
+        let value = &entry.translations[language];
+        if language == source_language || value.trim().is_empty() {
+            continue;
+        }
+        match parse_placeholders(value, syntax) {
+            Err(error) => issues.push(issue(language, syntax_problem(error))),
+            Ok(placeholders) => {
+                if let Some(source) = &source {
+                    issues.extend(
+                        compare(source, &placeholders)
+                            .into_iter()
+                            .map(|problem| issue(language, problem)),
+                    );
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn syntax_problem(error: SyntaxError) -> Problem {
+    Problem::SyntaxError {
+        message: error.message,
+        offset: error.offset as u32,
+    }
+}
+
+type PlaceholderCounts<'a> = BTreeMap<(PlaceholderKind, &'a str), (usize, &'a Placeholder)>;
+
+fn count(placeholders: &[Placeholder]) -> PlaceholderCounts<'_> {
+    let mut counts: PlaceholderCounts = BTreeMap::new();
+    for placeholder in placeholders {
+        let entry = counts
+            .entry((placeholder.kind, placeholder.name.as_str()))
+            .or_insert((0, placeholder));
+        // Named arguments may be repeated freely, positional printf specifiers may not
+        if placeholder.kind == PlaceholderKind::Printf || entry.0 == 0 {
+            entry.0 += 1;
+        }
+        if entry.1.argument_type.is_none() {
+            entry.1 = placeholder;
+        }
+    }
+    counts
+}
+
+fn compare(source: &[Placeholder], translation: &[Placeholder]) -> Vec<Problem> {
+    let source = count(source);
+    let translation = count(translation);
+    let mut problems = Vec::new();
+    let mut missing: Vec<&Placeholder> = Vec::new();
+    let mut extra: Vec<&Placeholder> = Vec::new();
+
+    for (key, (expected, placeholder)) in &source {
+        let (found, translated) = translation.get(key).copied().unwrap_or((0, placeholder));
+        missing.extend(std::iter::repeat(*placeholder).take(expected.saturating_sub(found)));
+        if found > 0 && placeholder.argument_type != translated.argument_type {
+            problems.push(Problem::TypeChanged {
+                placeholder: placeholder.to_string(),
+                expected: placeholder.argument_type.clone(),
+                found: translated.argument_type.clone(),
+            });
+        }
+    }
+    for (key, (found, placeholder)) in &translation {
+        let expected = source.get(key).map(|(count, _)| *count).unwrap_or(0);
+        extra.extend(std::iter::repeat(*placeholder).take(found.saturating_sub(expected)));
+    }
+
+    // A missing and an extra placeholder of the same kind most likely is a rename
+    let mut renamed = Vec::new();
+    missing.retain(|from| {
+        match extra
+            .iter()
+            .position(|to| to.kind == from.kind && to.kind != PlaceholderKind::Printf)
+        {
+            Some(index) => {
+                let to = extra.remove(index);
+                renamed.push(Problem::Renamed {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    problems.extend(missing.into_iter().map(|placeholder| Problem::Missing {
+        placeholder: placeholder.to_string(),
+    }));
+    problems.extend(extra.into_iter().map(|placeholder| Problem::Extra {
+        placeholder: placeholder.to_string(),
+    }));
+    problems.extend(renamed);
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(translations: &[(&str, &str)]) -> TranslationEntry {
+        TranslationEntry {
+            key: "key".to_string(),
+            value: "key".to_string(),
+            translations: translations
+                .iter()
+                .map(|(language, value)| (language.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+            in_use: true,
+            ..Default::default()
+        }
+    }
+
+    fn problems(translations: &[(&str, &str)], syntax: MessageSyntax) -> Vec<Problem> {
+        check_entry(&entry(translations), "en", syntax)
+            .into_iter()
+            .map(|issue| issue.problem)
+            .collect()
+    }
+
+    #[test]
+    fn parses_icu_mustache_and_printf_placeholders() {
+        let placeholders = parse_placeholders(
+            "Hi {name}, {{user}} has %1$s and {count, plural, one {# item} other {# items for {owner}}} don't '{literal}'",
+            MessageSyntax::Icu,
+        )
+        .unwrap();
+        let rendered: Vec<String> = placeholders.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            vec!["{name}", "{{user}}", "%1$s", "{count, plural}", "{owner}"],
+            rendered
+        );
+    }
+
+    #[test]
+    fn reports_icu_syntax_errors() {
+        for value in [
+            "{count, plural, one {# item}}",
+            "{count, plural, one {# item} other {# items}",
+            "{name",
+            "text }",
+            "{count, plurl, other {x}}",
+        ] {
+            assert!(
+                parse_placeholders(value, MessageSyntax::Icu).is_err(),
+                "{} should fail",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn reports_missing_extra_and_renamed_placeholders() {
+        assert_eq!(
+            vec![Problem::Renamed {
+                from: "{count}".to_string(),
+                to: "{anzahl}".to_string()
+            }],
+            problems(
+                &[("en", "{count} files"), ("de", "{anzahl} Dateien")],
+                MessageSyntax::Icu
+            )
+        );
+        assert_eq!(
+            vec![
+                Problem::Missing {
+                    placeholder: "%s".to_string()
+                },
+                Problem::Extra {
+                    placeholder: "{{name}}".to_string()
+                }
+            ],
+            problems(
+                &[("en", "%s and %s"), ("de", "%s und {{name}}")],
+                MessageSyntax::Icu
+            )
+        );
+        assert_eq!(
+            vec![Problem::TypeChanged {
+                placeholder: "{n, plural}".to_string(),
+                expected: Some("plural".to_string()),
+                found: None
+            }],
+            problems(
+                &[
+                    ("en", "{n, plural, other {# files}}"),
+                    ("de", "{n} Dateien")
+                ],
+                MessageSyntax::Icu
+            )
+        );
+        assert!(problems(&[("en", "{count} files"), ("de", "")], MessageSyntax::Icu).is_empty());
+    }
+
+    #[test]
+    fn checks_dotnet_format_items() {
+        assert!(parse_placeholders("{0,-10:N2} {{literal}}", MessageSyntax::DotNet).is_ok());
+        assert!(parse_placeholders("{name}", MessageSyntax::DotNet).is_err());
+        assert_eq!(
+            vec![Problem::Missing {
+                placeholder: "{1}".to_string()
+            }],
+            problems(
+                &[("en", "{0} of {1}"), ("de", "{0} von")],
+                MessageSyntax::DotNet
+            )
+        );
+    }
+
+    #[test]
+    fn printf_syntax_leaves_braces_alone() {
+        let placeholders =
+            parse_placeholders("{not ICU} %lld of %1$@ at 100%%", MessageSyntax::Printf).unwrap();
+        let rendered: Vec<String> = placeholders.iter().map(ToString::to_string).collect();
+        assert_eq!(vec!["%lld", "%1$@"], rendered);
+    }
+
+    #[test]
+    fn fluent_variables_are_found_in_select_expressions() {
+        let value = "{ $count ->\n    [one] One file in { -brand-name }\n   *[other] { NUMBER($count) } files by { $user.name }\n}";
+        let placeholders = parse_placeholders(value, MessageSyntax::Fluent);
+        assert!(
+            placeholders.is_err(),
+            "attributes of variables are not Fluent"
+        );
+
+        let value = "{ $count ->\n    [one] One file in { -brand-name }\n   *[other] { NUMBER($count, minimumFractionDigits: 1) } files by { $user }\n}";
+        let rendered: Vec<String> = parse_placeholders(value, MessageSyntax::Fluent)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(vec!["{ $count }", "{ $count }", "{ $user }"], rendered);
+
+        assert!(parse_placeholders("{ $n ->\n    [one] x\n}", MessageSyntax::Fluent).is_err());
+        assert_eq!(
+            vec![Problem::Renamed {
+                from: "{ $user }".to_string(),
+                to: "{ $name }".to_string()
+            }],
+            problems(
+                &[("en", "Hello { $user }"), ("de", "Hallo { $name }")],
+                MessageSyntax::Fluent
+            )
+        );
+    }
+}
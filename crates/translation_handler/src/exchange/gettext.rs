@@ -0,0 +1,562 @@
+use crate::exchange::ImportedValue;
+use local_storage::stores::translation_store::TranslationEntry;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One message of a PO or POT file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoMessage {
+    /// `# ` comments, written by translators
+    pub translator_comments: Vec<String>,
+    /// `#.` comments, written for translators
+    pub extracted_comments: Vec<String>,
+    /// `#:` source references
+    pub references: Vec<String>,
+    /// `#,` flags such as `fuzzy`
+    pub flags: Vec<String>,
+    /// `#| msgid` of the source text a fuzzy translation was made from
+    pub previous_msgid: Option<String>,
+    pub msgctxt: Option<String>,
+    pub msgid: String,
+    pub msgid_plural: Option<String>,
+    /// `msgstr`, or `msgstr[n]` for plural messages
+    pub msgstr: Vec<String>,
+    /// `#~` messages kept by msgmerge after their source went away
+    pub obsolete: bool,
+}
+
+impl PoMessage {
+    pub fn is_fuzzy(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "fuzzy")
+    }
+
+    fn is_header(&self) -> bool {
+        self.msgid.is_empty() && self.msgctxt.is_none()
+    }
+
+    fn translation(&self) -> &str {
+        self.msgstr.first().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// A PO file, or a POT template when the translations are empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    /// The header (`msgid ""`) comes first when the file has one
+    pub messages: Vec<PoMessage>,
+}
+
+impl Catalog {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        Parser::default().parse(content)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Value of a header field such as `Language`.
+    pub fn header_field(&self, name: &str) -> Option<String> {
+        let header = self.messages.iter().find(|message| message.is_header())?;
+        header.translation().lines().find_map(|line| {
+            let (field, value) = line.split_once(':')?;
+            (field.trim().eq_ignore_ascii_case(name)).then(|| value.trim().to_string())
+        })
+    }
+
+    pub fn language(&self) -> Option<String> {
+        self.header_field("Language")
+            .filter(|language| !language.is_empty())
+    }
+
+    /// The message of a key, which is stored as its msgctxt.
+    pub fn find(&self, key: &str) -> Option<&PoMessage> {
+        self.messages
+            .iter()
+            .find(|message| !message.obsolete && message.msgctxt.as_deref() == Some(key))
+    }
+}
+
+fn header(language: &str) -> PoMessage {
+    PoMessage {
+        msgstr: vec![format!(
+            "Language: {}\nMIME-Version: 1.0\nContent-Type: text/plain; charset=UTF-8\nContent-Transfer-Encoding: 8bit\n",
+            language
+        )],
+        ..Default::default()
+    }
+}
+
+/// Entries with plural forms are left out, as gettext numbers its forms per language instead of
+/// naming CLDR categories.
+fn exported_entries(entries: &[TranslationEntry]) -> impl Iterator<Item = &TranslationEntry> {
+    entries.iter().filter(|entry| entry.plurals.is_empty())
+}
+
+/// A POT template with the source text of every key. Keys go into `msgctxt`, so messages with the
+/// same source text stay apart.
+pub fn export_template(entries: &[TranslationEntry], source_language: &str) -> Catalog {
+    let mut messages = vec![header("")];
+    messages.extend(exported_entries(entries).map(|entry| PoMessage {
+        msgctxt: Some(entry.key.clone()),
+        msgid: source_text(entry, source_language),
+        msgstr: vec![String::new()],
+        ..Default::default()
+    }));
+    Catalog { messages }
+}
+
+/// The PO file of `language`. Translator comments and flags of a `previous` export are carried
+/// over, and translations whose source text changed since then are marked `fuzzy`.
+pub fn export_catalog(
+    entries: &[TranslationEntry],
+    source_language: &str,
+    language: &str,
+    previous: Option<&Catalog>,
+) -> Catalog {
+    let mut messages = vec![header(language)];
+    messages.extend(exported_entries(entries).map(|entry| {
+        let msgid = source_text(entry, source_language);
+        let translation = entry
+            .translations
+            .get(language)
+            .cloned()
+            .unwrap_or_default();
+        let mut message = PoMessage {
+            msgctxt: Some(entry.key.clone()),
+            msgid: msgid.clone(),
+            msgstr: vec![translation.clone()],
+            ..Default::default()
+        };
+        if let Some(previous) = previous.and_then(|catalog| catalog.find(&entry.key)) {
+            message.translator_comments = previous.translator_comments.clone();
+            // A fuzzy translation stays fuzzy until someone changes it
+            if previous.is_fuzzy() && previous.translation() == translation {
+                message.flags.push("fuzzy".to_string());
+                message.previous_msgid = previous.previous_msgid.clone();
+            }
+            if previous.msgid != msgid && !translation.is_empty() && !message.is_fuzzy() {
+                message.flags.push("fuzzy".to_string());
+                message.previous_msgid = Some(previous.msgid.clone());
+            }
+            message.flags.extend(
+                previous
+                    .flags
+                    .iter()
+                    .filter(|flag| *flag != "fuzzy")
+                    .cloned(),
+            );
+        }
+        message
+    }));
+    Catalog { messages }
+}
+
+fn source_text(entry: &TranslationEntry, source_language: &str) -> String {
+    entry
+        .translations
+        .get(source_language)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Translations of a PO file. Untranslated messages are skipped, fuzzy ones are kept so the
+/// import can report them.
+pub fn imported_values(catalog: &Catalog, language: &str) -> Vec<ImportedValue> {
+    catalog
+        .messages
+        .iter()
+        .filter(|message| !message.obsolete && !message.is_header())
+        .filter(|message| !message.translation().is_empty())
+        .map(|message| ImportedValue {
+            key: message
+                .msgctxt
+                .clone()
+                .unwrap_or_else(|| message.msgid.clone()),
+            language: language.to_string(),
+            source: Some(message.msgid.clone()),
+            value: message.translation().to_string(),
+            needs_review: message.is_fuzzy(),
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Msgctxt,
+    Msgid,
+    MsgidPlural,
+    Msgstr(usize),
+    PreviousMsgid,
+}
+
+#[derive(Default)]
+struct Parser {
+    messages: Vec<PoMessage>,
+    current: PoMessage,
+    field: Option<Field>,
+    has_content: bool,
+}
+
+impl Parser {
+    fn parse(mut self, content: &str) -> Result<Catalog, io::Error> {
+        for (index, line) in content.lines().enumerate() {
+            self.parse_line(line.trim()).map_err(|message| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", index + 1, message),
+                )
+            })?;
+        }
+        self.finish_message();
+        Ok(Catalog {
+            messages: self.messages,
+        })
+    }
+
+    fn finish_message(&mut self) {
+        if self.has_content {
+            self.messages.push(std::mem::take(&mut self.current));
+        }
+        self.current = PoMessage::default();
+        self.field = None;
+        self.has_content = false;
+    }
+
+    /// Comments and keywords after a msgstr belong to the next message, even without a blank
+    /// line in between.
+    fn start_part(&mut self) {
+        if matches!(self.field, Some(Field::Msgstr(_))) {
+            self.finish_message();
+        }
+        self.has_content = true;
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        if line.is_empty() {
+            self.finish_message();
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix("#~") {
+            self.start_part();
+            self.current.obsolete = true;
+            return self.parse_keyword(rest.trim());
+        }
+        if let Some(rest) = line.strip_prefix("#|") {
+            self.start_part();
+            let rest = rest.trim();
+            if let Some(value) = rest.strip_prefix("msgid ") {
+                self.current.previous_msgid = Some(unquote(value)?);
+                self.field = Some(Field::PreviousMsgid);
+            } else if rest.starts_with('"') && self.field == Some(Field::PreviousMsgid) {
+                let value = unquote(rest)?;
+                self.current
+                    .previous_msgid
+                    .get_or_insert_with(String::new)
+                    .push_str(&value);
+            }
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix('#') {
+            self.start_part();
+            self.field = None;
+            if let Some(flags) = rest.strip_prefix(',') {
+                self.current.flags.extend(
+                    flags
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|flag| !flag.is_empty())
+                        .map(str::to_string),
+                );
+            } else if let Some(comment) = rest.strip_prefix('.') {
+                self.current
+                    .extracted_comments
+                    .push(comment.trim().to_string());
+            } else if let Some(reference) = rest.strip_prefix(':') {
+                self.current
+                    .references
+                    .extend(reference.split_whitespace().map(str::to_string));
+            } else {
+                let comment = rest.strip_prefix(' ').unwrap_or(rest);
+                self.current.translator_comments.push(comment.to_string());
+            }
+            return Ok(());
+        }
+        self.parse_keyword(line)
+    }
+
+    fn parse_keyword(&mut self, line: &str) -> Result<(), String> {
+        if line.starts_with('"') {
+            let value = unquote(line)?;
+            return match self.field {
+                Some(field) => {
+                    self.field_value(field).push_str(&value);
+                    Ok(())
+                }
+                None => Err("string without a keyword".to_string()),
+            };
+        }
+        let (keyword, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("unexpected `{}`", line))?;
+        let value = unquote(value.trim())?;
+        let field = match keyword {
+            "msgctxt" => Field::Msgctxt,
+            "msgid" => Field::Msgid,
+            "msgid_plural" => Field::MsgidPlural,
+            "msgstr" => Field::Msgstr(0),
+            _ => match keyword
+                .strip_prefix("msgstr[")
+                .and_then(|index| index.strip_suffix(']'))
+            {
+                Some(index) => Field::Msgstr(
+                    index
+                        .parse()
+                        .map_err(|_| format!("invalid plural index in `{}`", keyword))?,
+                ),
+                None => return Err(format!("unknown keyword `{}`", keyword)),
+            },
+        };
+        if matches!(field, Field::Msgctxt | Field::Msgid) {
+            self.start_part();
+        }
+        self.has_content = true;
+        self.field = Some(field);
+        *self.field_value(field) = value;
+        Ok(())
+    }
+
+    fn field_value(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::Msgctxt => self.current.msgctxt.get_or_insert_with(String::new),
+            Field::Msgid => &mut self.current.msgid,
+            Field::MsgidPlural => self.current.msgid_plural.get_or_insert_with(String::new),
+            Field::PreviousMsgid => self.current.previous_msgid.get_or_insert_with(String::new),
+            Field::Msgstr(index) => {
+                if self.current.msgstr.len() <= index {
+                    self.current.msgstr.resize(index + 1, String::new());
+                }
+                &mut self.current.msgstr[index]
+            }
+        }
+    }
+}
+
+fn unquote(value: &str) -> Result<String, String> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .filter(|_| value.len() >= 2)
+        .ok_or_else(|| format!("expected a quoted string, found `{}`", value))?;
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('a') => result.push('\u{7}'),
+            Some('b') => result.push('\u{8}'),
+            Some('f') => result.push('\u{c}'),
+            Some('v') => result.push('\u{b}'),
+            Some(c @ ('"' | '\\' | '\'' | '?')) => result.push(c),
+            Some(c) => return Err(format!("unknown escape `\\{}`", c)),
+            None => return Err("string ends with a backslash".to_string()),
+        }
+    }
+    Ok(result)
+}
+
+fn quote(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Writes `keyword "value"`, splitting multi-line values after each `\n` the way xgettext does.
+fn write_field(
+    f: &mut fmt::Formatter<'_>,
+    prefix: &str,
+    keyword: &str,
+    value: &str,
+) -> fmt::Result {
+    let lines: Vec<&str> = value.split_inclusive('\n').collect();
+    if lines.len() <= 1 {
+        return writeln!(f, "{}{} {}", prefix, keyword, quote(value));
+    }
+    writeln!(f, "{}{} \"\"", prefix, keyword)?;
+    for line in lines {
+        writeln!(f, "{}{}", prefix, quote(line))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for PoMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for comment in &self.translator_comments {
+            if comment.is_empty() {
+                writeln!(f, "#")?;
+            } else {
+                writeln!(f, "# {}", comment)?;
+            }
+        }
+        for comment in &self.extracted_comments {
+            writeln!(f, "#. {}", comment)?;
+        }
+        if !self.references.is_empty() {
+            writeln!(f, "#: {}", self.references.join(" "))?;
+        }
+        if !self.flags.is_empty() {
+            writeln!(f, "#, {}", self.flags.join(", "))?;
+        }
+        if let Some(previous_msgid) = &self.previous_msgid {
+            write_field(f, "#| ", "msgid", previous_msgid)?;
+        }
+        let prefix = if self.obsolete { "#~ " } else { "" };
+        if let Some(msgctxt) = &self.msgctxt {
+            write_field(f, prefix, "msgctxt", msgctxt)?;
+        }
+        write_field(f, prefix, "msgid", &self.msgid)?;
+        match &self.msgid_plural {
+            Some(msgid_plural) => {
+                write_field(f, prefix, "msgid_plural", msgid_plural)?;
+                for (index, msgstr) in self.msgstr.iter().enumerate() {
+                    write_field(f, prefix, &format!("msgstr[{}]", index), msgstr)?;
+                }
+            }
+            None => write_field(f, prefix, "msgstr", self.translation())?,
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Catalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, message) in self.messages.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(key: &str, translations: &[(&str, &str)]) -> TranslationEntry {
+        TranslationEntry {
+            key: key.to_string(),
+            value: key.to_string(),
+            translations: translations
+                .iter()
+                .map(|(language, value)| (language.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+            in_use: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_comments_flags_context_and_continued_strings() {
+        let catalog = Catalog::parse(
+            r#"msgid ""
+msgstr ""
+"Language: de\n"
+
+# Checked with marketing
+#. shown on the toolbar
+#: src/toolbar.tsx:12
+#, fuzzy, javascript-format
+#| msgid "Save"
+msgctxt "saveButton"
+msgid ""
+"Save\n"
+"all"
+msgstr "Alles \"speichern\""
+#~ msgid "Gone"
+#~ msgstr "Weg"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(Some("de".to_string()), catalog.language());
+        let message = catalog.find("saveButton").unwrap();
+        assert_eq!(vec!["Checked with marketing"], message.translator_comments);
+        assert_eq!(vec!["shown on the toolbar"], message.extracted_comments);
+        assert_eq!(vec!["src/toolbar.tsx:12"], message.references);
+        assert!(message.is_fuzzy());
+        assert_eq!(Some("Save".to_string()), message.previous_msgid);
+        assert_eq!("Save\nall", message.msgid);
+        assert_eq!("Alles \"speichern\"", message.translation());
+        assert!(catalog.messages[2].obsolete);
+
+        assert_eq!(catalog, Catalog::parse(&catalog.to_string()).unwrap());
+    }
+
+    #[test]
+    fn rejects_unknown_keywords_with_the_line() {
+        let error = Catalog::parse("msgid \"a\"\nmsgstring \"b\"\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn export_marks_translations_of_changed_source_text_fuzzy() {
+        let entries = vec![
+            entry("save", &[("en", "Save all"), ("de", "Speichern")]),
+            entry("open", &[("en", "Open"), ("de", "Öffnen")]),
+        ];
+        let previous = export_catalog(
+            &[
+                entry("save", &[("en", "Save"), ("de", "Speichern")]),
+                entry("open", &[("en", "Open"), ("de", "Öffnen")]),
+            ],
+            "en",
+            "de",
+            None,
+        );
+        let mut previous = previous;
+        previous.messages[2]
+            .translator_comments
+            .push("Keep it short".to_string());
+
+        let catalog = export_catalog(&entries, "en", "de", Some(&previous));
+
+        let save = catalog.find("save").unwrap();
+        assert!(save.is_fuzzy());
+        assert_eq!(Some("Save".to_string()), save.previous_msgid);
+        let open = catalog.find("open").unwrap();
+        assert!(!open.is_fuzzy());
+        assert_eq!(vec!["Keep it short"], open.translator_comments);
+
+        let values = imported_values(&catalog, "de");
+        assert!(values[0].needs_review);
+        assert_eq!(Some("Open".to_string()), values[1].source);
+    }
+}
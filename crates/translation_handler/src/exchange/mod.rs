@@ -0,0 +1,258 @@
+pub mod gettext;
+pub mod spreadsheet;
+pub mod xliff;
+
+use crate::format::{KeyValue, TranslationFormat};
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+/// A translation read from an exchange file, before it is checked against the location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedValue {
+    pub key: String,
+    pub language: String,
+    /// Source text the translation was made from, when the file carries it
+    pub source: Option<String>,
+    pub value: String,
+    /// Marked for review in the file, e.g. a gettext `fuzzy` flag
+    pub needs_review: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+pub struct ValueChange {
+    pub key: String,
+    pub language: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConflictReason {
+    /// The source text changed since the file was exported
+    SourceChanged {
+        exported: String,
+        current: String,
+    },
+    NeedsReview,
+    /// The file has different values for the same key and language
+    Duplicate,
+    /// The key has plural forms, which are edited one category at a time
+    PluralForms,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+pub struct ImportConflict {
+    pub key: String,
+    pub language: String,
+    pub reason: ConflictReason,
+}
+
+/// Outcome of checking imported values against the current entries of a location.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, specta::Type)]
+pub struct ImportReport {
+    pub changed: Vec<ValueChange>,
+    /// Keys the location does not have
+    pub unknown: Vec<String>,
+    pub conflicted: Vec<ImportConflict>,
+    pub unchanged: u32,
+}
+
+/// Checks imported values against `entries` without writing anything. Only the values in
+/// `changed` of the report are meant to be applied.
+pub fn plan_import(
+    entries: &[TranslationEntry],
+    source_language: &str,
+    values: Vec<ImportedValue>,
+) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut seen: HashMap<(String, String), String> = HashMap::new();
+
+    for imported in values {
+        let Some(entry) = entries.iter().find(|entry| entry.key == imported.key) else {
+            if !report.unknown.contains(&imported.key) {
+                report.unknown.push(imported.key);
+            }
+            continue;
+        };
+        let conflict = |reason: ConflictReason| ImportConflict {
+            key: imported.key.clone(),
+            language: imported.language.clone(),
+            reason,
+        };
+
+        let seen_key = (imported.key.clone(), imported.language.clone());
+        if let Some(previous) = seen.get(&seen_key) {
+            if *previous != imported.value {
+                report.changed.retain(|change| {
+                    change.key != imported.key || change.language != imported.language
+                });
+                report.conflicted.push(conflict(ConflictReason::Duplicate));
+            }
+            continue;
+        }
+        seen.insert(seen_key, imported.value.clone());
+
+        if imported.needs_review {
+            report
+                .conflicted
+                .push(conflict(ConflictReason::NeedsReview));
+            continue;
+        }
+        let current_source = entry
+            .translations
+            .get(source_language)
+            .cloned()
+            .unwrap_or_default();
+        match &imported.source {
+            Some(exported)
+                if imported.language != source_language && *exported != current_source =>
+            {
+                report
+                    .conflicted
+                    .push(conflict(ConflictReason::SourceChanged {
+                        exported: exported.clone(),
+                        current: current_source,
+                    }));
+                continue;
+            }
+            _ => {}
+        }
+
+        let old_value = entry
+            .translations
+            .get(&imported.language)
+            .cloned()
+            .unwrap_or_default();
+        if old_value == imported.value {
+            report.unchanged += 1;
+        } else if entry.plurals.contains_key(&imported.language) {
+            report
+                .conflicted
+                .push(conflict(ConflictReason::PluralForms));
+        } else {
+            report.changed.push(ValueChange {
+                key: imported.key,
+                language: imported.language,
+                old_value,
+                new_value: imported.value,
+            });
+        }
+    }
+    report
+}
+
+/// Writes the changed values of a report, one language file at a time. Values are set as they
+/// are: an imported source value does not retranslate the other languages.
+pub async fn apply_import(
+    format: &dyn TranslationFormat,
+    location: &location::Data,
+    settings: &settings::Data,
+    entries: &[TranslationEntry],
+    changes: &[ValueChange],
+) -> Result<(), io::Error> {
+    let mut by_language: BTreeMap<&str, Vec<KeyValue>> = BTreeMap::new();
+    for change in changes {
+        let Some(entry) = entries.iter().find(|entry| entry.key == change.key) else {
+            continue;
+        };
+        by_language
+            .entry(change.language.as_str())
+            .or_default()
+            .push(KeyValue {
+                ts_key: entry.key.clone(),
+                json_key: entry.value.clone(),
+                value: change.new_value.clone(),
+            });
+    }
+    for (language, values) in by_language {
+        format
+            .set_values(location, settings, language, values)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, translations: &[(&str, &str)]) -> TranslationEntry {
+        TranslationEntry {
+            key: key.to_string(),
+            value: key.to_string(),
+            translations: translations
+                .iter()
+                .map(|(language, value)| (language.to_string(), value.to_string()))
+                .collect(),
+            in_use: true,
+            ..Default::default()
+        }
+    }
+
+    fn imported(key: &str, source: Option<&str>, value: &str) -> ImportedValue {
+        ImportedValue {
+            key: key.to_string(),
+            language: "de".to_string(),
+            source: source.map(str::to_string),
+            value: value.to_string(),
+            needs_review: false,
+        }
+    }
+
+    #[test]
+    fn plan_import_sorts_values_into_changed_unknown_and_conflicted() {
+        let entries = vec![
+            entry("save", &[("en", "Save"), ("de", "")]),
+            entry("open", &[("en", "Open"), ("de", "Öffnen")]),
+            entry("close", &[("en", "Close now"), ("de", "")]),
+        ];
+        let report = plan_import(
+            &entries,
+            "en",
+            vec![
+                imported("save", Some("Save"), "Speichern"),
+                imported("open", Some("Open"), "Öffnen"),
+                imported("close", Some("Close"), "Schließen"),
+                imported("gone", None, "Weg"),
+            ],
+        );
+
+        assert_eq!(
+            vec![ValueChange {
+                key: "save".to_string(),
+                language: "de".to_string(),
+                old_value: "".to_string(),
+                new_value: "Speichern".to_string(),
+            }],
+            report.changed
+        );
+        assert_eq!(vec!["gone".to_string()], report.unknown);
+        assert_eq!(
+            ConflictReason::SourceChanged {
+                exported: "Close".to_string(),
+                current: "Close now".to_string(),
+            },
+            report.conflicted[0].reason
+        );
+        assert_eq!(1, report.unchanged);
+    }
+
+    #[test]
+    fn plan_import_drops_values_given_twice() {
+        let entries = vec![entry("save", &[("en", "Save")])];
+        let report = plan_import(
+            &entries,
+            "en",
+            vec![
+                imported("save", None, "Speichern"),
+                imported("save", None, "Sichern"),
+            ],
+        );
+        assert!(report.changed.is_empty());
+        assert_eq!(ConflictReason::Duplicate, report.conflicted[0].reason);
+    }
+}
@@ -0,0 +1,127 @@
+use super::{invalid, Matrix};
+use std::io;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Writes RFC 4180 CSV with a byte order mark, so spreadsheet applications detect UTF-8.
+pub fn to_string(matrix: &Matrix) -> String {
+    let mut csv = String::from(BYTE_ORDER_MARK);
+    for row in matrix {
+        let fields: Vec<String> = row.iter().map(|field| quote(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+fn quote(field: &str) -> String {
+    let needs_quotes = field.contains([',', '"', '\r', '\n'])
+        || field.starts_with(char::is_whitespace)
+        || field.ends_with(char::is_whitespace);
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Reads CSV as written by spreadsheet applications. The separator is taken from the header
+/// line, since some locales save with semicolons.
+pub fn parse(content: &str) -> Result<Matrix, io::Error> {
+    let content = content.strip_prefix(BYTE_ORDER_MARK).unwrap_or(content);
+    let separator = separator(content);
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            '"' => {
+                return Err(invalid(format!(
+                    "line {}: quote inside an unquoted field",
+                    line
+                )))
+            }
+            c if c == separator => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                push_row(&mut rows, std::mem::take(&mut row));
+                line += 1;
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(invalid(format!("line {}: unterminated quoted field", line)));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        push_row(&mut rows, row);
+    }
+    Ok(rows)
+}
+
+/// Blank lines are not rows.
+fn push_row(rows: &mut Matrix, row: Vec<String>) {
+    if row.iter().any(|field| !field.is_empty()) {
+        rows.push(row);
+    }
+}
+
+fn separator(content: &str) -> char {
+    let header = content.lines().next().unwrap_or_default();
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|separator| header.matches(*separator).count())
+        .filter(|separator| header.contains(*separator))
+        .unwrap_or(',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields_survive_a_round_trip() {
+        let matrix: Matrix = vec![
+            vec!["ts_key".to_string(), "en".to_string()],
+            vec!["greeting".to_string(), "Hello, \"friend\"".to_string()],
+            vec!["lines".to_string(), "first\nsecond".to_string()],
+            vec!["padded".to_string(), " spaced ".to_string()],
+        ];
+        assert_eq!(matrix, parse(&to_string(&matrix)).unwrap());
+    }
+
+    #[test]
+    fn parse_detects_semicolons() {
+        let matrix = parse("ts_key;de\r\nsave;\"Speichern; jetzt\"\r\n\r\n").unwrap();
+        assert_eq!(
+            vec![
+                vec!["ts_key".to_string(), "de".to_string()],
+                vec!["save".to_string(), "Speichern; jetzt".to_string()],
+            ],
+            matrix
+        );
+    }
+}
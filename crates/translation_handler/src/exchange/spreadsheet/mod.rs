@@ -0,0 +1,159 @@
+mod csv;
+mod xlsx;
+
+use crate::exchange::ImportedValue;
+use local_storage::stores::translation_store::TranslationEntry;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const TS_KEY_COLUMN: &str = "ts_key";
+const JSON_KEY_COLUMN: &str = "json_key";
+const IN_USE_COLUMN: &str = "in_use";
+
+/// A sheet as rows of cells, the first row being the header.
+pub type Matrix = Vec<Vec<String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadsheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SpreadsheetFormat {
+    /// Format of a file, going by its extension.
+    pub fn from_path(path: &Path) -> Result<Self, io::Error> {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("csv") => Ok(SpreadsheetFormat::Csv),
+            Some("xlsx") => Ok(SpreadsheetFormat::Xlsx),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is neither a .csv nor a .xlsx file", path.display()),
+            )),
+        }
+    }
+}
+
+/// One row per key and one column per language, after the key columns.
+pub fn export_matrix(entries: &[TranslationEntry], languages: &[String]) -> Matrix {
+    let mut header = vec![
+        TS_KEY_COLUMN.to_string(),
+        JSON_KEY_COLUMN.to_string(),
+        IN_USE_COLUMN.to_string(),
+    ];
+    header.extend(languages.iter().cloned());
+
+    let mut rows = vec![header];
+    rows.extend(entries.iter().map(|entry| {
+        let mut row = vec![
+            entry.key.clone(),
+            entry.value.clone(),
+            entry.in_use.to_string(),
+        ];
+        row.extend(languages.iter().map(|language| {
+            entry
+                .translations
+                .get(language)
+                .cloned()
+                .unwrap_or_default()
+        }));
+        row
+    }));
+    rows
+}
+
+/// The language cells of a sheet. Every column that is not a key column is a language, empty
+/// cells are left out so they do not clear a translation.
+pub fn imported_values(matrix: &Matrix) -> Result<Vec<ImportedValue>, io::Error> {
+    let Some((header, rows)) = matrix.split_first() else {
+        return Err(invalid("the sheet is empty".to_string()));
+    };
+    let key_column = header
+        .iter()
+        .position(|column| column.trim() == TS_KEY_COLUMN)
+        .ok_or_else(|| invalid(format!("the sheet has no {} column", TS_KEY_COLUMN)))?;
+    let language_columns: Vec<(usize, &str)> = header
+        .iter()
+        .enumerate()
+        .map(|(index, column)| (index, column.trim()))
+        .filter(|(_, column)| {
+            !column.is_empty() && ![TS_KEY_COLUMN, JSON_KEY_COLUMN, IN_USE_COLUMN].contains(column)
+        })
+        .collect();
+
+    let mut values = Vec::new();
+    for row in rows {
+        let Some(key) = row.get(key_column).filter(|key| !key.trim().is_empty()) else {
+            continue;
+        };
+        for (index, language) in &language_columns {
+            let Some(value) = row.get(*index).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            values.push(ImportedValue {
+                key: key.trim().to_string(),
+                language: language.to_string(),
+                source: None,
+                value: value.clone(),
+                needs_review: false,
+            });
+        }
+    }
+    Ok(values)
+}
+
+pub fn read_matrix(path: &Path) -> Result<Matrix, io::Error> {
+    let matrix = match SpreadsheetFormat::from_path(path)? {
+        SpreadsheetFormat::Csv => csv::parse(&fs::read_to_string(path)?),
+        SpreadsheetFormat::Xlsx => xlsx::parse(&fs::read(path)?),
+    };
+    matrix.map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+pub fn write_matrix(path: &Path, matrix: &Matrix) -> Result<(), io::Error> {
+    match SpreadsheetFormat::from_path(path)? {
+        SpreadsheetFormat::Csv => fs::write(path, csv::to_string(matrix)),
+        SpreadsheetFormat::Xlsx => fs::write(path, xlsx::to_bytes(matrix)?),
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::plan_import;
+
+    #[test]
+    fn imported_values_reads_language_columns_only() {
+        let entry = TranslationEntry {
+            key: "saveButton".to_string(),
+            value: "save_button".to_string(),
+            translations: [("en", "Save"), ("de", "Speichern"), ("fr", "")]
+                .iter()
+                .map(|(language, value)| (language.to_string(), value.to_string()))
+                .collect(),
+            in_use: true,
+            ..Default::default()
+        };
+        let languages = vec!["en".to_string(), "de".to_string(), "fr".to_string()];
+        let mut matrix = export_matrix(&[entry.clone()], &languages);
+        assert_eq!(
+            vec!["ts_key", "json_key", "in_use", "en", "de", "fr"],
+            matrix[0]
+        );
+        matrix[1][4] = "Sichern".to_string();
+
+        let values = imported_values(&matrix).unwrap();
+        assert_eq!(2, values.len());
+        let report = plan_import(&[entry], "en", values);
+        assert_eq!(1, report.changed.len());
+        assert_eq!("Sichern", report.changed[0].new_value);
+        assert_eq!(1, report.unchanged);
+    }
+}
@@ -0,0 +1,357 @@
+use super::{invalid, Matrix};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::io::{self, Cursor, Read, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="xml" ContentType="application/xml"/>
+  <Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>
+  <Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>
+</Types>
+"#;
+
+const ROOT_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>
+</Relationships>
+"#;
+
+const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <sheets>
+    <sheet name="Translations" sheetId="1" r:id="rId1"/>
+  </sheets>
+</workbook>
+"#;
+
+const WORKBOOK_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+</Relationships>
+"#;
+
+const DEFAULT_SHEET: &str = "xl/worksheets/sheet1.xml";
+
+/// A workbook with a single sheet. Cells are inline strings, so no shared string table or
+/// styles part is needed.
+pub fn to_bytes(matrix: &Matrix) -> Result<Vec<u8>, io::Error> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELATIONSHIPS.to_string()),
+        ("xl/workbook.xml", WORKBOOK.to_string()),
+        (
+            "xl/_rels/workbook.xml.rels",
+            WORKBOOK_RELATIONSHIPS.to_string(),
+        ),
+        (DEFAULT_SHEET, sheet_xml(matrix)),
+    ] {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+fn sheet_xml(matrix: &Matrix) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\n\
+         <sheetData>\n",
+    );
+    for (row_index, row) in matrix.iter().enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">", row_index + 1));
+        for (column_index, value) in row.iter().enumerate() {
+            if value.is_empty() {
+                continue;
+            }
+            // Control characters other than tab and newlines are not allowed in XML 1.0
+            let value: String = value
+                .chars()
+                .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+                .collect();
+            xml.push_str(&format!(
+                "<c r=\"{}{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                column_name(column_index),
+                row_index + 1,
+                escape(&value)
+            ));
+        }
+        xml.push_str("</row>\n");
+    }
+    xml.push_str("</sheetData>\n</worksheet>\n");
+    xml
+}
+
+/// `A`, `B`, ..., `Z`, `AA`, ... for a zero based column index.
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// Zero based column index of a cell reference such as `AB12`.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference
+        .bytes()
+        .take_while(|byte| byte.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let number = letters.iter().fold(0usize, |number, letter| {
+        number * 26 + (letter.to_ascii_uppercase() - b'A' + 1) as usize
+    });
+    Some(number - 1)
+}
+
+/// Reads the first sheet of a workbook saved by any spreadsheet application.
+pub fn parse(bytes: &[u8]) -> Result<Matrix, io::Error> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| invalid(e.to_string()))?;
+    let shared_strings = match read_part(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => parse_shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+    let sheet_path = first_sheet_path(&mut archive)?.unwrap_or_else(|| DEFAULT_SHEET.to_string());
+    let sheet = read_part(&mut archive, &sheet_path)?
+        .ok_or_else(|| invalid(format!("the workbook has no {}", sheet_path)))?;
+    parse_sheet(&sheet, &shared_strings)
+}
+
+fn read_part(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<String>, io::Error> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(error) => return Err(invalid(error.to_string())),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(Some(content))
+}
+
+fn xml_error(error: quick_xml::Error) -> io::Error {
+    invalid(error.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, io::Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|e| invalid(e.to_string()))?;
+        if attribute.key.local_name().as_ref() == name.as_bytes() {
+            let value = attribute.unescape_value().map_err(xml_error)?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Path of the first sheet in the workbook, resolved through the workbook relationships.
+fn first_sheet_path(archive: &mut ZipArchive<Cursor<&[u8]>>) -> Result<Option<String>, io::Error> {
+    let (Some(workbook), Some(relationships)) = (
+        read_part(archive, "xl/workbook.xml")?,
+        read_part(archive, "xl/_rels/workbook.xml.rels")?,
+    ) else {
+        return Ok(None);
+    };
+
+    let mut relationship_id = None;
+    let mut reader = Reader::from_str(&workbook);
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(ref element) | Event::Empty(ref element)
+                if element.local_name().as_ref() == b"sheet" =>
+            {
+                relationship_id = attribute(element, "id")?;
+                break;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let Some(relationship_id) = relationship_id else {
+        return Ok(None);
+    };
+
+    let mut reader = Reader::from_str(&relationships);
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(ref element) | Event::Empty(ref element)
+                if element.local_name().as_ref() == b"Relationship"
+                    && attribute(element, "Id")?.as_deref() == Some(&relationship_id) =>
+            {
+                return Ok(attribute(element, "Target")?.map(|target| {
+                    match target.strip_prefix('/') {
+                        Some(absolute) => absolute.to_string(),
+                        None => format!("xl/{}", target),
+                    }
+                }));
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Text of each `<si>`, joining rich text runs and leaving out phonetic hints.
+fn parse_shared_strings(xml: &str) -> Result<Vec<String>, io::Error> {
+    let mut strings = Vec::new();
+    let mut reader = Reader::from_str(xml);
+    let mut current: Option<String> = None;
+    let mut in_text = false;
+    let mut in_phonetic = false;
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(ref element) => match element.local_name().as_ref() {
+                b"si" => current = Some(String::new()),
+                b"rPh" => in_phonetic = true,
+                b"t" => in_text = !in_phonetic,
+                _ => {}
+            },
+            Event::Empty(ref element) if element.local_name().as_ref() == b"si" => {
+                strings.push(String::new());
+            }
+            Event::Text(ref text) if in_text => {
+                if let Some(current) = current.as_mut() {
+                    current.push_str(&text.unescape().map_err(xml_error)?);
+                }
+            }
+            Event::End(ref element) => match element.local_name().as_ref() {
+                b"si" => strings.extend(current.take()),
+                b"rPh" => in_phonetic = false,
+                b"t" => in_text = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+#[derive(Default)]
+struct Cell {
+    column: Option<usize>,
+    kind: Option<String>,
+    value: String,
+}
+
+fn parse_sheet(xml: &str, shared_strings: &[String]) -> Result<Matrix, io::Error> {
+    let mut rows: Matrix = Vec::new();
+    let mut row: Option<Vec<String>> = None;
+    let mut cell: Option<Cell> = None;
+    let mut in_value = false;
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(ref element) => match element.local_name().as_ref() {
+                b"row" => row = Some(Vec::new()),
+                b"c" => {
+                    cell = Some(Cell {
+                        column: attribute(element, "r")?.and_then(|r| column_index(&r)),
+                        kind: attribute(element, "t")?,
+                        value: String::new(),
+                    })
+                }
+                b"v" | b"t" => in_value = cell.is_some(),
+                _ => {}
+            },
+            Event::Empty(ref element) if element.local_name().as_ref() == b"row" => {
+                rows.push(Vec::new());
+            }
+            Event::Text(ref text) if in_value => {
+                if let Some(cell) = cell.as_mut() {
+                    cell.value.push_str(&text.unescape().map_err(xml_error)?);
+                }
+            }
+            Event::End(ref element) => match element.local_name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    if let (Some(row), Some(cell)) = (row.as_mut(), cell.take()) {
+                        let column = cell.column.unwrap_or(row.len());
+                        let value = cell_value(cell, shared_strings)?;
+                        if row.len() <= column {
+                            row.resize(column + 1, String::new());
+                        }
+                        row[column] = value;
+                    }
+                }
+                b"row" => rows.extend(row.take()),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    // Rows without any value are formatting only
+    while rows
+        .last()
+        .is_some_and(|row| row.iter().all(|value| value.is_empty()))
+    {
+        rows.pop();
+    }
+    Ok(rows)
+}
+
+fn cell_value(cell: Cell, shared_strings: &[String]) -> Result<String, io::Error> {
+    match cell.kind.as_deref() {
+        Some("s") => {
+            let index: usize = cell
+                .value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("invalid shared string index {}", cell.value)))?;
+            shared_strings
+                .get(index)
+                .cloned()
+                .ok_or_else(|| invalid(format!("shared string {} does not exist", index)))
+        }
+        Some("b") => Ok(match cell.value.trim() {
+            "1" => "true".to_string(),
+            _ => "false".to_string(),
+        }),
+        _ => Ok(cell.value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workbook_survives_a_round_trip() {
+        let matrix: Matrix = vec![
+            vec!["ts_key".to_string(), "in_use".to_string(), "en".to_string()],
+            vec![
+                "greeting".to_string(),
+                "true".to_string(),
+                "Hello <b>&</b>\nworld ".to_string(),
+            ],
+            vec!["empty".to_string(), "false".to_string(), "".to_string()],
+        ];
+        let parsed = parse(&to_bytes(&matrix).unwrap()).unwrap();
+        assert_eq!(matrix[..2], parsed[..2]);
+        assert_eq!(vec!["empty".to_string(), "false".to_string()], parsed[2]);
+    }
+
+    #[test]
+    fn column_names_and_indexes_agree() {
+        for (index, name) in [(0, "A"), (25, "Z"), (26, "AA"), (701, "ZZ"), (702, "AAA")] {
+            assert_eq!(name, column_name(index));
+            assert_eq!(Some(index), column_index(&format!("{}7", name)));
+        }
+    }
+}
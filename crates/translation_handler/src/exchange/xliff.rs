@@ -0,0 +1,514 @@
+use crate::exchange::ImportedValue;
+use local_storage::stores::translation_store::TranslationEntry;
+use quick_xml::events::{BytesDecl, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, Cursor};
+use std::path::Path;
+
+const XLIFF_1_2_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:1.2";
+const XLIFF_2_0_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, specta::Type)]
+pub enum XliffVersion {
+    #[serde(rename = "1.2")]
+    V1_2,
+    #[serde(rename = "2.0")]
+    V2_0,
+}
+
+impl XliffVersion {
+    /// State of an exported unit, in the vocabulary of the version.
+    fn state(&self, translated: bool) -> &'static str {
+        match (self, translated) {
+            (XliffVersion::V1_2, true) => "translated",
+            (XliffVersion::V1_2, false) => "needs-translation",
+            (XliffVersion::V2_0, true) => "translated",
+            (XliffVersion::V2_0, false) => "initial",
+        }
+    }
+
+    /// Whether a target in `state` is done. Units without a state count as done.
+    fn is_final_state(&self, state: &str) -> bool {
+        match self {
+            XliffVersion::V1_2 => matches!(state, "translated" | "final" | "signed-off"),
+            XliffVersion::V2_0 => matches!(state, "translated" | "reviewed" | "final"),
+        }
+    }
+}
+
+/// A trans-unit (1.2) or unit (2.0). Multiple 2.0 segments are joined into one text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XliffUnit {
+    pub id: String,
+    pub source: String,
+    pub target: Option<String>,
+    pub state: Option<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XliffDocument {
+    pub version: XliffVersion,
+    pub original: String,
+    pub source_language: String,
+    pub target_language: Option<String>,
+    pub units: Vec<XliffUnit>,
+}
+
+/// The units for `target_language`, with the current translation as target. With
+/// `untranslated_only` only keys without a translation are included.
+pub fn export_document(
+    entries: &[TranslationEntry],
+    original: &str,
+    source_language: &str,
+    target_language: &str,
+    version: XliffVersion,
+    untranslated_only: bool,
+) -> XliffDocument {
+    let units = entries
+        .iter()
+        // Plural forms have no XLIFF equivalent without ICU in the source text
+        .filter(|entry| entry.plurals.is_empty())
+        .filter_map(|entry| {
+            let target = entry
+                .translations
+                .get(target_language)
+                .filter(|value| !value.trim().is_empty())
+                .cloned();
+            if untranslated_only && target.is_some() {
+                return None;
+            }
+            let mut notes = Vec::new();
+            if entry.value != entry.key {
+                notes.push(format!("File key: {}", entry.value));
+            }
+            if !entry.in_use {
+                notes.push("Not used in the code".to_string());
+            }
+            Some(XliffUnit {
+                id: entry.key.clone(),
+                source: entry
+                    .translations
+                    .get(source_language)
+                    .cloned()
+                    .unwrap_or_default(),
+                state: Some(version.state(target.is_some()).to_string()),
+                target,
+                notes,
+            })
+        })
+        .collect();
+    XliffDocument {
+        version,
+        original: original.to_string(),
+        source_language: source_language.to_string(),
+        target_language: Some(target_language.to_string()),
+        units,
+    }
+}
+
+/// Translations of a returned document. Units without a target are skipped, units in a state
+/// that is not done are kept so the import can report them.
+pub fn imported_values(document: &XliffDocument, language: &str) -> Vec<ImportedValue> {
+    document
+        .units
+        .iter()
+        .filter_map(|unit| {
+            let target = unit.target.as_ref().filter(|target| !target.is_empty())?;
+            Some(ImportedValue {
+                key: unit.id.clone(),
+                language: language.to_string(),
+                source: Some(unit.source.clone()),
+                value: target.clone(),
+                needs_review: unit
+                    .state
+                    .as_deref()
+                    .is_some_and(|state| !document.version.is_final_state(state)),
+            })
+        })
+        .collect()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn xml_error(error: quick_xml::Error) -> io::Error {
+    invalid(error.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, io::Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|e| invalid(e.to_string()))?;
+        if attribute.key.local_name().as_ref() == name.as_bytes() {
+            let value = attribute.unescape_value().map_err(xml_error)?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TextField {
+    Source,
+    Target,
+    Note,
+}
+
+impl XliffDocument {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(false);
+
+        let mut document: Option<XliffDocument> = None;
+        let mut unit: Option<XliffUnit> = None;
+        let mut field: Option<TextField> = None;
+        let mut text = String::new();
+        // Depth of inline elements such as <g> or <pc> inside a source or target
+        let mut inline_depth = 0;
+
+        loop {
+            let position = reader.buffer_position();
+            let event = reader
+                .read_event()
+                .map_err(|e| invalid(format!("at byte {}: {}", position, e)))?;
+            match event {
+                Event::Start(ref element) | Event::Empty(ref element) => {
+                    let is_empty = matches!(event, Event::Empty(_));
+                    let name = element.local_name();
+                    match name.as_ref() {
+                        _ if field.is_some() => {
+                            if !is_empty {
+                                inline_depth += 1;
+                            }
+                        }
+                        b"xliff" => {
+                            let version = match attribute(element, "version")?.as_deref() {
+                                Some("1.2") => XliffVersion::V1_2,
+                                Some(version) if version.starts_with("2.") => XliffVersion::V2_0,
+                                other => {
+                                    return Err(invalid(format!(
+                                        "unsupported XLIFF version {:?}",
+                                        other
+                                    )))
+                                }
+                            };
+                            document = Some(XliffDocument {
+                                version,
+                                original: String::new(),
+                                source_language: attribute(element, "srcLang")?.unwrap_or_default(),
+                                target_language: attribute(element, "trgLang")?,
+                                units: Vec::new(),
+                            });
+                        }
+                        b"file" => {
+                            let document = document
+                                .as_mut()
+                                .ok_or_else(|| invalid("<file> outside <xliff>".to_string()))?;
+                            document.original = attribute(element, "original")?.unwrap_or_default();
+                            if let Some(source_language) = attribute(element, "source-language")? {
+                                document.source_language = source_language;
+                            }
+                            if let Some(target_language) = attribute(element, "target-language")? {
+                                document.target_language = Some(target_language);
+                            }
+                        }
+                        b"trans-unit" | b"unit" => {
+                            let id = attribute(element, "id")?.ok_or_else(|| {
+                                invalid("unit without an id attribute".to_string())
+                            })?;
+                            unit = Some(XliffUnit {
+                                id,
+                                state: None,
+                                ..Default::default()
+                            });
+                        }
+                        b"segment" => {
+                            if let Some(unit) = unit.as_mut() {
+                                if let Some(state) = attribute(element, "state")? {
+                                    unit.state = Some(state);
+                                }
+                            }
+                        }
+                        b"source" | b"target" | b"note" if unit.is_some() => {
+                            let current = match name.as_ref() {
+                                b"source" => TextField::Source,
+                                b"target" => TextField::Target,
+                                _ => TextField::Note,
+                            };
+                            let unit = unit.as_mut().unwrap();
+                            if current == TextField::Target {
+                                if let Some(state) = attribute(element, "state")? {
+                                    unit.state = Some(state);
+                                }
+                                // 2.0 segments add up to one target
+                                unit.target.get_or_insert_with(String::new);
+                            }
+                            if !is_empty {
+                                field = Some(current);
+                                text.clear();
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(ref content) if field.is_some() => {
+                    text.push_str(&content.unescape().map_err(xml_error)?);
+                }
+                Event::CData(content) if field.is_some() => {
+                    text.push_str(&String::from_utf8_lossy(&content.into_inner()));
+                }
+                Event::End(ref element) => {
+                    let name = element.local_name();
+                    if field.is_some() && inline_depth > 0 {
+                        inline_depth -= 1;
+                        continue;
+                    }
+                    match name.as_ref() {
+                        b"source" | b"target" | b"note" if field.is_some() => {
+                            let unit = unit.as_mut().unwrap();
+                            match field.take().unwrap() {
+                                TextField::Source => unit.source.push_str(&text),
+                                TextField::Target => {
+                                    unit.target.get_or_insert_with(String::new).push_str(&text)
+                                }
+                                TextField::Note => unit.notes.push(text.clone()),
+                            }
+                        }
+                        b"trans-unit" | b"unit" => {
+                            if let (Some(document), Some(mut unit)) =
+                                (document.as_mut(), unit.take())
+                            {
+                                // An empty target is an untranslated unit
+                                if unit.target.as_deref() == Some("") {
+                                    unit.target = None;
+                                }
+                                document.units.push(unit);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        document.ok_or_else(|| invalid("not an XLIFF document".to_string()))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_xml()?)
+    }
+
+    pub fn to_xml(&self) -> Result<String, io::Error> {
+        let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+            .map_err(xml_error)?;
+        let target_language = self.target_language.as_deref().unwrap_or_default();
+        match self.version {
+            XliffVersion::V1_2 => writer
+                .create_element("xliff")
+                .with_attributes([("version", "1.2"), ("xmlns", XLIFF_1_2_NAMESPACE)])
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("file")
+                        .with_attributes([
+                            ("original", self.original.as_str()),
+                            ("source-language", self.source_language.as_str()),
+                            ("target-language", target_language),
+                            ("datatype", "plaintext"),
+                        ])
+                        .write_inner_content(|writer| {
+                            writer
+                                .create_element("body")
+                                .write_inner_content(|writer| self.write_units_1_2(writer))?;
+                            Ok::<(), quick_xml::Error>(())
+                        })?;
+                    Ok::<(), quick_xml::Error>(())
+                }),
+            XliffVersion::V2_0 => writer
+                .create_element("xliff")
+                .with_attributes([
+                    ("xmlns", XLIFF_2_0_NAMESPACE),
+                    ("version", "2.0"),
+                    ("srcLang", self.source_language.as_str()),
+                    ("trgLang", target_language),
+                ])
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("file")
+                        .with_attributes([("id", "f1"), ("original", self.original.as_str())])
+                        .write_inner_content(|writer| self.write_units_2_0(writer))?;
+                    Ok::<(), quick_xml::Error>(())
+                }),
+        }
+        .map_err(xml_error)?;
+        let mut xml = String::from_utf8(writer.into_inner().into_inner())
+            .map_err(|e| invalid(e.to_string()))?;
+        xml.push('\n');
+        Ok(xml)
+    }
+
+    fn write_units_1_2(&self, writer: &mut Writer<Cursor<Vec<u8>>>) -> quick_xml::Result<()> {
+        for unit in &self.units {
+            writer
+                .create_element("trans-unit")
+                .with_attribute(("id", unit.id.as_str()))
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("source")
+                        .write_text_content(BytesText::new(&unit.source))?;
+                    let mut target = writer.create_element("target");
+                    if let Some(state) = &unit.state {
+                        target = target.with_attribute(("state", state.as_str()));
+                    }
+                    target.write_text_content(BytesText::new(
+                        unit.target.as_deref().unwrap_or_default(),
+                    ))?;
+                    for note in &unit.notes {
+                        writer
+                            .create_element("note")
+                            .write_text_content(BytesText::new(note))?;
+                    }
+                    Ok::<(), quick_xml::Error>(())
+                })?;
+        }
+        Ok(())
+    }
+
+    fn write_units_2_0(&self, writer: &mut Writer<Cursor<Vec<u8>>>) -> quick_xml::Result<()> {
+        for unit in &self.units {
+            writer
+                .create_element("unit")
+                .with_attribute(("id", unit.id.as_str()))
+                .write_inner_content(|writer| {
+                    if !unit.notes.is_empty() {
+                        writer
+                            .create_element("notes")
+                            .write_inner_content(|writer| {
+                                for note in &unit.notes {
+                                    writer
+                                        .create_element("note")
+                                        .write_text_content(BytesText::new(note))?;
+                                }
+                                Ok::<(), quick_xml::Error>(())
+                            })?;
+                    }
+                    let mut segment = writer.create_element("segment");
+                    if let Some(state) = &unit.state {
+                        segment = segment.with_attribute(("state", state.as_str()));
+                    }
+                    segment.write_inner_content(|writer| {
+                        writer
+                            .create_element("source")
+                            .write_text_content(BytesText::new(&unit.source))?;
+                        if let Some(target) = &unit.target {
+                            writer
+                                .create_element("target")
+                                .write_text_content(BytesText::new(target))?;
+                        }
+                        Ok::<(), quick_xml::Error>(())
+                    })?;
+                    Ok::<(), quick_xml::Error>(())
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<TranslationEntry> {
+        vec![
+            TranslationEntry {
+                key: "saveButton".to_string(),
+                value: "common.save".to_string(),
+                translations: [("en", "Save <all>"), ("de", "Alles speichern")]
+                    .into_iter()
+                    .map(|(language, value)| (language.to_string(), value.to_string()))
+                    .collect(),
+                in_use: true,
+                ..Default::default()
+            },
+            TranslationEntry {
+                key: "open".to_string(),
+                value: "open".to_string(),
+                translations: [("en", "Open")]
+                    .into_iter()
+                    .map(|(language, value)| (language.to_string(), value.to_string()))
+                    .collect(),
+                in_use: false,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trips_both_versions() {
+        for version in [XliffVersion::V1_2, XliffVersion::V2_0] {
+            let document = export_document(&entries(), "app", "en", "de", version, false);
+            let xml = document.to_xml().unwrap();
+            assert!(xml.contains("Save &lt;all&gt;"), "{}", xml);
+
+            let parsed = XliffDocument::parse(&xml).unwrap();
+            assert_eq!(version, parsed.version);
+            assert_eq!(Some("de".to_string()), parsed.target_language);
+            assert_eq!(document.units, parsed.units);
+        }
+    }
+
+    #[test]
+    fn exports_only_untranslated_units() {
+        let document = export_document(&entries(), "app", "en", "de", XliffVersion::V1_2, true);
+        let ids: Vec<&str> = document.units.iter().map(|unit| unit.id.as_str()).collect();
+        assert_eq!(vec!["open"], ids);
+        assert_eq!(
+            vec!["Not used in the code".to_string()],
+            document.units[0].notes
+        );
+    }
+
+    #[test]
+    fn imports_targets_and_flags_units_under_review() {
+        let document = XliffDocument::parse(
+            r#"<?xml version="1.0"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file original="app" source-language="en" target-language="de" datatype="plaintext">
+    <body>
+      <trans-unit id="saveButton">
+        <source>Save</source>
+        <target state="translated">Speichern <g id="1">alles</g></target>
+      </trans-unit>
+      <trans-unit id="open">
+        <source>Open</source>
+        <target state="needs-review-translation"><![CDATA[Öffnen]]></target>
+      </trans-unit>
+      <trans-unit id="close">
+        <source>Close</source>
+        <target state="needs-translation"/>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>"#,
+        )
+        .unwrap();
+
+        let values = imported_values(&document, "de");
+        assert_eq!(2, values.len());
+        assert_eq!("Speichern alles", values[0].value);
+        assert!(!values[0].needs_review);
+        assert_eq!("Öffnen", values[1].value);
+        assert!(values[1].needs_review);
+    }
+}
@@ -0,0 +1,200 @@
+pub mod resource;
+
+use local_storage::stores::translation_store::TranslationEntry;
+use resource::FtlResource;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tag the location is stored with, which picks its translation format.
+pub const TAG: &str = "FLUENT";
+
+/// File new keys go to when the source language has no resources yet.
+const DEFAULT_FILE: &str = "main.ftl";
+
+fn ftl_files(directory: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "ftl"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Languages of the `locales/<language>` directories that have `.ftl` files.
+pub fn get_languages(path: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|dir_entry| !ftl_files(&dir_entry.path()).is_empty())
+        .map(|dir_entry| dir_entry.file_name().to_string_lossy().to_string())
+        .collect();
+    languages.sort();
+    languages
+}
+
+pub fn is_location(path: &Path) -> bool {
+    path.is_dir() && !get_languages(path).is_empty()
+}
+
+/// The language the others are translated from: the default language of the settings, its
+/// primary subtag, a region of it such as `en-US`, or English.
+pub fn source_language(path: &Path, default_language: &str) -> String {
+    let languages = get_languages(path);
+    let primary = default_language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default();
+    let regional = |language: &&String| {
+        language
+            .split(['-', '_'])
+            .next()
+            .is_some_and(|subtag| subtag == primary)
+    };
+    [default_language, primary, "en", "en-US"]
+        .into_iter()
+        .find(|candidate| languages.iter().any(|language| language == candidate))
+        .or(languages.iter().find(regional).map(String::as_str))
+        .or(languages.first().map(String::as_str))
+        .unwrap_or(default_language)
+        .to_string()
+}
+
+/// Entries keyed by message id, `-term` id or `id.attribute`, in the order keys first
+/// appear. Languages without a value for a key get an empty translation.
+pub fn get_translations(path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    let languages = get_languages(path);
+    let mut entries: Vec<TranslationEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for language in &languages {
+        for file in ftl_files(&path.join(language)) {
+            for (key, value) in FtlResource::read(&file)?.values() {
+                let position = *positions.entry(key.clone()).or_insert_with(|| {
+                    entries.push(TranslationEntry {
+                        key: key.clone(),
+                        value: key.clone(),
+                        in_use: true,
+                        ..Default::default()
+                    });
+                    entries.len() - 1
+                });
+                entries[position]
+                    .translations
+                    .insert(language.clone(), value);
+            }
+        }
+    }
+    for entry in &mut entries {
+        for language in &languages {
+            entry.translations.entry(language.clone()).or_default();
+        }
+    }
+    Ok(entries)
+}
+
+/// Name of the file that has `key` in any language.
+fn file_with_key(path: &Path, key: &str) -> Result<Option<String>, io::Error> {
+    for language in get_languages(path) {
+        for file in ftl_files(&path.join(language)) {
+            if FtlResource::read(&file)?.get(key).is_some() {
+                return Ok(file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+pub fn contains_key(path: &Path, key: &str) -> Result<bool, io::Error> {
+    Ok(file_with_key(path, key)?.is_some())
+}
+
+/// Sets the value of `key` in `language`, in the file that has the key in some language.
+/// New keys go to the first file of the source language. An empty value removes the key
+/// from the language, as Fluent falls back for missing messages.
+pub fn set_value(
+    path: &Path,
+    language: &str,
+    source_language: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), io::Error> {
+    let directory = path.join(language);
+    if value.is_empty() {
+        for file in ftl_files(&directory) {
+            let mut resource = FtlResource::read(&file)?;
+            if resource.remove(key)? {
+                resource.write(&file)?;
+            }
+        }
+        return Ok(());
+    }
+    let name = match file_with_key(path, key)? {
+        Some(name) => name,
+        None => ftl_files(&path.join(source_language))
+            .first()
+            .and_then(|file| file.file_name())
+            .map_or(DEFAULT_FILE.to_string(), |name| {
+                name.to_string_lossy().to_string()
+            }),
+    };
+    let file = directory.join(name);
+    let mut resource = if file.is_file() {
+        FtlResource::read(&file)?
+    } else {
+        FtlResource::parse("")?
+    };
+    resource
+        .set(key, value)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", file.display(), e)))?;
+    fs::create_dir_all(&directory)?;
+    resource.write(&file)
+}
+
+pub fn remove_keys(path: &Path, keys: &[String]) -> Result<(), io::Error> {
+    for language in get_languages(path) {
+        for file in ftl_files(&path.join(language)) {
+            let mut resource = FtlResource::read(&file)?;
+            let mut removed = false;
+            for key in keys {
+                removed |= resource.remove(key)?;
+            }
+            if removed {
+                resource.write(&file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Creates `locales/<language>` with an empty file for every file name the other languages use.
+pub fn add_language(path: &Path, language: &str) -> Result<(), io::Error> {
+    let directory = path.join(language);
+    if !ftl_files(&directory).is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has .ftl files", directory.display()),
+        ));
+    }
+    let mut names: Vec<PathBuf> = get_languages(path)
+        .iter()
+        .flat_map(|existing| ftl_files(&path.join(existing)))
+        .filter_map(|file| file.file_name().map(PathBuf::from))
+        .collect();
+    names.sort();
+    names.dedup();
+    if names.is_empty() {
+        names.push(PathBuf::from(DEFAULT_FILE));
+    }
+    fs::create_dir_all(&directory)?;
+    for name in names {
+        fs::write(directory.join(name), "")?;
+    }
+    Ok(())
+}
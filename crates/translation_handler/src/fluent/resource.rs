@@ -0,0 +1,541 @@
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+const INDENT: &str = "    ";
+
+/// A pattern and the lines it is written on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    lines: Range<usize>,
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Attribute {
+    name: String,
+    pattern: Pattern,
+}
+
+/// A message, or a term when the id starts with `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    id: String,
+    /// First line, including the comment attached to the entry
+    start: usize,
+    line: usize,
+    end: usize,
+    value: Option<Pattern>,
+    attributes: Vec<Attribute>,
+}
+
+/// A Fluent (`.ftl`) resource. Edits replace only the lines of the touched message, so
+/// comments, blank lines and select expressions elsewhere stay as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FtlResource {
+    lines: Vec<String>,
+    newline: &'static str,
+    entries: Vec<Entry>,
+}
+
+fn invalid(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line + 1, message),
+    )
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Splits `id = pattern` or `.attribute = pattern` into the name and the text after `=`.
+fn split_definition(text: &str) -> Option<(&str, &str)> {
+    let (name, rest) = text.split_once('=')?;
+    let name = name.trim_end();
+    let identifier = name.strip_prefix('-').unwrap_or(name);
+    is_identifier(identifier).then_some((name, rest))
+}
+
+/// Change of placeable depth over a line, ignoring braces in string literals.
+fn brace_balance(line: &str) -> i32 {
+    let mut balance = 0;
+    let mut in_string = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' if balance > 0 => in_string = !in_string,
+            '{' if !in_string => balance += 1,
+            '}' if !in_string => balance -= 1,
+            _ => {}
+        }
+    }
+    balance
+}
+
+/// The text of a pattern: the part after `=` and the continuation lines without their
+/// common indentation.
+fn pattern_value(first: &str, continuation: &[String]) -> String {
+    let indent = continuation
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut lines: Vec<&str> = Vec::new();
+    let first = first.trim();
+    if !first.is_empty() {
+        lines.push(first);
+    }
+    for line in continuation {
+        if is_blank(line) {
+            lines.push("");
+        } else {
+            lines.push(line[indent..].trim_end());
+        }
+    }
+    while lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Lines of `name = value` indented by `indent`, with continuation lines one step deeper.
+fn format_definition(indent: &str, name: &str, value: &str) -> Vec<String> {
+    let mut value_lines = value.split('\n');
+    let mut lines = vec![format!(
+        "{}{} = {}",
+        indent,
+        name,
+        value_lines.next().unwrap_or_default()
+    )];
+    for line in value_lines {
+        if line.is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.push(format!("{}{}{}", indent, INDENT, line));
+        }
+    }
+    lines
+}
+
+fn parse_entries(lines: &[String]) -> Result<Vec<Entry>, io::Error> {
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        let Some((id, rest)) = (!line.starts_with([' ', '#']))
+            .then(|| split_definition(line))
+            .flatten()
+        else {
+            // Comments, blank lines and junk stay as they are
+            index += 1;
+            continue;
+        };
+        let mut start = index;
+        while start > 0 && lines[start - 1].starts_with('#') && !lines[start - 1].starts_with("##")
+        {
+            start -= 1;
+        }
+
+        // The entry goes on over indented lines and every line inside an open placeable
+        let mut depth = brace_balance(rest);
+        let mut end = index + 1;
+        let mut next = index + 1;
+        while next < lines.len() {
+            let line = &lines[next];
+            if depth > 0 || line.starts_with(' ') {
+                depth += brace_balance(line);
+                next += 1;
+                end = next;
+            } else if is_blank(line) {
+                next += 1;
+            } else {
+                break;
+            }
+        }
+        if depth != 0 {
+            return Err(invalid(index, &format!("unclosed placeable in `{}`", id)));
+        }
+
+        // Split the lines into the value and the attributes
+        let mut value = None;
+        let mut attributes = Vec::new();
+        let mut segment_start = index;
+        let mut segment: Option<(String, &str)> = None;
+        let mut depth = brace_balance(rest);
+        let mut finish = |name: Option<String>, first: &str, range: Range<usize>| {
+            let pattern = Pattern {
+                value: pattern_value(first, &lines[range.start + 1..range.end]),
+                lines: range,
+            };
+            match name {
+                Some(name) => attributes.push(Attribute { name, pattern }),
+                None if !pattern.value.is_empty() => value = Some(pattern),
+                None => {}
+            }
+        };
+        for (offset, line) in lines[index + 1..end].iter().enumerate() {
+            let number = index + 1 + offset;
+            let attribute = (depth == 0)
+                .then(|| line.trim_start().strip_prefix('.'))
+                .flatten()
+                .and_then(split_definition);
+            if let Some((name, first)) = attribute {
+                let (previous_name, previous_first) = segment
+                    .take()
+                    .map_or((None, rest), |(name, first)| (Some(name), first));
+                finish(previous_name, previous_first, segment_start..number);
+                segment = Some((name.to_string(), first));
+                segment_start = number;
+            }
+            depth += brace_balance(line);
+        }
+        let (last_name, last_first) = segment
+            .take()
+            .map_or((None, rest), |(name, first)| (Some(name), first));
+        finish(last_name, last_first, segment_start..end);
+        // A message without value and attributes is junk to Fluent as well
+        if value.is_none() && attributes.is_empty() {
+            index = end;
+            continue;
+        }
+
+        entries.push(Entry {
+            id: id.to_string(),
+            start,
+            line: index,
+            end,
+            value,
+            attributes,
+        });
+        index = end;
+    }
+    Ok(entries)
+}
+
+impl FtlResource {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let lines: Vec<String> = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let entries = parse_entries(&lines)?;
+        Ok(FtlResource {
+            lines,
+            newline,
+            entries,
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_ftl())
+    }
+
+    pub fn to_ftl(&self) -> String {
+        let mut content = self.lines.join(self.newline);
+        if !self.lines.is_empty() {
+            content.push_str(self.newline);
+        }
+        content
+    }
+
+    /// Values keyed by message id, `-term` id or `id.attribute`, in file order.
+    pub fn values(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        for entry in &self.entries {
+            if let Some(pattern) = &entry.value {
+                values.push((entry.id.clone(), pattern.value.clone()));
+            }
+            for attribute in &entry.attributes {
+                values.push((
+                    format!("{}.{}", entry.id, attribute.name),
+                    attribute.pattern.value.clone(),
+                ));
+            }
+        }
+        values
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let (id, attribute) = split_key(key);
+        let entry = self.entries.iter().find(|entry| entry.id == id)?;
+        let pattern = match attribute {
+            Some(name) => {
+                &entry
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.name == name)?
+                    .pattern
+            }
+            None => entry.value.as_ref()?,
+        };
+        Some(&pattern.value)
+    }
+
+    fn splice(&mut self, range: Range<usize>, lines: Vec<String>) -> Result<(), io::Error> {
+        self.lines.splice(range, lines);
+        self.entries = parse_entries(&self.lines)?;
+        Ok(())
+    }
+
+    /// Whether entries are set apart by blank lines rather than grouped.
+    fn separates_entries(&self) -> bool {
+        let separated = self
+            .entries
+            .iter()
+            .filter(|entry| entry.start > 0 && is_blank(&self.lines[entry.start - 1]))
+            .count();
+        separated * 2 > self.entries.len()
+    }
+
+    /// Sets the value of a message, term or attribute. A new message is added after the last
+    /// one, a new attribute after the last line of its message.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), io::Error> {
+        let (id, attribute) = split_key(key);
+        if !is_identifier(id.trim_start_matches('-'))
+            || attribute.is_some_and(|name| !is_identifier(name))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{}` is not a valid Fluent identifier", key),
+            ));
+        }
+        let Some(entry) = self.entries.iter().find(|entry| entry.id == id) else {
+            let mut lines = Vec::new();
+            let insert_at = match self.entries.last() {
+                Some(last) => {
+                    if self.separates_entries() {
+                        lines.push(String::new());
+                    }
+                    last.end
+                }
+                None => {
+                    if self.lines.last().is_some_and(|line| !is_blank(line)) {
+                        lines.push(String::new());
+                    }
+                    self.lines.len()
+                }
+            };
+            match attribute {
+                Some(name) => {
+                    lines.push(format!("{} =", id));
+                    lines.extend(format_definition(INDENT, &format!(".{}", name), value));
+                }
+                None => lines.extend(format_definition("", id, value)),
+            }
+            return self.splice(insert_at..insert_at, lines);
+        };
+
+        let (range, lines) = match attribute {
+            Some(name) => match entry.attributes.iter().find(|a| a.name == name) {
+                Some(existing) => {
+                    let line = &self.lines[existing.pattern.lines.start];
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    (
+                        existing.pattern.lines.clone(),
+                        format_definition(indent, &format!(".{}", name), value),
+                    )
+                }
+                None => (
+                    entry.end..entry.end,
+                    format_definition(INDENT, &format!(".{}", name), value),
+                ),
+            },
+            // A message with attributes only gets its value on the id line
+            None => (
+                entry
+                    .value
+                    .as_ref()
+                    .map_or(entry.line..entry.line + 1, |existing| {
+                        existing.lines.clone()
+                    }),
+                format_definition("", id, value),
+            ),
+        };
+        self.splice(range, lines)
+    }
+
+    /// Removes a message or term with its comment, or a single attribute. A message left
+    /// without value and attributes is removed as a whole.
+    pub fn remove(&mut self, key: &str) -> Result<bool, io::Error> {
+        let (id, attribute) = split_key(key);
+        let Some(entry) = self.entries.iter().find(|entry| entry.id == id) else {
+            return Ok(false);
+        };
+        match attribute {
+            Some(name) => {
+                let Some(existing) = entry.attributes.iter().find(|a| a.name == name) else {
+                    return Ok(false);
+                };
+                if entry.value.is_some() || entry.attributes.len() > 1 {
+                    let range = existing.pattern.lines.clone();
+                    self.splice(range, Vec::new())?;
+                    return Ok(true);
+                }
+            }
+            None => match &entry.value {
+                None => return Ok(false),
+                Some(existing) if !entry.attributes.is_empty() => {
+                    // Keep the attributes under a message without a value
+                    let range = existing.lines.start + 1..existing.lines.end;
+                    let line = entry.line;
+                    self.lines[line] = format!("{} =", entry.id);
+                    self.splice(range, Vec::new())?;
+                    return Ok(true);
+                }
+                Some(_) => {}
+            },
+        }
+        let mut range = entry.start..entry.end;
+        // Don't leave two blank lines where the entry was
+        let blank_before = range.start == 0 || is_blank(&self.lines[range.start - 1]);
+        if blank_before && self.lines.get(range.end).is_some_and(|line| is_blank(line)) {
+            range.end += 1;
+        }
+        self.splice(range, Vec::new())?;
+        Ok(true)
+    }
+}
+
+/// `id.attribute` into the id and the attribute name.
+fn split_key(key: &str) -> (&str, Option<&str>) {
+    match key.split_once('.') {
+        Some((id, attribute)) => (id, Some(attribute)),
+        None => (key, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "### Main window
+
+## Toolbar
+
+# Shown on the save button
+save = Save
+    .title = Save the document
+-brand-name = Notes
+
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+login-input =
+    .placeholder = email@example.com
+    .aria-label = Login input
+";
+
+    #[test]
+    fn messages_terms_and_attributes_are_read() {
+        let resource = FtlResource::parse(CONTENT).unwrap();
+        assert_eq!(
+            vec![
+                ("save".to_string(), "Save".to_string()),
+                ("save.title".to_string(), "Save the document".to_string()),
+                ("-brand-name".to_string(), "Notes".to_string()),
+                (
+                    "emails".to_string(),
+                    "{ $count ->\n    [one] One email\n   *[other] { $count } emails\n}"
+                        .to_string()
+                ),
+                (
+                    "login-input.placeholder".to_string(),
+                    "email@example.com".to_string()
+                ),
+                (
+                    "login-input.aria-label".to_string(),
+                    "Login input".to_string()
+                ),
+            ],
+            resource.values()
+        );
+        assert!(FtlResource::parse("broken = { $x\n").is_err());
+    }
+
+    #[test]
+    fn edits_keep_comments_blank_lines_and_selects() {
+        let mut resource = FtlResource::parse(CONTENT).unwrap();
+        resource.set("save", "Store").unwrap();
+        resource.set("save.accesskey", "S").unwrap();
+        resource
+            .set("login-input.placeholder", "name@example.com")
+            .unwrap();
+        resource.set("login-input", "Login").unwrap();
+        resource.set("cancel", "Cancel\nnow").unwrap();
+        assert!(resource.remove("-brand-name").unwrap());
+        assert!(resource.remove("login-input.aria-label").unwrap());
+        assert!(!resource.remove("missing").unwrap());
+
+        assert_eq!(
+            "### Main window
+
+## Toolbar
+
+# Shown on the save button
+save = Store
+    .title = Save the document
+    .accesskey = S
+
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+login-input = Login
+    .placeholder = name@example.com
+cancel = Cancel
+    now
+",
+            resource.to_ftl()
+        );
+
+        assert!(resource.remove("save.title").unwrap());
+        assert!(resource.remove("save.accesskey").unwrap());
+        assert!(resource.remove("save").unwrap());
+        assert!(resource.remove("login-input").unwrap());
+        assert_eq!(
+            "### Main window
+
+## Toolbar
+
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+login-input =
+    .placeholder = name@example.com
+cancel = Cancel
+    now
+",
+            resource.to_ftl()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::checker::MessageSyntax;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::mobile::android::{self, DEFAULT_LANGUAGE};
+use crate::mobile::{updated_values, MobileValue};
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use std::path::Path;
+
+/// An Android location: the `res` directory, with a `strings.xml` per `values-<qualifier>`
+/// directory.
+pub struct AndroidFormat;
+
+#[async_trait]
+impl TranslationFormat for AndroidFormat {
+    /// The unqualified `values` directory is read as the `default` language.
+    fn source_language(
+        &self,
+        _location: &location::Data,
+        _settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        Ok(DEFAULT_LANGUAGE.to_string())
+    }
+
+    fn is_location(&self, path: &Path) -> bool {
+        android::is_resource_directory(path)
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Printf
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        android::get_translations(Path::new(&location.path))
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let resource_directory = Path::new(&location.path);
+        let file = android::strings_file(resource_directory, DEFAULT_LANGUAGE);
+        if android::read_strings(&file)?
+            .iter()
+            .any(|(name, _)| *name == key.ts_key)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{} already has a string named {}",
+                    file.display(),
+                    key.ts_key
+                ),
+            ));
+        }
+        let value = if key.plural_values.is_empty() {
+            MobileValue::Text(key.value)
+        } else {
+            MobileValue::Plural(key.plural_values.into_iter().collect())
+        };
+        android::write_values(&file, &[(key.ts_key, value)])?;
+        android::get_translations(resource_directory)
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        let resource_directory = Path::new(&location.path);
+        for (language, value) in updated_values(&key) {
+            let file = android::strings_file(resource_directory, &language);
+            android::write_values(&file, &[(key.ts_key.clone(), value)])?;
+        }
+        Ok(())
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let file = android::strings_file(Path::new(&location.path), language);
+        let values: Vec<(String, MobileValue)> = values
+            .into_iter()
+            .map(|value| (value.ts_key, MobileValue::Text(value.value)))
+            .collect();
+        android::write_values(&file, &values)
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        let resource_directory = Path::new(&location.path);
+        for language in android::get_languages(resource_directory) {
+            android::remove_names(
+                &android::strings_file(resource_directory, &language),
+                &ts_keys,
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        Ok(android::get_languages(Path::new(&location.path)))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        android::add_language(Path::new(&location.path), language).map(|_| ())
+    }
+}
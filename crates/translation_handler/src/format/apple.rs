@@ -0,0 +1,108 @@
+use crate::checker::MessageSyntax;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::mobile::apple;
+use crate::mobile::{updated_values, MobileValue};
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use std::path::Path;
+
+/// An iOS or macOS location: either a directory with `<language>.lproj/Localizable.strings`
+/// (and `.stringsdict`) files, or a `.xcstrings` String Catalog.
+pub struct AppleFormat;
+
+#[async_trait]
+impl TranslationFormat for AppleFormat {
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        apple::development_language(Path::new(&location.path), &settings.default_language)
+    }
+
+    fn is_location(&self, path: &Path) -> bool {
+        apple::is_location(path)
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Printf
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        apple::get_translations(Path::new(&location.path))
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let path = Path::new(&location.path);
+        if apple::contains_key(path, &key.ts_key)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already has the key {}", location.path, key.ts_key),
+            ));
+        }
+        let language = self.source_language(location, settings)?;
+        let value = if key.plural_values.is_empty() {
+            MobileValue::Text(key.value)
+        } else {
+            MobileValue::Plural(key.plural_values.into_iter().collect())
+        };
+        apple::set_values(path, &language, &[(key.ts_key, value)])?;
+        apple::get_translations(path)
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        let path = Path::new(&location.path);
+        for (language, value) in updated_values(&key) {
+            apple::set_values(path, &language, &[(key.ts_key.clone(), value)])?;
+        }
+        Ok(())
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let values: Vec<(String, MobileValue)> = values
+            .into_iter()
+            .map(|value| (value.ts_key, MobileValue::Text(value.value)))
+            .collect();
+        apple::set_values(Path::new(&location.path), language, &values)
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        apple::remove_keys(Path::new(&location.path), &ts_keys)
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        apple::get_languages(Path::new(&location.path))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        apple::add_language(Path::new(&location.path), language)
+    }
+}
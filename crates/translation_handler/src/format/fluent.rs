@@ -0,0 +1,132 @@
+use crate::checker::{parse_placeholders, MessageSyntax};
+use crate::fluent;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use std::path::Path;
+
+/// A `locales` directory with a `<language>/*.ftl` folder per language. Messages are keyed by
+/// id, terms by `-id` and attributes by `id.attribute`.
+pub struct FluentFormat;
+
+/// Rejects values that would not parse back, such as an unclosed placeable.
+fn validate(key: &str, value: &str) -> Result<(), io::Error> {
+    parse_placeholders(value, MessageSyntax::Fluent)
+        .map(|_| ())
+        .map_err(|error| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a valid Fluent pattern: {}", key, error),
+            )
+        })
+}
+
+#[async_trait]
+impl TranslationFormat for FluentFormat {
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        Ok(fluent::source_language(
+            Path::new(&location.path),
+            &settings.default_language,
+        ))
+    }
+
+    fn is_location(&self, path: &Path) -> bool {
+        fluent::is_location(path)
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Fluent
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        fluent::get_translations(Path::new(&location.path))
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let path = Path::new(&location.path);
+        if fluent::contains_key(path, &key.ts_key)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already has the key {}", location.path, key.ts_key),
+            ));
+        }
+        validate(&key.ts_key, &key.value)?;
+        let language = self.source_language(location, settings)?;
+        fluent::set_value(path, &language, &language, &key.ts_key, &key.value)?;
+        fluent::get_translations(path)
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        for value in key.translation_values.values() {
+            validate(&key.ts_key, value)?;
+        }
+        let path = Path::new(&location.path);
+        let source_language = self.source_language(location, settings)?;
+        for (language, value) in &key.translation_values {
+            fluent::set_value(path, language, &source_language, &key.ts_key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        for value in &values {
+            validate(&value.ts_key, &value.value)?;
+        }
+        let path = Path::new(&location.path);
+        let source_language = self.source_language(location, settings)?;
+        for value in &values {
+            fluent::set_value(
+                path,
+                language,
+                &source_language,
+                &value.ts_key,
+                &value.value,
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        fluent::remove_keys(Path::new(&location.path), &ts_keys)
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        Ok(fluent::get_languages(Path::new(&location.path)))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        fluent::add_language(Path::new(&location.path), language)
+    }
+}
@@ -0,0 +1,183 @@
+mod android;
+mod apple;
+mod arb;
+mod fluent;
+mod properties;
+mod react;
+mod resx;
+mod yaml;
+
+pub use android::AndroidFormat;
+pub use apple::AppleFormat;
+pub use arb::ArbFormat;
+pub use fluent::FluentFormat;
+pub use properties::PropertiesFormat;
+pub use react::ReactFormat;
+pub use resx::ResxFormat;
+pub use yaml::YamlFormat;
+
+use crate::checker::MessageSyntax;
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::mobile::MobilePlatform;
+use crate::plural::PluralCategory;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// A key to add to a location. Formats without a separate file key use `ts_key` only.
+#[derive(Debug, Clone, Default)]
+pub struct NewKey {
+    pub ts_key: String,
+    pub json_key: String,
+    pub value: String,
+    pub plural_values: HashMap<PluralCategory, String>,
+    /// Note for translators, written by formats that keep one with the key
+    pub description: Option<String>,
+    /// Values of other languages, for formats that add them in the same call
+    pub translations: HashMap<String, String>,
+}
+
+/// A new value of an existing key, written by [`TranslationFormat::set_values`].
+#[derive(Debug, Clone, Default)]
+pub struct KeyValue {
+    pub ts_key: String,
+    pub json_key: String,
+    pub value: String,
+}
+
+/// Operations every kind of location supports, whatever files it keeps its strings in.
+#[async_trait]
+pub trait TranslationFormat: Send + Sync {
+    /// Language the other languages are translated from.
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error>;
+
+    /// Whether a path the user picked holds files of this format, so it can be added without
+    /// naming its type. Formats that need a layout or a project to be found keep the default.
+    fn is_location(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// Interpolation syntax of the values, used by the placeholder checker.
+    fn message_syntax(&self) -> MessageSyntax;
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error>;
+
+    /// Adds a key in the source language and returns the updated entries.
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error>;
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error>;
+
+    /// Writes values of one language as they are, reading and writing its file once. Unlike
+    /// `update`, other languages are left alone and nothing is retranslated.
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error>;
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        json_keys: Vec<String>,
+    ) -> Result<(), io::Error>;
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error>;
+
+    /// Creates an empty translation file for `language`.
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error>;
+}
+
+/// Formats keyed by the tag stored with a location. Detection tries them in the order they
+/// were registered.
+pub struct FormatRegistry {
+    formats: Vec<(String, Box<dyn TranslationFormat>)>,
+}
+
+impl FormatRegistry {
+    pub fn new() -> Self {
+        Self {
+            formats: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, tag: &str, format: Box<dyn TranslationFormat>) {
+        match self.formats.iter_mut().find(|(known, _)| known == tag) {
+            Some((_, registered)) => *registered = format,
+            None => self.formats.push((tag.to_string(), format)),
+        }
+    }
+
+    pub fn get(&self, tag: &str) -> Option<&dyn TranslationFormat> {
+        self.formats
+            .iter()
+            .find(|(known, _)| known == tag)
+            .map(|(_, format)| format.as_ref())
+    }
+
+    /// Tag of the first format whose files are at `path`.
+    pub fn detect(&self, path: &Path) -> Option<&str> {
+        self.formats
+            .iter()
+            .find(|(_, format)| format.is_location(path))
+            .map(|(tag, _)| tag.as_str())
+    }
+
+    /// Format of a location, failing for tags nothing is registered for.
+    pub fn for_location(
+        &self,
+        location: &location::Data,
+    ) -> Result<&dyn TranslationFormat, io::Error> {
+        self.get(&location.tag).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "no translation format registered for location tag `{}`",
+                    location.tag
+                ),
+            )
+        })
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register("FE", Box::new(ReactFormat));
+        registry.register("BE", Box::new(ResxFormat));
+        registry.register(MobilePlatform::Android.tag(), Box::new(AndroidFormat));
+        registry.register(MobilePlatform::Apple.tag(), Box::new(AppleFormat));
+        registry.register(crate::yaml::TAG, Box::new(YamlFormat));
+        registry.register(crate::fluent::TAG, Box::new(FluentFormat));
+        registry.register(crate::arb::TAG, Box::new(ArbFormat));
+        registry.register(crate::properties::TAG, Box::new(PropertiesFormat));
+        registry
+    }
+}
+
+fn other_error(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
@@ -0,0 +1,106 @@
+use crate::checker::MessageSyntax;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::properties;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use std::path::Path;
+
+/// A directory of Java `ResourceBundle` files, `<base>.properties` for the default language and
+/// `<base>_<language>.properties` for the others.
+pub struct PropertiesFormat;
+
+#[async_trait]
+impl TranslationFormat for PropertiesFormat {
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        Ok(properties::source_language(
+            Path::new(&location.path),
+            &settings.default_language,
+        ))
+    }
+
+    /// `MessageFormat` arguments such as `{0}` are numbered ICU arguments.
+    fn is_location(&self, path: &Path) -> bool {
+        properties::is_location(path)
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Icu
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        properties::get_translations(Path::new(&location.path))
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let path = Path::new(&location.path);
+        if properties::contains_key(path, &key.ts_key)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already has the key {}", location.path, key.ts_key),
+            ));
+        }
+        let language = self.source_language(location, settings)?;
+        properties::set_value(path, &language, &key.ts_key, &key.value)?;
+        properties::get_translations(path)
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        let path = Path::new(&location.path);
+        for (language, value) in &key.translation_values {
+            properties::set_value(path, language, &key.ts_key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let path = Path::new(&location.path);
+        for value in &values {
+            properties::set_value(path, language, &value.ts_key, &value.value)?;
+        }
+        Ok(())
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        properties::remove_keys(Path::new(&location.path), &ts_keys)
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        Ok(properties::get_languages(Path::new(&location.path)))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        properties::add_language(Path::new(&location.path), language)
+    }
+}
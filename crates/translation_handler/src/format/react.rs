@@ -0,0 +1,116 @@
+use crate::checker::MessageSyntax;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::json::{set_value, LocaleFile};
+use crate::frontend::layout::FrontendLayout;
+use crate::frontend::setter::add_frontend_language;
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::TranslationHandler;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use serde_json::Value;
+use std::io;
+
+/// A React location: keys in a `messages.ts` file, values in one JSON file per language.
+pub struct ReactFormat;
+
+#[async_trait]
+impl TranslationFormat for ReactFormat {
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        Ok(layout
+            .source_language(&settings.default_language)
+            .to_string())
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Icu
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        TranslationHandler::get_frontend_translations(&location.path, &layout).await
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        TranslationHandler::add_new_frontend_key(
+            location.path.clone(),
+            key.ts_key,
+            key.json_key,
+            key.value,
+            key.plural_values,
+            settings.clone(),
+            &layout,
+        )
+        .await
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        TranslationHandler::update_keys(location.path.clone(), key, settings.clone(), &layout).await
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        let file = layout.locale_file(&location.path, language);
+        let mut locale_file = LocaleFile::read(&file)?;
+        for value in values {
+            set_value(
+                &mut locale_file.data,
+                &value.json_key,
+                Value::String(value.value),
+                layout.json_layout,
+            )?;
+        }
+        locale_file.write(&file)
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        TranslationHandler::remove_frontend_key(location.path.clone(), ts_keys, json_keys, &layout)
+            .await
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        Ok(TranslationHandler::extract_language_codes_from_locales(
+            location.path.clone(),
+            &layout,
+        ))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        let layout = FrontendLayout::from_location(location)?;
+        add_frontend_language(&location.path, language, &layout)
+    }
+}
@@ -0,0 +1,102 @@
+use crate::backend::getter::get_backend_languages;
+use crate::backend::setter::add_backend_language;
+use crate::checker::MessageSyntax;
+use crate::format::{other_error, KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::TranslationHandler;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use tracing::info;
+
+/// A .NET location: a neutral resx file with one satellite resx file per culture next to it.
+pub struct ResxFormat;
+
+#[async_trait]
+impl TranslationFormat for ResxFormat {
+    /// The neutral resx file is read as the `default` language.
+    fn source_language(
+        &self,
+        _location: &location::Data,
+        _settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        Ok("default".to_string())
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::DotNet
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        TranslationHandler::get_backend_translations(&location.path).await
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        TranslationHandler::add_new_backend_key(
+            location.path.clone(),
+            key.ts_key,
+            key.value,
+            key.description,
+            key.translations,
+            settings.clone(),
+        )
+        .await
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        TranslationHandler::update_backend_key(location.path.clone(), key, settings.clone())
+            .await
+            .map_err(other_error)
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let values: Vec<(String, String)> = values
+            .into_iter()
+            .map(|value| (value.ts_key, value.value))
+            .collect();
+        TranslationHandler::set_backend_values(&location.path, language, &values)
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        let summary =
+            TranslationHandler::remove_backend_key(location.path.clone(), ts_keys).await?;
+        for removed in summary {
+            info!("Removed {:?} from {}", removed.keys, removed.file.display());
+        }
+        Ok(())
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        Ok(get_backend_languages(&location.path))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        add_backend_language(&location.path, language).map(|_| ())
+    }
+}
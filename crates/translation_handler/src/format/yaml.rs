@@ -0,0 +1,106 @@
+use crate::checker::MessageSyntax;
+use crate::format::{KeyValue, NewKey, TranslationFormat};
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::yaml;
+use async_trait::async_trait;
+use db::prisma::{location, settings};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::io;
+use std::path::Path;
+
+/// A directory of `config/locales/<language>.yml` files. Nested scopes are keys joined with
+/// dots, such as `activerecord.errors.blank`.
+pub struct YamlFormat;
+
+#[async_trait]
+impl TranslationFormat for YamlFormat {
+    fn source_language(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+    ) -> Result<String, io::Error> {
+        Ok(yaml::source_language(
+            Path::new(&location.path),
+            &settings.default_language,
+        ))
+    }
+
+    /// Rails interpolates `%{name}`, which the ICU parser reads as the argument `name`.
+    fn is_location(&self, path: &Path) -> bool {
+        yaml::is_location(path)
+    }
+
+    fn message_syntax(&self) -> MessageSyntax {
+        MessageSyntax::Icu
+    }
+
+    async fn list(&self, location: &location::Data) -> Result<Vec<TranslationEntry>, io::Error> {
+        yaml::get_translations(Path::new(&location.path))
+    }
+
+    async fn add(
+        &self,
+        location: &location::Data,
+        settings: &settings::Data,
+        key: NewKey,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let path = Path::new(&location.path);
+        if yaml::contains_key(path, &key.ts_key)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already has the key {}", location.path, key.ts_key),
+            ));
+        }
+        let language = self.source_language(location, settings)?;
+        yaml::set_value(path, &language, &key.ts_key, &key.value)?;
+        yaml::get_translations(path)
+    }
+
+    async fn update(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        key: UpdatedKeyValues,
+    ) -> Result<(), io::Error> {
+        let path = Path::new(&location.path);
+        for (language, value) in &key.translation_values {
+            yaml::set_value(path, language, &key.ts_key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn set_values(
+        &self,
+        location: &location::Data,
+        _settings: &settings::Data,
+        language: &str,
+        values: Vec<KeyValue>,
+    ) -> Result<(), io::Error> {
+        let path = Path::new(&location.path);
+        for value in &values {
+            yaml::set_value(path, language, &value.ts_key, &value.value)?;
+        }
+        Ok(())
+    }
+
+    async fn remove(
+        &self,
+        location: &location::Data,
+        ts_keys: Vec<String>,
+        _json_keys: Vec<String>,
+    ) -> Result<(), io::Error> {
+        yaml::remove_keys(Path::new(&location.path), &ts_keys)
+    }
+
+    async fn languages(&self, location: &location::Data) -> Result<Vec<String>, io::Error> {
+        Ok(yaml::get_languages(Path::new(&location.path)))
+    }
+
+    async fn add_language(
+        &self,
+        location: &location::Data,
+        language: &str,
+    ) -> Result<(), io::Error> {
+        yaml::add_language(Path::new(&location.path), language)
+    }
+}
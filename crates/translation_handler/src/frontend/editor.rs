@@ -0,0 +1,528 @@
+use crate::frontend::parser::{
+    is_ident_part, is_ident_start, parse_define_locales, MessageEntry, MessagesObject, QuoteStyle,
+    Span,
+};
+use std::io;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertPosition {
+    /// Before the first entry whose key sorts after the new key.
+    Sorted,
+    /// After the last entry.
+    End,
+}
+
+/// Formatting conventions detected from the existing file, so new entries look like their
+/// neighbours.
+struct Style {
+    indent: String,
+    newline: &'static str,
+    quote: QuoteStyle,
+    quote_keys: bool,
+    trailing_comma: bool,
+}
+
+impl Style {
+    fn format_key(&self, key: &str) -> String {
+        if !self.quote_keys && is_identifier(key) {
+            key.to_string()
+        } else {
+            let quote = match self.quote {
+                QuoteStyle::Double => QuoteStyle::Double,
+                _ => QuoteStyle::Single,
+            };
+            quote_string(key, quote)
+        }
+    }
+
+    fn format_entry(&self, key: &str, value: &str) -> String {
+        format!(
+            "{}: {}",
+            self.format_key(key),
+            quote_string(value, self.quote)
+        )
+    }
+}
+
+/// Edits the `defineLocales` object of a messages.ts file in place. Only the bytes of the
+/// touched entries change, everything else in the file is kept as it was.
+pub struct MessagesEditor {
+    source: String,
+    object: MessagesObject,
+}
+
+impl MessagesEditor {
+    pub fn new(source: String) -> Result<Self, io::Error> {
+        let object = parse_define_locales(&source)?;
+        Ok(Self { source, object })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn into_source(self) -> String {
+        self.source
+    }
+
+    pub fn entries(&self) -> &[MessageEntry] {
+        &self.object.entries
+    }
+
+    pub fn is_sorted(&self) -> bool {
+        self.object
+            .entries
+            .windows(2)
+            .all(|pair| pair[0].key <= pair[1].key)
+    }
+
+    pub fn insert(
+        &mut self,
+        key: &str,
+        value: &str,
+        position: InsertPosition,
+    ) -> Result<(), io::Error> {
+        if self.object.get(key).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("key `{}` already exists in messages.ts", key),
+            ));
+        }
+        let style = self.style();
+        let entry = style.format_entry(key, value);
+
+        let next = match position {
+            InsertPosition::Sorted => self
+                .object
+                .entries
+                .iter()
+                .find(|existing| existing.key.as_str() > key),
+            InsertPosition::End => None,
+        };
+        let edits = match next {
+            Some(next) => self.insert_before(next, &entry, &style),
+            None => self.insert_at_end(&entry, &style),
+        };
+        self.apply(edits)
+    }
+
+    /// Removes the given keys and returns the ones that were found.
+    pub fn remove(&mut self, keys: &[String]) -> Result<Vec<String>, io::Error> {
+        let entries = &self.object.entries;
+        let mut edits: Vec<(Range<usize>, String)> = entries
+            .iter()
+            .filter(|entry| keys.contains(&entry.key))
+            .map(|entry| (removal_range(&self.source, entry), String::new()))
+            .collect();
+        let removed: Vec<String> = entries
+            .iter()
+            .filter(|entry| keys.contains(&entry.key))
+            .map(|entry| entry.key.clone())
+            .collect();
+
+        // Without a trailing comma on the old last property, the new last property loses its
+        // comma too.
+        let removed_spans: Vec<Span> = entries
+            .iter()
+            .filter(|entry| keys.contains(&entry.key))
+            .map(|entry| entry.span)
+            .collect();
+        let properties = self.object.properties();
+        if let Some((last, None)) = properties.last() {
+            if removed_spans.contains(last) {
+                if let Some(comma) = properties
+                    .iter()
+                    .rev()
+                    .find(|(span, _)| !removed_spans.contains(span))
+                    .and_then(|(_, comma)| *comma)
+                {
+                    edits.push((comma..comma + 1, String::new()));
+                }
+            }
+        }
+
+        self.apply(edits)?;
+        Ok(removed)
+    }
+
+    pub fn rename(&mut self, old_key: &str, new_key: &str) -> Result<(), io::Error> {
+        if old_key == new_key {
+            return Ok(());
+        }
+        if self.object.get(new_key).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("key `{}` already exists in messages.ts", new_key),
+            ));
+        }
+        let entry = self.find(old_key)?;
+        let old_text = entry.key_span.as_str(&self.source);
+        let key = match old_text.chars().next() {
+            Some('"') => quote_string(new_key, QuoteStyle::Double),
+            Some('\'') => quote_string(new_key, QuoteStyle::Single),
+            _ => self.style().format_key(new_key),
+        };
+        let span = entry.key_span;
+        self.apply(vec![(span.start..span.end, key)])
+    }
+
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<(), io::Error> {
+        let entry = self.find(key)?;
+        let span = entry.value_span;
+        let value = quote_string(value, entry.quote);
+        self.apply(vec![(span.start..span.end, value)])
+    }
+
+    fn find(&self, key: &str) -> Result<&MessageEntry, io::Error> {
+        self.object.get(key).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("key `{}` does not exist in messages.ts", key),
+            )
+        })
+    }
+
+    fn insert_before(
+        &self,
+        next: &MessageEntry,
+        entry: &str,
+        style: &Style,
+    ) -> Vec<(Range<usize>, String)> {
+        let next_line = line_start(&self.source, next.span.start);
+        let leading = &self.source[next_line..next.span.start];
+        if leading.trim().is_empty() {
+            // Keep comments directly above the next entry attached to it.
+            let position = self.comment_block_start(next_line);
+            let text = format!("{}{},{}", leading, entry, style.newline);
+            vec![(position..position, text)]
+        } else {
+            vec![(next.span.start..next.span.start, format!("{}, ", entry))]
+        }
+    }
+
+    fn insert_at_end(&self, entry: &str, style: &Style) -> Vec<(Range<usize>, String)> {
+        let open = self.object.span.start;
+        let close = self.object.span.end - 1;
+        let comma = if style.trailing_comma { "," } else { "" };
+
+        let Some((last_span, last_comma)) = self.object.properties().last().copied() else {
+            let base_indent = leading_whitespace(&self.source, open);
+            return if self.source[open..close].contains('\n') {
+                let position = line_start(&self.source, close);
+                let text = format!("{}{}{}{}", style.indent, entry, comma, style.newline);
+                vec![(position..position, text)]
+            } else {
+                let text = format!(
+                    "{}{}{}{}{}",
+                    style.newline, style.indent, entry, comma, style.newline
+                ) + base_indent;
+                vec![(open + 1..close, text)]
+            };
+        };
+
+        let after = last_comma.map_or(last_span.end, |comma| comma + 1);
+        let last_line = line_start(&self.source, last_span.start);
+        let after_line = line_end(&self.source, after);
+        let owns_line = self.source[last_line..last_span.start].trim().is_empty();
+
+        if owns_line && after_line <= close {
+            let indent = &self.source[last_line..last_span.start];
+            let mut edits = vec![(
+                after_line..after_line,
+                format!("{}{}{}{}", indent, entry, comma, style.newline),
+            )];
+            if last_comma.is_none() {
+                edits.push((last_span.end..last_span.end, ",".to_string()));
+            }
+            edits
+        } else if last_comma.is_some() {
+            vec![(after..after, format!(" {}{}", entry, comma))]
+        } else {
+            vec![(last_span.end..last_span.end, format!(", {}", entry))]
+        }
+    }
+
+    /// Walks up from `line` over `//` and `/* */` comment lines.
+    fn comment_block_start(&self, line: usize) -> usize {
+        let floor = line_end(&self.source, self.object.span.start);
+        let mut start = line;
+        while start > floor {
+            let previous = line_start(&self.source, start - 1);
+            let text = self.source[previous..start].trim();
+            let is_comment = text.starts_with("//")
+                || text.starts_with("/*")
+                || text.starts_with('*')
+                || text.ends_with("*/");
+            if !is_comment {
+                break;
+            }
+            start = previous;
+        }
+        start
+    }
+
+    fn style(&self) -> Style {
+        let entries = &self.object.entries;
+        let newline = if self.source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let indent = entries
+            .iter()
+            .map(|entry| {
+                let start = line_start(&self.source, entry.span.start);
+                &self.source[start..entry.span.start]
+            })
+            .find(|leading| !leading.is_empty() && leading.trim().is_empty())
+            .map(|leading| leading.to_string())
+            .unwrap_or_else(|| {
+                let base = leading_whitespace(&self.source, self.object.span.start);
+                format!("{}{}", base, indent_unit(&self.source))
+            });
+
+        let count = |style: QuoteStyle| entries.iter().filter(|e| e.quote == style).count();
+        let quote = [QuoteStyle::Single, QuoteStyle::Double, QuoteStyle::Backtick]
+            .into_iter()
+            .max_by_key(|style| (count(*style), *style == QuoteStyle::Single))
+            .filter(|style| count(*style) > 0)
+            .unwrap_or(QuoteStyle::Single);
+
+        let quote_keys = !entries.is_empty()
+            && entries.iter().all(|entry| {
+                let key = entry.key_span.as_str(&self.source);
+                key.starts_with('\'') || key.starts_with('"')
+            });
+        let trailing_comma = self
+            .object
+            .properties()
+            .last()
+            .map_or(true, |(_, comma)| comma.is_some());
+
+        Style {
+            indent,
+            newline,
+            quote,
+            quote_keys,
+            trailing_comma,
+        }
+    }
+
+    fn apply(&mut self, mut edits: Vec<(Range<usize>, String)>) -> Result<(), io::Error> {
+        edits.sort_by(|a, b| b.0.start.cmp(&a.0.start));
+        for (range, text) in edits {
+            self.source.replace_range(range, &text);
+        }
+        self.object = parse_define_locales(&self.source)?;
+        Ok(())
+    }
+}
+
+/// Whole lines are removed when the entry is the only thing on them, so no blank line is
+/// left behind. Otherwise only the entry, its comma and the following spaces are cut.
+fn removal_range(source: &str, entry: &MessageEntry) -> Range<usize> {
+    let end = entry.comma.map_or(entry.span.end, |comma| comma + 1);
+    let start_of_line = line_start(source, entry.span.start);
+    let end_of_line = line_end(source, end);
+    let rest_of_line = source[end..end_of_line].trim();
+
+    if source[start_of_line..entry.span.start].trim().is_empty()
+        && (rest_of_line.is_empty() || rest_of_line.starts_with("//"))
+    {
+        start_of_line..end_of_line
+    } else {
+        let trailing_spaces = source[end..]
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .count();
+        entry.span.start..end + trailing_spaces
+    }
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
+/// Offset just past the newline ending the line that contains `offset`.
+fn line_end(source: &str, offset: usize) -> usize {
+    source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index + 1)
+}
+
+fn leading_whitespace(source: &str, offset: usize) -> &str {
+    let start = line_start(source, offset);
+    let line = &source[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+fn indent_unit(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|leading| !leading.is_empty())
+        .map(|leading| {
+            if leading.starts_with('\t') {
+                "\t".to_string()
+            } else {
+                leading.to_string()
+            }
+        })
+        .unwrap_or_else(|| "    ".to_string())
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().map_or(false, is_ident_start) && chars.all(is_ident_part)
+}
+
+fn quote_string(value: &str, quote: QuoteStyle) -> String {
+    let delimiter = quote.as_char();
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push(delimiter);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '$' if quote == QuoteStyle::Backtick && chars.peek() == Some(&'{') => {
+                quoted.push_str("\\$")
+            }
+            c if c == delimiter => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push(delimiter);
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(source: &str, action: impl FnOnce(&mut MessagesEditor)) -> String {
+        let mut editor = MessagesEditor::new(source.to_string()).unwrap();
+        action(&mut editor);
+        editor.into_source()
+    }
+
+    #[test]
+    fn edits_keep_commas_around_skipped_properties() {
+        let source =
+            "export default defineLocales({\n  a: 'a',\n  locales: {\n    en: 'English'\n  }\n});";
+        let result = edit(source, |editor| {
+            editor.insert("b", "b", InsertPosition::End).unwrap()
+        });
+        assert_eq!(
+            "export default defineLocales({\n  a: 'a',\n  locales: {\n    en: 'English'\n  },\n  b: 'b'\n});",
+            result
+        );
+        let source = "export default defineLocales({\n  locales: { en: 'English' },\n  a: 'a'\n});";
+        let result = edit(source, |editor| {
+            editor.remove(&["a".to_string()]).unwrap();
+        });
+        assert_eq!(
+            "export default defineLocales({\n  locales: { en: 'English' }\n});",
+            result
+        );
+    }
+
+    #[test]
+    fn insert_sorted_keeps_indentation_quotes_and_comments() {
+        let source = "export default defineLocales({\r\n\talpha: \"home.alpha\",\r\n\t// about gamma\r\n\tgamma: \"home.gamma\",\r\n}, locales);\r\n";
+        let result = edit(source, |editor| {
+            editor
+                .insert("beta", "home.beta", InsertPosition::Sorted)
+                .unwrap()
+        });
+        assert_eq!(
+            "export default defineLocales({\r\n\talpha: \"home.alpha\",\r\n\tbeta: \"home.beta\",\r\n\t// about gamma\r\n\tgamma: \"home.gamma\",\r\n}, locales);\r\n",
+            result
+        );
+    }
+
+    #[test]
+    fn insert_at_end_follows_trailing_comma_style() {
+        let source = "export default defineLocales({\n  a: 'a',\n  b: 'b'\n}, locales);\n";
+        let result = edit(source, |editor| {
+            editor.insert("c-d", "c.d", InsertPosition::End).unwrap()
+        });
+        assert_eq!(
+            "export default defineLocales({\n  a: 'a',\n  b: 'b',\n  'c-d': 'c.d'\n}, locales);\n",
+            result
+        );
+    }
+
+    #[test]
+    fn insert_into_empty_and_inline_objects() {
+        let result = edit("export default defineLocales({}, locales);", |editor| {
+            editor.insert("a", "it's", InsertPosition::End).unwrap()
+        });
+        assert_eq!(
+            "export default defineLocales({\n    a: 'it\\'s',\n}, locales);",
+            result
+        );
+
+        let result = edit(
+            "export default defineLocales({ a: 'a', c: 'c' }, locales);",
+            |editor| editor.insert("b", "b", InsertPosition::Sorted).unwrap(),
+        );
+        assert_eq!(
+            "export default defineLocales({ a: 'a', b: 'b', c: 'c' }, locales);",
+            result
+        );
+
+        let result = edit(
+            "export default defineLocales({ a: 'a' }, locales);",
+            |editor| editor.insert("b", "b", InsertPosition::End).unwrap(),
+        );
+        assert_eq!(
+            "export default defineLocales({ a: 'a', b: 'b' }, locales);",
+            result
+        );
+    }
+
+    #[test]
+    fn insert_rejects_duplicates() {
+        let mut editor =
+            MessagesEditor::new("export default defineLocales({ a: 'a' }, locales);".to_string())
+                .unwrap();
+        let error = editor.insert("a", "b", InsertPosition::End).unwrap_err();
+        assert_eq!(io::ErrorKind::AlreadyExists, error.kind());
+    }
+
+    #[test]
+    fn remove_last_entry_keeps_missing_trailing_comma() {
+        let source =
+            "export default defineLocales({\n  a: 'a',\n  b: 'b', // note\n  c: 'c'\n}, locales);";
+        let mut editor = MessagesEditor::new(source.to_string()).unwrap();
+        let removed = editor
+            .remove(&["b".to_string(), "c".to_string(), "x".to_string()])
+            .unwrap();
+        assert_eq!(vec!["b".to_string(), "c".to_string()], removed);
+        assert_eq!(
+            "export default defineLocales({\n  a: 'a'\n}, locales);",
+            editor.source()
+        );
+    }
+
+    #[test]
+    fn rename_and_set_value_only_touch_their_spans() {
+        let source = "export default defineLocales({\n  'old-key': \"a.b\", // comment\n  other: 'x',\n}, locales);";
+        let result = edit(source, |editor| {
+            editor.rename("old-key", "newKey").unwrap();
+            editor.set_value("other", "y\"z").unwrap();
+        });
+        assert_eq!(
+            "export default defineLocales({\n  'newKey': \"a.b\", // comment\n  other: 'y\"z',\n}, locales);",
+            result
+        );
+    }
+}
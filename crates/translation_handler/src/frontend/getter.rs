@@ -1,183 +1,5 @@
-use crate::{frontend::PathType, TranslationHandler};
-use glob::glob;
-use local_storage::stores::translation_store::TranslationEntry;
-use regex::Regex;
-use serde_json::{self, Value};
-use std::collections::HashMap;
-use std::fs::{self, read_to_string, OpenOptions};
-use std::io::{self, Read, Write};
-use tracing::{error, info};
-
-impl TranslationHandler {}
-
-pub fn remove_key_from_language_jsons(
-    locales_path: String,
-    keys: Vec<String>,
-) -> Result<(), std::io::Error> {
-    info!(target: "remover", "Removing keys from language JSON files in {}", locales_path);
-    info!(target: "remover", "Requested keys to remove: {:?}", keys);
-    let json_files =
-        glob(format!("{}/*.json", locales_path).as_str()).expect("Failed to read glob pattern");
-
-    for entry in json_files {
-        match entry {
-            Ok(path) => {
-                let file_name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("unknown");
-                info!(target: "remover", "Processing file: {}", path.display());
-
-                let file_content = fs::read_to_string(&path)?;
-
-                // Detect original indentation
-                let indent = file_content
-                    .lines()
-                    .find(|line| line.trim().starts_with('"'))
-                    .map(|line| {
-                        let leading = line
-                            .chars()
-                            .take_while(|c| c.is_whitespace())
-                            .collect::<String>();
-                        if leading.contains('\t') {
-                            "\t".to_string()
-                        } else {
-                            leading
-                        }
-                    })
-                    .unwrap_or("  ".to_string());
-                info!(target: "remover", "Detected indent for {}: {:?}", path.display(), indent);
-
-                // Parse JSON content
-                let mut json: HashMap<String, Value> = serde_json::from_str(&file_content)
-                    .map_err(|e| {
-                        error!(target: "remover", "Failed to parse JSON in {}: {}", path.display(), e);
-                        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid JSON: {}", e))
-                    })?;
-
-                // Log original content
-                info!(target: "remover", "Original content for {}: {:?}", path.display(), json);
-
-                // Remove specified keys
-                let original_len = json.len();
-                for key in &keys {
-                    json.remove(key);
-                }
-
-                // Log if keys were removed
-                let new_len = json.len();
-                if new_len < original_len {
-                    info!(target: "remover", "Removed keys from {}: {:?}", path.display(), keys);
-                } else {
-                    info!(target: "remover", "No matching keys found in {}", path.display());
-                }
-
-                // Serialize with original indentation
-                let mut new_content = String::new();
-                new_content.push_str("{\n");
-                let mut entries: Vec<_> = json.into_iter().collect();
-                entries.sort_by(|a, b| a.0.cmp(&b.0)); // Sort to maintain consistent order
-                for (i, (key, value)) in entries.iter().enumerate() {
-                    let formatted_value = serde_json::to_string(value).map_err(|e| {
-                        io::Error::new(io::ErrorKind::Other, format!("Serialization error: {}", e))
-                    })?;
-                    new_content.push_str(&format!(
-                        "{}{}: {}",
-                        indent,
-                        serde_json::to_string(key).unwrap(),
-                        formatted_value
-                    ));
-                    if i < entries.len() - 1 {
-                        new_content.push_str(",");
-                    }
-                    new_content.push_str("\n");
-                }
-                new_content.push_str("}");
-
-                // Write back to file
-                let mut file = OpenOptions::new().write(true).truncate(true).open(&path)?;
-                file.write_all(new_content.as_bytes())?;
-                file.flush()?;
-                info!(target: "remover", "Successfully updated: {}", path.display());
-            }
-            Err(e) => {
-                error!(target: "remover", "Error reading file: {:?}", e);
-            }
-        }
-    }
-    Ok(())
-}
-
-pub fn remove_key_from_messages_ts(path: String, keys: Vec<String>) -> Result<(), std::io::Error> {
-    info!(target: "remover", "Removing keys from message.ts at {}", path);
-    let mut file = OpenOptions::new().read(true).open(path.clone())?;
-
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-
-    let re = Regex::new(r"(?s)\s*export\s+default\s+defineLocales\s*\(").unwrap();
-    let json_start = re.find(&content).map(|m| m.end()).ok_or_else(|| {
-        info!(target: "remover", "Did not find 'export default defineLocales(' in {}", path);
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            "Could not find 'export default defineLocales(' pattern",
-        )
-    })?;
-
-    let json_end = content[json_start..]
-        .find("locales")
-        .map(|i| json_start + i)
-        .ok_or_else(|| {
-            info!(target: "remover", "Did not find 'locales' in {}", path);
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Could not find 'locales' in file",
-            )
-        })?;
-
-    let key_value_pairs = &content[json_start..json_end];
-    let mut new_entries = String::new();
-
-    let key_value_regex = Regex::new(r#"\s*(\w+)\s*:\s*(?:"([^"]*)"|'([^']*)')\s*,?\s*"#).unwrap();
-    let newline = if std::env::consts::OS == "windows" {
-        "\r\n"
-    } else {
-        "\n"
-    };
-
-    key_value_pairs
-        .lines()
-        .for_each(|line| match key_value_regex.captures(line) {
-            None => new_entries.push_str(&format!("{}{}", line, newline)),
-            Some(capture) => {
-                let key = capture.get(1).unwrap().as_str();
-                if !keys.iter().any(|k| k == key) {
-                    new_entries.push_str(&format!("{}{}", line, newline));
-                }
-            }
-        });
-
-    if new_entries.ends_with(newline) {
-        new_entries.pop();
-        if newline.len() > 1 {
-            new_entries.pop();
-        }
-    }
-
-    let new_file = format!(
-        "{}{}{}",
-        &content[..json_start],
-        new_entries,
-        &content[json_end..]
-    );
-
-    let mut file = OpenOptions::new().write(true).truncate(true).open(path)?;
-    file.write_all(new_file.as_bytes())?;
-    file.flush()?;
-
-    info!(target: "remover", "Successfully removed keys and updated file");
-    Ok(())
-}
+pub use crate::frontend::remover::remove_key_from_language_jsons;
+pub use crate::frontend::remover::remove_key_from_messages_ts;
 
 #[cfg(test)]
 mod tests {
@@ -341,6 +163,31 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_remove_key_from_language_jsons_nested_keys() {
+        let temp_dir = setup_test_dir();
+        let locales_path = temp_dir.path().to_string_lossy().to_string();
+
+        let json_content = r#"{
+  "home": {
+    "title": "Title",
+    "footer": {
+      "copyright": "Copyright"
+    }
+  },
+  "flat.key": "Flat"
+}"#;
+        create_json_file(temp_dir.path(), "en.json", json_content).unwrap();
+
+        let keys_to_remove = vec!["home.footer.copyright".to_string(), "flat.key".to_string()];
+        let result = remove_key_from_language_jsons(locales_path, keys_to_remove);
+
+        assert!(result.is_ok());
+
+        let content = read_file_content(&temp_dir.path().join("en.json"));
+        assert_eq!(content, "{\n  \"home\": {\n    \"title\": \"Title\"\n  }\n}");
+    }
+
     #[test]
     fn test_remove_key_from_messages_ts_basic() {
         let temp_dir = setup_test_dir();
@@ -491,7 +338,11 @@ export default defineLocales({
         let result =
             remove_key_from_messages_ts(file_path.to_string_lossy().to_string(), keys_to_remove);
 
-        assert!(result.is_err());
+        assert!(result.is_ok());
+        assert_eq!(
+            "export default defineLocales({\n});",
+            read_file_content(&file_path)
+        );
     }
 
     #[test]
@@ -526,6 +377,29 @@ export default defineLocales({
         assert!(content.contains("// Comment here"));
     }
 
+    #[test]
+    fn test_remove_key_from_messages_ts_multi_line_and_quoted_keys() {
+        let temp_dir = setup_test_dir();
+        let file_path = temp_dir.path().join("messages.ts");
+
+        let ts_content = "export default defineLocales({\r\n  'foo-bar': 'foo.bar', // keep me out\r\n  long:\r\n    'home.' +\r\n    'long',\r\n  short: `home.short`,\r\n}, locales);\r\n";
+
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(ts_content.as_bytes()).unwrap();
+
+        let keys_to_remove = vec!["foo-bar".to_string(), "long".to_string()];
+        let result =
+            remove_key_from_messages_ts(file_path.to_string_lossy().to_string(), keys_to_remove);
+
+        assert!(result.is_ok());
+
+        let content = read_file_content(&file_path);
+        assert_eq!(
+            "export default defineLocales({\r\n  short: `home.short`,\r\n}, locales);\r\n",
+            content
+        );
+    }
+
     #[test]
     fn test_remove_key_from_messages_ts_file_not_found() {
         let keys_to_remove = vec!["any_key".to_string()];
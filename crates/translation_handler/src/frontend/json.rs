@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How keys are laid out in a location's locale JSON files.
+///
+/// `Flat` files store `"home.title": "..."` at the top level, `Nested` files store
+/// `"home": { "title": "..." }`. Both layouts are read the same way, the layout only
+/// decides how new keys are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonLayout {
+    #[default]
+    Flat,
+    Nested,
+}
+
+impl JsonLayout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonLayout::Flat => "flat",
+            JsonLayout::Nested => "nested",
+        }
+    }
+}
+
+impl FromStr for JsonLayout {
+    type Err = io::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flat" => Ok(JsonLayout::Flat),
+            "nested" => Ok(JsonLayout::Nested),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown json layout `{}`", s),
+            )),
+        }
+    }
+}
+
+/// Looks a key up by its literal name first, then as a dotted path through nested objects.
+pub fn get_value<'a>(data: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    if let Some(value) = data.get(key) {
+        return Some(value);
+    }
+    let mut segments = key.split('.');
+    let mut current = data.get(segments.next()?)?;
+    for segment in segments {
+        current = current.as_object()?.get(segment)?;
+    }
+    Some(current)
+}
+
+/// Text of a translation value. Objects and arrays are namespaces, not translations.
+pub fn value_as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+/// Writes `value` at `key`. An existing entry is updated where it is, whichever layout it
+/// uses. New entries follow `layout`, creating the intermediate objects of a nested path.
+pub fn set_value(
+    data: &mut Map<String, Value>,
+    key: &str,
+    value: Value,
+    layout: JsonLayout,
+) -> Result<(), io::Error> {
+    if data.contains_key(key) || layout == JsonLayout::Flat {
+        data.insert(key.to_string(), value);
+        return Ok(());
+    }
+
+    let segments: Vec<&str> = key.split('.').collect();
+    let (leaf, parents) = segments.split_last().unwrap();
+    let mut current = data;
+    for (index, segment) in parents.iter().enumerate() {
+        let child = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        current = child.as_object_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "cannot write `{}`, `{}` is not an object",
+                    key,
+                    segments[..=index].join(".")
+                ),
+            )
+        })?;
+    }
+    current.insert(leaf.to_string(), value);
+    Ok(())
+}
+
+/// Removes a key by literal name or dotted path and prunes the objects it leaves empty.
+pub fn remove_value(data: &mut Map<String, Value>, key: &str) -> bool {
+    if data.remove(key).is_some() {
+        return true;
+    }
+    let segments: Vec<&str> = key.split('.').collect();
+    remove_path(data, &segments)
+}
+
+fn remove_path(data: &mut Map<String, Value>, segments: &[&str]) -> bool {
+    match segments {
+        [] => false,
+        [leaf] => data.remove(*leaf).is_some(),
+        [head, rest @ ..] => {
+            let Some(child) = data.get_mut(*head).and_then(Value::as_object_mut) else {
+                return false;
+            };
+            let removed = remove_path(child, rest);
+            if removed && child.is_empty() {
+                data.remove(*head);
+            }
+            removed
+        }
+    }
+}
+
+/// Guesses the layout of a location's locale files: nested as soon as one file nests objects.
+pub fn detect_layout(files: &[PathBuf]) -> JsonLayout {
+    let nested = files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str::<Map<String, Value>>(&content).ok())
+        .any(|data| data.values().any(Value::is_object));
+    if nested {
+        JsonLayout::Nested
+    } else {
+        JsonLayout::Flat
+    }
+}
+
+/// A parsed locale file together with the formatting needed to write it back unchanged.
+pub struct LocaleFile {
+    pub data: Map<String, Value>,
+    indent: String,
+    newline: &'static str,
+    final_newline: bool,
+}
+
+impl LocaleFile {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let data: Map<String, Value> = serde_json::from_str(content).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("Invalid JSON: {}", e))
+        })?;
+        let indent = content
+            .lines()
+            .find(|line| line.trim().starts_with('"'))
+            .map(|line| {
+                line.chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect::<String>()
+            })
+            .filter(|indent| !indent.is_empty())
+            .unwrap_or_else(|| "  ".to_string());
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        Ok(Self {
+            data,
+            indent,
+            newline,
+            final_newline: content.ends_with('\n'),
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl std::fmt::Display for LocaleFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut content = String::new();
+        // An empty file keeps its braces on separate lines
+        content.push('{');
+        content.push_str(self.newline);
+        for (index, (key, value)) in self.data.iter().enumerate() {
+            content.push_str(&self.indent);
+            write_entry(&mut content, key, value, 1, &self.indent, self.newline);
+            if index + 1 < self.data.len() {
+                content.push(',');
+            }
+            content.push_str(self.newline);
+        }
+        content.push('}');
+        if self.final_newline {
+            content.push_str(self.newline);
+        }
+        f.write_str(&content)
+    }
+}
+
+fn write_entry(
+    content: &mut String,
+    key: &str,
+    value: &Value,
+    depth: usize,
+    indent: &str,
+    newline: &str,
+) {
+    content.push_str(&Value::String(key.to_string()).to_string());
+    content.push_str(": ");
+    write_value(content, value, depth, indent, newline);
+}
+
+fn write_value(content: &mut String, value: &Value, depth: usize, indent: &str, newline: &str) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            content.push('{');
+            content.push_str(newline);
+            for (index, (key, child)) in map.iter().enumerate() {
+                content.push_str(&indent.repeat(depth + 1));
+                write_entry(content, key, child, depth + 1, indent, newline);
+                if index + 1 < map.len() {
+                    content.push(',');
+                }
+                content.push_str(newline);
+            }
+            content.push_str(&indent.repeat(depth));
+            content.push('}');
+        }
+        Value::Array(items) if !items.is_empty() => {
+            content.push('[');
+            content.push_str(newline);
+            for (index, item) in items.iter().enumerate() {
+                content.push_str(&indent.repeat(depth + 1));
+                write_value(content, item, depth + 1, indent, newline);
+                if index + 1 < items.len() {
+                    content.push(',');
+                }
+                content.push_str(newline);
+            }
+            content.push_str(&indent.repeat(depth));
+            content.push(']');
+        }
+        other => content.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn get_value_reads_literal_and_dotted_keys() {
+        let data = map(json!({
+            "home.flat": "Flat",
+            "home": { "title": "Title", "nested": { "deep": "Deep" } }
+        }));
+        assert_eq!(Some(&json!("Flat")), get_value(&data, "home.flat"));
+        assert_eq!(Some(&json!("Title")), get_value(&data, "home.title"));
+        assert_eq!(Some(&json!("Deep")), get_value(&data, "home.nested.deep"));
+        assert_eq!(None, get_value(&data, "home.missing"));
+        assert_eq!(None, get_value(&data, "home.title.more"));
+    }
+
+    #[test]
+    fn set_value_creates_intermediate_objects_for_nested_layout() {
+        let mut data = map(json!({ "home": { "title": "Title" } }));
+        set_value(&mut data, "home.sub.text", json!("Text"), JsonLayout::Nested).unwrap();
+        set_value(&mut data, "flat.key", json!("Flat"), JsonLayout::Flat).unwrap();
+        assert_eq!(
+            json!({
+                "home": { "title": "Title", "sub": { "text": "Text" } },
+                "flat.key": "Flat"
+            }),
+            Value::Object(data.clone())
+        );
+        assert!(set_value(&mut data, "home.title.x", json!("X"), JsonLayout::Nested).is_err());
+    }
+
+    #[test]
+    fn remove_value_prunes_empty_objects() {
+        let mut data = map(json!({
+            "home": { "sub": { "text": "Text" }, "title": "Title" },
+            "other": "Other"
+        }));
+        assert!(remove_value(&mut data, "home.sub.text"));
+        assert_eq!(
+            json!({ "home": { "title": "Title" }, "other": "Other" }),
+            Value::Object(data.clone())
+        );
+        assert!(remove_value(&mut data, "home.title"));
+        assert!(!remove_value(&mut data, "home.title"));
+        assert_eq!(json!({ "other": "Other" }), Value::Object(data));
+    }
+
+    #[test]
+    fn locale_file_round_trips_formatting_and_order() {
+        let content = "{\r\n\t\"b\": \"B\",\r\n\t\"a\": {\r\n\t\t\"c\": \"C\"\r\n\t},\r\n\t\"empty\": {}\r\n}\r\n";
+        let file = LocaleFile::parse(content).unwrap();
+        assert_eq!(content, file.to_string());
+    }
+}
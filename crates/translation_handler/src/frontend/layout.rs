@@ -0,0 +1,186 @@
+use crate::frontend::json::JsonLayout;
+use db::prisma::location;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const LANG_PLACEHOLDER: &str = "{lang}";
+
+/// Where a frontend location keeps its translation files.
+///
+/// Every path is relative to the location path. `locale_file_pattern` is relative to
+/// `locales_directory` and contains `{lang}` once, e.g. `{lang}.json` or `{lang}/common.json`.
+/// Without a `source_language` the location uses the default language from the settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct FrontendLayout {
+    pub messages_file: String,
+    pub locales_directory: String,
+    pub locale_file_pattern: String,
+    pub languages_file: String,
+    #[serde(default)]
+    pub source_language: Option<String>,
+    #[serde(default)]
+    pub json_layout: JsonLayout,
+}
+
+impl Default for FrontendLayout {
+    fn default() -> Self {
+        Self {
+            messages_file: "messages.ts".to_string(),
+            locales_directory: "locales".to_string(),
+            locale_file_pattern: "{lang}.json".to_string(),
+            languages_file: "locales/locales.ts".to_string(),
+            source_language: None,
+            json_layout: JsonLayout::Flat,
+        }
+    }
+}
+
+impl FrontendLayout {
+    pub fn from_location(location: &location::Data) -> Result<Self, io::Error> {
+        let layout = Self {
+            messages_file: location.messages_file.clone(),
+            locales_directory: location.locales_directory.clone(),
+            locale_file_pattern: location.locale_file_pattern.clone(),
+            languages_file: location.languages_file.clone(),
+            source_language: location.source_language.clone(),
+            json_layout: location.json_layout.parse()?,
+        };
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    pub fn validate(&self) -> Result<(), io::Error> {
+        let invalid = |message: String| Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        if self.locale_file_pattern.matches(LANG_PLACEHOLDER).count() != 1 {
+            return invalid(format!(
+                "locale file pattern `{}` must contain {} exactly once",
+                self.locale_file_pattern, LANG_PLACEHOLDER
+            ));
+        }
+        if !self.locale_file_pattern.ends_with(".json") {
+            return invalid(format!(
+                "locale file pattern `{}` must point to a .json file",
+                self.locale_file_pattern
+            ));
+        }
+        if self.messages_file.is_empty() {
+            return invalid("messages file must not be empty".to_string());
+        }
+        if self.source_language.as_deref() == Some("") {
+            return invalid("source language must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Language new strings are written in and translated from.
+    pub fn source_language<'a>(&'a self, default_language: &'a str) -> &'a str {
+        self.source_language.as_deref().unwrap_or(default_language)
+    }
+
+    /// Path of the locale file of `lang`, whether it exists or not.
+    pub fn locale_file(&self, path: &str, lang: &str) -> PathBuf {
+        join(
+            &join(Path::new(path), &self.locales_directory),
+            &self.locale_file_pattern.replace(LANG_PLACEHOLDER, lang),
+        )
+    }
+
+    /// Existing locale files matching the pattern, with the language each one belongs to.
+    pub fn locale_files(&self, path: &str) -> Vec<(String, PathBuf)> {
+        let locales_path = join(Path::new(path), &self.locales_directory);
+        let glob_pattern = format!(
+            "{}/{}",
+            glob::Pattern::escape(&locales_path.to_string_lossy()),
+            self.locale_file_pattern.replace(LANG_PLACEHOLDER, "*")
+        );
+        let (prefix, suffix) = self
+            .locale_file_pattern
+            .split_once(LANG_PLACEHOLDER)
+            .unwrap_or(("", ".json"));
+
+        let mut files: Vec<(String, PathBuf)> = glob::glob(&glob_pattern)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|file| {
+                let relative = file
+                    .strip_prefix(&locales_path)
+                    .ok()?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let lang = relative.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                if lang.is_empty() || lang.contains('/') {
+                    return None;
+                }
+                Some((lang.to_string(), file))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+/// Joins a `/` or `\` separated relative path with the separators of the current OS.
+pub(crate) fn join(base: &Path, relative: &str) -> PathBuf {
+    relative
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .fold(base.to_path_buf(), |path, segment| path.join(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn locale_files_match_the_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        for lang in ["en-GB", "de-DE"] {
+            let lang_dir = dir.path().join("public/locales").join(lang);
+            fs::create_dir_all(&lang_dir).unwrap();
+            fs::write(lang_dir.join("common.json"), "{}").unwrap();
+            fs::write(lang_dir.join("other.json"), "{}").unwrap();
+        }
+        let layout = FrontendLayout {
+            locales_directory: "public/locales".to_string(),
+            locale_file_pattern: "{lang}/common.json".to_string(),
+            ..Default::default()
+        };
+
+        let langs: Vec<String> = layout
+            .locale_files(&root)
+            .into_iter()
+            .map(|(lang, _)| lang)
+            .collect();
+        assert_eq!(vec!["de-DE".to_string(), "en-GB".to_string()], langs);
+        assert_eq!(
+            dir.path().join("public/locales/fr-FR/common.json"),
+            layout.locale_file(&root, "fr-FR")
+        );
+    }
+
+    #[test]
+    fn validate_rejects_patterns_without_a_single_language() {
+        let layout = FrontendLayout {
+            locale_file_pattern: "common.json".to_string(),
+            ..Default::default()
+        };
+        assert!(layout.validate().is_err());
+        assert!(FrontendLayout::default().validate().is_ok());
+    }
+
+    #[test]
+    fn source_language_falls_back_to_the_default_language() {
+        let layout = FrontendLayout::default();
+        assert_eq!("de-DE", layout.source_language("de-DE"));
+
+        let layout = FrontendLayout {
+            source_language: Some("en-US".to_string()),
+            ..Default::default()
+        };
+        assert_eq!("en-US", layout.source_language("de-DE"));
+    }
+}
@@ -1,75 +1,37 @@
+pub mod editor;
 pub mod getter;
+pub mod json;
+pub mod layout;
+pub mod parser;
 pub mod remover;
 pub mod setter;
 pub mod updater;
+pub mod usage;
 
-
+use crate::frontend::layout::{join, FrontendLayout};
+use std::path::Path;
 
 pub enum PathType {
     MessageTsFile,
     TranslationDirectory,
     TranslationExportFile,
-    EnGbFile,
+    LanguageFile(String),
 }
 impl PathType {
-    fn create_path(self, path: String) -> String {
-        match self {
-            PathType::MessageTsFile => match std::env::consts::OS {
-                "macos" => {
-                    format!("{}/messages.ts", path)
-                }
-                "windows" => {
-                    format!("{}\\messages.ts", path)
-                }
-                "linux" => {
-                    format!("{}/messages.ts", path)
-                }
-                _ => {
-                    format!("{}/messages.ts", path)
-                }
-            },
-            PathType::TranslationDirectory => match std::env::consts::OS {
-                "macos" => {
-                    format!("{}/locales", path)
-                }
-                "windows" => {
-                    format!("{}\\locales", path)
-                }
-                "linux" => {
-                    format!("{}/locales", path)
-                }
-                _ => {
-                    format!("{}/locales", path)
-                }
-            },
-            PathType::TranslationExportFile => match std::env::consts::OS {
-                "macos" => {
-                    format!("{}/locales/locales.ts", path)
-                }
-                "windows" => {
-                    format!("{}\\locales\\locales.ts", path)
-                }
-                "linux" => {
-                    format!("{}/locales/locales.ts", path)
-                }
-                _ => {
-                    format!("{}/locales/locales.ts", path)
-                }
-            },
-            PathType::EnGbFile => match std::env::consts::OS {
-                "macos" => {
-                    format!("{}/locales/en-GB.json", path)
-                }
-                "windows" => {
-                    format!("{}\\locales\\en-GB.json", path)
-                }
-                "linux" => {
-                    format!("{}/locales/en-GB.json", path)
-                }
-                _ => {
-                    format!("{}/locales/en-GB.json", path)
-                }
-            },
-        }
+    fn create_path(self, path: String, layout: &FrontendLayout) -> String {
+        let relative = match self {
+            PathType::MessageTsFile => &layout.messages_file,
+            PathType::TranslationDirectory => &layout.locales_directory,
+            PathType::TranslationExportFile => &layout.languages_file,
+            PathType::LanguageFile(lang) => {
+                return layout
+                    .locale_file(&path, &lang)
+                    .to_string_lossy()
+                    .to_string()
+            }
+        };
+        join(Path::new(&path), relative)
+            .to_string_lossy()
+            .to_string()
     }
 }
@@ -0,0 +1,831 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+
+/// Byte range into the parsed source, `start` inclusive and `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn as_str<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    fn at(source: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map(|line| line.chars().count())
+            .unwrap_or(0)
+            + 1;
+        Self {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Single,
+    Double,
+    Backtick,
+}
+
+impl QuoteStyle {
+    pub fn as_char(&self) -> char {
+        match self {
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Double => '"',
+            QuoteStyle::Backtick => '`',
+        }
+    }
+}
+
+/// One `key: 'value'` property of the `defineLocales` object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageEntry {
+    pub key: String,
+    pub value: String,
+    pub key_span: Span,
+    pub value_span: Span,
+    /// From the start of the key to the end of the value, including any `as`/`satisfies` suffix.
+    pub span: Span,
+    /// Offset of the comma following the entry, if there is one.
+    pub comma: Option<usize>,
+    pub quote: QuoteStyle,
+}
+
+/// A property whose value isn't a string, such as a nested object. It holds no message, but
+/// the editor has to keep the commas around it right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedProperty {
+    pub key: String,
+    pub span: Span,
+    pub comma: Option<usize>,
+}
+
+enum Property {
+    Message(MessageEntry),
+    Skipped(SkippedProperty),
+}
+
+/// The object literal passed as first argument to `defineLocales`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessagesObject {
+    pub entries: Vec<MessageEntry>,
+    pub skipped: Vec<SkippedProperty>,
+    /// From the opening to the closing brace, both included.
+    pub span: Span,
+}
+
+impl MessagesObject {
+    pub fn get(&self, key: &str) -> Option<&MessageEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Span and comma of every property, messages and skipped ones, in source order.
+    pub fn properties(&self) -> Vec<(Span, Option<usize>)> {
+        let mut properties: Vec<(Span, Option<usize>)> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.span, entry.comma))
+            .chain(
+                self.skipped
+                    .iter()
+                    .map(|skipped| (skipped.span, skipped.comma)),
+            )
+            .collect();
+        properties.sort_by_key(|(span, _)| span.start);
+        properties
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String, QuoteStyle),
+    Template(String, bool),
+    Number,
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).map(|(_, c)| *c)
+    }
+
+    fn offset(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map(|(offset, _)| *offset)
+            .unwrap_or(self.source.len())
+    }
+
+    fn error(&self, offset: usize, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, offset, message)
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        while let Some(c) = self.peek(0) {
+            let start = self.offset();
+            if c.is_whitespace() {
+                self.pos += 1;
+            } else if c == '/' && self.peek(1) == Some('/') {
+                while self.peek(0).map_or(false, |c| c != '\n') {
+                    self.pos += 1;
+                }
+            } else if c == '/' && self.peek(1) == Some('*') {
+                self.pos += 2;
+                loop {
+                    match (self.peek(0), self.peek(1)) {
+                        (Some('*'), Some('/')) => {
+                            self.pos += 2;
+                            break;
+                        }
+                        (Some(_), _) => self.pos += 1,
+                        (None, _) => return Err(self.error(start, "unterminated block comment")),
+                    }
+                }
+            } else if c == '\'' || c == '"' {
+                let quote = if c == '\'' {
+                    QuoteStyle::Single
+                } else {
+                    QuoteStyle::Double
+                };
+                let value = self.read_string(c, start)?;
+                tokens.push(Token {
+                    kind: TokenKind::Str(value, quote),
+                    span: Span::new(start, self.offset()),
+                });
+            } else if c == '`' {
+                let (value, has_substitutions) = self.read_template(start)?;
+                tokens.push(Token {
+                    kind: TokenKind::Template(value, has_substitutions),
+                    span: Span::new(start, self.offset()),
+                });
+            } else if c.is_ascii_digit() {
+                while self
+                    .peek(0)
+                    .map_or(false, |c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+                {
+                    self.pos += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Number,
+                    span: Span::new(start, self.offset()),
+                });
+            } else if is_ident_start(c) {
+                let mut ident = String::new();
+                while let Some(c) = self.peek(0).filter(|c| is_ident_part(*c)) {
+                    ident.push(c);
+                    self.pos += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Ident(ident),
+                    span: Span::new(start, self.offset()),
+                });
+            } else {
+                self.pos += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Punct(c),
+                    span: Span::new(start, self.offset()),
+                });
+            }
+        }
+        Ok(tokens)
+    }
+
+    fn read_string(&mut self, quote: char, start: usize) -> Result<String, ParseError> {
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            match self.peek(0) {
+                None | Some('\n') => return Err(self.error(start, "unterminated string literal")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some('\\') => self.read_escape(&mut value)?,
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_template(&mut self, start: usize) -> Result<(String, bool), ParseError> {
+        self.pos += 1;
+        let mut value = String::new();
+        let mut has_substitutions = false;
+        loop {
+            match self.peek(0) {
+                None => return Err(self.error(start, "unterminated template literal")),
+                Some('`') => {
+                    self.pos += 1;
+                    return Ok((value, has_substitutions));
+                }
+                Some('\\') => self.read_escape(&mut value)?,
+                Some('$') if self.peek(1) == Some('{') => {
+                    has_substitutions = true;
+                    self.pos += 2;
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match self.peek(0) {
+                            None => return Err(self.error(start, "unterminated template literal")),
+                            Some('{') => depth += 1,
+                            Some('}') => depth -= 1,
+                            _ => {}
+                        }
+                        self.pos += 1;
+                    }
+                }
+                Some('\r') => {
+                    // Template literals normalise CRLF line terminators to LF.
+                    self.pos += 1;
+                    if self.peek(0) != Some('\n') {
+                        value.push('\n');
+                    }
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_escape(&mut self, value: &mut String) -> Result<(), ParseError> {
+        let start = self.offset();
+        self.pos += 1;
+        let Some(c) = self.peek(0) else {
+            return Err(self.error(start, "unterminated escape sequence"));
+        };
+        self.pos += 1;
+        match c {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            'r' => value.push('\r'),
+            'b' => value.push('\u{8}'),
+            'f' => value.push('\u{c}'),
+            'v' => value.push('\u{b}'),
+            '0' if !self.peek(0).map_or(false, |c| c.is_ascii_digit()) => value.push('\0'),
+            // Line continuation: the escaped line terminator is not part of the value.
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            '\r' => {
+                if self.peek(0) == Some('\n') {
+                    self.pos += 1;
+                }
+            }
+            'x' => {
+                let code = self.read_hex(2, start)?;
+                value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            'u' => {
+                let code = if self.peek(0) == Some('{') {
+                    self.pos += 1;
+                    let mut digits = String::new();
+                    while let Some(c) = self.peek(0).filter(|c| *c != '}') {
+                        digits.push(c);
+                        self.pos += 1;
+                    }
+                    if self.peek(0) != Some('}') {
+                        return Err(self.error(start, "invalid unicode escape sequence"));
+                    }
+                    self.pos += 1;
+                    u32::from_str_radix(&digits, 16)
+                        .map_err(|_| self.error(start, "invalid unicode escape sequence"))?
+                } else {
+                    let code = self.read_hex(4, start)?;
+                    // Join UTF-16 surrogate pairs written as two consecutive escapes.
+                    if (0xD800..0xDC00).contains(&code)
+                        && self.peek(0) == Some('\\')
+                        && self.peek(1) == Some('u')
+                    {
+                        let saved = self.pos;
+                        self.pos += 2;
+                        match self.read_hex(4, start) {
+                            Ok(low) if (0xDC00..0xE000).contains(&low) => {
+                                0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                            }
+                            _ => {
+                                self.pos = saved;
+                                code
+                            }
+                        }
+                    } else {
+                        code
+                    }
+                };
+                value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            other => value.push(other),
+        }
+        Ok(())
+    }
+
+    fn read_hex(&mut self, length: usize, start: usize) -> Result<u32, ParseError> {
+        let mut digits = String::new();
+        for _ in 0..length {
+            match self.peek(0).filter(|c| c.is_ascii_hexdigit()) {
+                Some(c) => {
+                    digits.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.error(start, "invalid hexadecimal escape sequence")),
+            }
+        }
+        u32::from_str_radix(&digits, 16)
+            .map_err(|_| self.error(start, "invalid hexadecimal escape sequence"))
+    }
+}
+
+pub(crate) fn is_ident_start(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphabetic()
+}
+
+pub(crate) fn is_ident_part(c: char) -> bool {
+    is_ident_start(c) || c.is_alphanumeric()
+}
+
+fn is_ident_at(tokens: &[Token], index: usize, name: &str) -> bool {
+    matches!(tokens.get(index), Some(Token { kind: TokenKind::Ident(ident), .. }) if ident == name)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Punct(p), .. }) if *p == c)
+    }
+
+    fn is_ident(&self, name: &str) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Ident(ident), .. }) if ident == name)
+    }
+
+    fn offset(&self) -> usize {
+        self.peek()
+            .map(|token| token.span.start)
+            .unwrap_or(self.source.len())
+    }
+
+    fn error(&self, offset: usize, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, offset, message)
+    }
+
+    fn expect_punct(&mut self, c: char, message: &str) -> Result<Span, ParseError> {
+        if self.is_punct(c) {
+            let span = self.tokens[self.pos].span;
+            self.pos += 1;
+            Ok(span)
+        } else {
+            Err(self.error(self.offset(), message))
+        }
+    }
+
+    /// Moves past `export default defineLocales(`, skipping an optional type argument list.
+    fn find_define_locales(&mut self) -> Result<(), ParseError> {
+        let start = (0..self.tokens.len()).find(|&index| {
+            is_ident_at(&self.tokens, index, "export")
+                && is_ident_at(&self.tokens, index + 1, "default")
+                && is_ident_at(&self.tokens, index + 2, "defineLocales")
+        });
+        let Some(start) = start else {
+            return Err(self.error(0, "could not find `export default defineLocales(`"));
+        };
+        self.pos = start + 3;
+        if self.is_punct('<') {
+            self.skip_balanced('<', '>');
+        }
+        self.expect_punct('(', "expected `(` after `defineLocales`")?;
+        Ok(())
+    }
+
+    fn skip_balanced(&mut self, open: char, close: char) {
+        let mut depth = 0;
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Punct(c) if c == open => depth += 1,
+                TokenKind::Punct(c) if c == close => depth -= 1,
+                _ => {}
+            }
+            self.pos += 1;
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+
+    /// Skips a trailing `as const` or `satisfies Type` clause and returns the end of it.
+    fn skip_type_suffix(&mut self) -> Option<usize> {
+        let mut end = None;
+        while self.is_ident("as") || self.is_ident("satisfies") {
+            self.pos += 1;
+            let mut depth = 0i32;
+            while let Some(token) = self.peek() {
+                match token.kind {
+                    TokenKind::Punct('(' | '[' | '{' | '<') => depth += 1,
+                    TokenKind::Punct(')' | ']' | '}' | '>') if depth > 0 => depth -= 1,
+                    TokenKind::Punct(',' | ')' | '}') if depth == 0 => break,
+                    TokenKind::Ident(ref ident)
+                        if depth == 0 && (ident == "as" || ident == "satisfies") =>
+                    {
+                        break
+                    }
+                    _ => {}
+                }
+                end = Some(token.span.end);
+                self.pos += 1;
+            }
+        }
+        end
+    }
+
+    fn parse_object(&mut self) -> Result<MessagesObject, ParseError> {
+        let open = self.expect_punct(
+            '{',
+            "expected an object literal as first argument of `defineLocales`",
+        )?;
+        let mut entries: Vec<MessageEntry> = Vec::new();
+        let mut skipped: Vec<SkippedProperty> = Vec::new();
+        let mut seen = HashSet::new();
+
+        loop {
+            if self.is_punct('}') {
+                break;
+            }
+            let key_start = self.offset();
+            let comma = match self.parse_entry()? {
+                Property::Message(entry) => {
+                    if !seen.insert(entry.key.clone()) {
+                        return Err(self.error(
+                            entry.key_span.start,
+                            format!("duplicate key `{}`", entry.key),
+                        ));
+                    }
+                    entries.push(entry);
+                    &mut entries.last_mut().unwrap().comma
+                }
+                Property::Skipped(property) => {
+                    if !seen.insert(property.key.clone()) {
+                        return Err(
+                            self.error(key_start, format!("duplicate key `{}`", property.key))
+                        );
+                    }
+                    skipped.push(property);
+                    &mut skipped.last_mut().unwrap().comma
+                }
+            };
+            if self.is_punct(',') {
+                *comma = Some(self.tokens[self.pos].span.start);
+                self.pos += 1;
+            } else if !self.is_punct('}') {
+                return Err(self.error(self.offset(), "expected `,` or `}` after property"));
+            }
+        }
+        let close = self.expect_punct('}', "unterminated object literal")?;
+
+        Ok(MessagesObject {
+            entries,
+            skipped,
+            span: Span::new(open.start, close.end),
+        })
+    }
+
+    /// Moves past a value that isn't a string, up to the `,` or `}` ending the property, and
+    /// returns the end of it.
+    fn skip_value(&mut self, key: &str) -> Result<usize, ParseError> {
+        let mut depth = 0;
+        let mut end = None;
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Punct('(' | '[' | '{') => depth += 1,
+                TokenKind::Punct(')' | ']' | '}') if depth > 0 => depth -= 1,
+                TokenKind::Punct(',' | ')' | '}') if depth == 0 => break,
+                _ => {}
+            }
+            end = Some(token.span.end);
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            return Err(self.error(self.source.len(), "unterminated object literal"));
+        }
+        end.ok_or_else(|| self.error(self.offset(), format!("expected a value for key `{}`", key)))
+    }
+
+    fn parse_entry(&mut self) -> Result<Property, ParseError> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.error(self.source.len(), "unterminated object literal"));
+        };
+        let key = match token.kind {
+            TokenKind::Ident(ident) => ident,
+            TokenKind::Str(value, _) => value,
+            TokenKind::Number => token.span.as_str(self.source).to_string(),
+            TokenKind::Punct('[') => {
+                return Err(self.error(token.span.start, "computed keys are not supported"))
+            }
+            TokenKind::Punct('.') => {
+                return Err(self.error(token.span.start, "spread properties are not supported"))
+            }
+            _ => return Err(self.error(token.span.start, "expected a property key")),
+        };
+        self.pos += 1;
+
+        if !self.is_punct(':') {
+            let message = if self.is_punct(',') || self.is_punct('}') {
+                format!("shorthand property `{}` is not supported", key)
+            } else {
+                format!("expected `:` after key `{}`", key)
+            };
+            return Err(self.error(self.offset(), message));
+        }
+        self.pos += 1;
+
+        let value_start = self.pos;
+        let Some((value, value_span, quote)) = self.parse_value(&key)? else {
+            self.pos = value_start;
+            let end = self.skip_value(&key)?;
+            return Ok(Property::Skipped(SkippedProperty {
+                key,
+                span: Span::new(token.span.start, end),
+                comma: None,
+            }));
+        };
+        let end = self.skip_type_suffix().unwrap_or(value_span.end);
+
+        Ok(Property::Message(MessageEntry {
+            key,
+            value,
+            key_span: token.span,
+            value_span,
+            span: Span::new(token.span.start, end),
+            comma: None,
+            quote,
+        }))
+    }
+
+    /// Reads a string literal, a template literal without substitutions or a `+`
+    /// concatenation of those. Returns `None` for any other value.
+    fn parse_value(&mut self, key: &str) -> Result<Option<(String, Span, QuoteStyle)>, ParseError> {
+        let mut value = String::new();
+        let mut span: Option<Span> = None;
+        let mut quote = QuoteStyle::Single;
+
+        loop {
+            let Some(token) = self.peek().cloned() else {
+                return Err(self.error(self.source.len(), "unterminated object literal"));
+            };
+            match token.kind {
+                TokenKind::Str(part, style) => {
+                    value.push_str(&part);
+                    if span.is_none() {
+                        quote = style;
+                    }
+                }
+                TokenKind::Template(part, false) => {
+                    value.push_str(&part);
+                    if span.is_none() {
+                        quote = QuoteStyle::Backtick;
+                    }
+                }
+                TokenKind::Template(_, true) => {
+                    return Err(self.error(
+                        token.span.start,
+                        format!(
+                            "template literal with substitutions is not supported for key `{}`",
+                            key
+                        ),
+                    ))
+                }
+                _ => return Ok(None),
+            }
+            span = Some(Span::new(
+                span.map_or(token.span.start, |span| span.start),
+                token.span.end,
+            ));
+            self.pos += 1;
+
+            if self.is_punct('+') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(Some((value, span.unwrap(), quote)))
+    }
+}
+
+/// Parses the object literal of `export default defineLocales({ ... }, locales)` in a
+/// messages.ts file. The locales argument is optional, and properties whose value isn't a
+/// string are skipped.
+pub fn parse_define_locales(source: &str) -> Result<MessagesObject, ParseError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        pos: 0,
+    };
+    parser.find_define_locales()?;
+    parser.parse_object()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> MessagesObject {
+        parse_define_locales(source).expect("failed to parse messages.ts")
+    }
+
+    #[test]
+    fn parses_simple_entries_with_spans() {
+        let source = "export default defineLocales({\n  hello: 'home.hello',\n  bye: \"home.bye\",\n}, locales);";
+        let object = parse(source);
+
+        assert_eq!(2, object.entries.len());
+        let hello = object.get("hello").unwrap();
+        assert_eq!("home.hello", hello.value);
+        assert_eq!("hello", hello.key_span.as_str(source));
+        assert_eq!("'home.hello'", hello.value_span.as_str(source));
+        assert_eq!(QuoteStyle::Single, hello.quote);
+        assert_eq!(QuoteStyle::Double, object.get("bye").unwrap().quote);
+        assert!(object.get("bye").unwrap().comma.is_some());
+        assert!(source[object.span.start..].starts_with('{'));
+        assert!(source[..object.span.end].ends_with('}'));
+    }
+
+    #[test]
+    fn parses_quoted_keys_templates_and_escapes() {
+        let source = r#"import { defineLocales } from '@/lib/i18n';
+import locales from './locales/locales';
+
+export default defineLocales({
+    'foo-bar': 'foo.bar', // trailing comment
+    "quoted": `template.value`,
+    /* block
+       comment */
+    escaped: 'it\'s \"fine\"!',
+    multiLine:
+        'home.' +
+        'title',
+    constValue: 'const.value' as const,
+}, locales) satisfies Messages;"#;
+        let object = parse(source);
+
+        let keys: Vec<&str> = object.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            vec!["foo-bar", "quoted", "escaped", "multiLine", "constValue"],
+            keys
+        );
+        assert_eq!("foo.bar", object.get("foo-bar").unwrap().value);
+        assert_eq!("template.value", object.get("quoted").unwrap().value);
+        assert_eq!(QuoteStyle::Backtick, object.get("quoted").unwrap().quote);
+        assert_eq!("it's \"fine\"!", object.get("escaped").unwrap().value);
+        assert_eq!("home.title", object.get("multiLine").unwrap().value);
+        let const_value = object.get("constValue").unwrap();
+        assert_eq!("const.value", const_value.value);
+        assert_eq!(
+            "constValue: 'const.value' as const",
+            const_value.span.as_str(source)
+        );
+    }
+
+    #[test]
+    fn parses_object_with_as_const_and_type_arguments() {
+        let source =
+            "export default defineLocales<Record<string, string>>({ a: 'a' } as const, locales);";
+        let object = parse(source);
+        assert_eq!("a", object.get("a").unwrap().value);
+    }
+
+    #[test]
+    fn reports_template_substitutions_with_position() {
+        let source = "export default defineLocales({\n  a: `x${y}`,\n}, locales);";
+        let error = parse_define_locales(source).unwrap_err();
+        assert_eq!(2, error.line);
+        assert_eq!(6, error.column);
+        assert!(error.message.contains("`a`"));
+    }
+
+    #[test]
+    fn reports_unsupported_entries() {
+        let cases = [
+            "export default defineLocales({ a }, locales);",
+            "export default defineLocales({ [a]: 'b' }, locales);",
+            "export default defineLocales({ ...other }, locales);",
+            "export default defineLocales({ a: 'b', a: 'c' }, locales);",
+            "export default defineLocales({ a: 'b' 'c' }, locales);",
+            "export default defineLocales({ a: 'b }, locales);",
+            "const messages = { a: 'b' };",
+        ];
+        for source in cases {
+            assert!(
+                parse_define_locales(source).is_err(),
+                "expected an error for {}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn skips_values_that_are_not_strings() {
+        let source = "export default defineLocales({\n  a: 'a.a',\n  locales: {\n    en: 'English',\n  },\n  count: 1 + other('x', 'y'),\n  b: 'b.' + suffix,\n  c: `c.c`\n});";
+        let object = parse(source);
+        let keys: Vec<&str> = object.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(vec!["a", "c"], keys);
+        let skipped: Vec<&str> = object
+            .skipped
+            .iter()
+            .map(|p| p.span.as_str(source))
+            .collect();
+        assert_eq!(
+            vec![
+                "locales: {\n    en: 'English',\n  }",
+                "count: 1 + other('x', 'y')",
+                "b: 'b.' + suffix"
+            ],
+            skipped
+        );
+        assert!(object
+            .skipped
+            .iter()
+            .all(|property| property.comma.is_some()));
+        assert!(parse_define_locales("export default defineLocales({ a: 'a', a: b });").is_err());
+    }
+
+    #[test]
+    fn ignores_define_locales_in_comments_and_strings() {
+        let source = "// export default defineLocales({ wrong: 'x' }, locales)\nconst s = 'export default defineLocales(';\nexport default defineLocales({ right: 'y' }, locales);";
+        let object = parse(source);
+        assert!(object.get("wrong").is_none());
+        assert_eq!("y", object.get("right").unwrap().value);
+    }
+}
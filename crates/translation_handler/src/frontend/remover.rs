@@ -1,11 +1,18 @@
+use crate::frontend::editor::MessagesEditor;
+use crate::frontend::json::{get_value, remove_value, value_as_text, LocaleFile};
+use crate::frontend::layout::FrontendLayout;
+use crate::frontend::parser::parse_define_locales;
+use crate::frontend::usage::scan_usages;
+use crate::plural::{plural_key, required_categories, PluralCategory, ALL_CATEGORIES};
 use crate::{frontend::PathType, TranslationHandler};
 use glob::glob;
 use local_storage::stores::translation_store::TranslationEntry;
 use regex::Regex;
-use serde_json::{self, Value};
+use serde_json::{self, Map, Value};
 use std::collections::HashMap;
 use std::fs::{self, read_to_string, OpenOptions};
-use std::io::{self, Read, Write};
+use std::io::{self, Write};
+use std::path::Path;
 use tracing::{error, info};
 
 impl TranslationHandler {
@@ -15,8 +22,19 @@ impl TranslationHandler {
         Some(language_code.as_str().to_string())
     }
 
-    pub fn extract_language_codes_from_locales(path: String) -> Vec<String> {
-        let sub_path = PathType::TranslationExportFile.create_path(path);
+    pub fn extract_language_codes_from_locales(
+        path: String,
+        layout: &FrontendLayout,
+    ) -> Vec<String> {
+        let sub_path = PathType::TranslationExportFile.create_path(path.clone(), layout);
+        if !Path::new(&sub_path).is_file() {
+            info!("No language file at {}, using the locale files", sub_path);
+            return layout
+                .locale_files(&path)
+                .into_iter()
+                .map(|(lang, _)| lang)
+                .collect();
+        }
         info!("Reading locales.ts file in {}", sub_path);
         let language_code_regex = Regex::new(r"'(\w{2}-\w{2})").unwrap();
         let file_content = read_to_string(&sub_path)
@@ -31,128 +49,142 @@ impl TranslationHandler {
         language_codes
     }
 
-    pub async fn get_key_values_from_messages_ts(path: &str) -> HashMap<String, String> {
-        let sub_path = PathType::MessageTsFile.create_path(path.to_owned());
-        let file_content = read_to_string(&sub_path)
-            .expect(&format!("Failed to read messages.ts file in {}", sub_path));
-        let mut mappings = HashMap::new();
+    pub async fn get_key_values_from_messages_ts(
+        path: &str,
+        layout: &FrontendLayout,
+    ) -> Result<HashMap<String, String>, io::Error> {
+        let sub_path = PathType::MessageTsFile.create_path(path.to_owned(), layout);
         info!(target: "translation", "Reading messages.ts file in {}", sub_path);
-        info!(target: "translation", "File content (first 200 chars): {}", file_content.chars().take(200).collect::<String>());
+        let file_content = read_to_string(&sub_path)?;
 
-        
-        let re = Regex::new(r"(?s)\s*export\s+default\s+defineLocales\s*\(\s*\{(.*)\}\s*,\s*locales\s*").unwrap();
-        let json_content = re
-            .captures(&file_content)
-            .and_then(|cap| cap.get(1).map(|m| m.as_str()))
-            .unwrap_or(&file_content);
-        info!(target: "translation", "Extracted JSON content (first 200 chars): {}", json_content.chars().take(200).collect::<String>());
+        let messages = parse_define_locales(&file_content).map_err(|e| {
+            error!(target: "translation", "Failed to parse {}: {}", sub_path, e);
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", sub_path, e))
+        })?;
 
-        // Regex to match key: 'value' or key: "value" with optional comma and whitespace
-        let key_value_regex = Regex::new(r#"\s*(\w+)\s*:\s*(?:"([^"]*)"|'([^']*)')\s*,?\s*"#).unwrap();
-        for capture in key_value_regex.captures_iter(json_content) {
-            let key = capture.get(1).unwrap().as_str().to_string();
-            let value = capture
-                .get(2)
-                .or(capture.get(3))
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_default();
-            mappings.insert(key.clone(), value.clone());
-            info!(target: "translation", "Captured key: {}, value: {}", key, value);
+        let mut mappings = HashMap::new();
+        for entry in messages.entries {
+            info!(target: "translation", "Captured key: {}, value: {}", entry.key, entry.value);
+            mappings.insert(entry.key, entry.value);
         }
 
         info!(target: "translation", "Total keys captured: {}", mappings.len());
-        mappings
+        Ok(mappings)
     }
 
     pub fn read_lang_files_in_locales(
         path: &str,
         keys: HashMap<String, String>,
-    ) -> Vec<TranslationEntry> {
-        let sub_path = PathType::TranslationDirectory.create_path(path.to_owned());
-
-        let json_files = glob(format!("{}/*.json", sub_path).as_str()).expect("Failed to read glob pattern");
+        layout: &FrontendLayout,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let json_files = layout.locale_files(path);
 
         let mut translation_entries: Vec<TranslationEntry> = Vec::new();
 
-        for entry in json_files {
-            match entry {
-                Ok(path) => {
-                    let file_content = fs::read_to_string(&path).expect("Unable to read file");
-                    let data: HashMap<String, Value> = serde_json::from_str(&file_content).expect(
-                        &format!("JSON was not well-formatted in {}", path.display()),
-                    );
-                    let file_stem = path.file_stem().unwrap().to_str().unwrap().to_string();
+        for (file_stem, path) in json_files {
+            match fs::read_to_string(&path) {
+                Ok(file_content) => {
+                    let data: Map<String, Value> =
+                        serde_json::from_str(&file_content).map_err(|e| {
+                            error!(target: "translation", "Failed to parse {}: {}", path.display(), e);
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("{}: {}", path.display(), e),
+                            )
+                        })?;
 
                     for (ts_key, json_key) in &keys {
-                        if let Some(json_value_object) = data.get(json_key) {
-                            let value = json_value_object.as_str().unwrap_or("");
-                            if let Some(entry) = translation_entries
-                                .iter_mut()
-                                .find(|entry| entry.value == *json_key)
-                            {
-                                entry.translations.insert(file_stem.clone(), value.to_string());
-                            } else {
-                                let mut translations = HashMap::new();
-                                translations.insert(file_stem.clone(), value.to_string());
-                                let trans_entry = TranslationEntry {
+                        let forms = plural_forms(&data, json_key);
+                        // A plural key shows its `other` form as the value
+                        let value = get_value(&data, json_key)
+                            .and_then(value_as_text)
+                            .or_else(|| forms.get(PluralCategory::Other.as_str()).cloned())
+                            .unwrap_or_default();
+
+                        let entry = match translation_entries
+                            .iter()
+                            .position(|entry| entry.value == *json_key)
+                        {
+                            Some(index) => &mut translation_entries[index],
+                            None => {
+                                translation_entries.push(TranslationEntry {
                                     key: ts_key.clone(),
                                     value: json_key.clone(),
-                                    translations,
                                     in_use: true,
-                                };
-                                translation_entries.push(trans_entry);
-                            }
-                        } else {
-                            if let Some(entry) = translation_entries
-                                .iter_mut()
-                                .find(|entry| entry.value == *json_key)
-                            {
-                                entry.translations.insert(file_stem.clone(), "".to_owned());
-                            } else {
-                                let mut translations = HashMap::new();
-                                translations.insert(file_stem.clone(), "".to_owned());
-                                let trans_entry = TranslationEntry {
-                                    key: ts_key.clone(),
-                                    value: json_key.clone(),
-                                    translations,
-                                    in_use: true,
-                                };
-                                translation_entries.push(trans_entry);
+                                    ..Default::default()
+                                });
+                                translation_entries.last_mut().unwrap()
                             }
+                        };
+                        entry.translations.insert(file_stem.clone(), value);
+                        if !forms.is_empty() {
+                            entry.plurals.insert(file_stem.clone(), forms);
                         }
                     }
                 }
                 Err(e) => println!("{:?}", e),
             }
         }
-        translation_entries.iter_mut().for_each(|entry| {
-            if entry.translations.iter().all(|(_key, value)| value == &"".to_owned()) {
-                entry.in_use = false;
-            }
-        });
         translation_entries
+            .iter_mut()
+            .filter(|entry| !entry.plurals.is_empty())
+            .for_each(|entry| {
+                for language in entry.translations.keys() {
+                    let forms = entry.plurals.get(language);
+                    let missing: Vec<String> = required_categories(language)
+                        .iter()
+                        .map(|category| category.as_str().to_string())
+                        .filter(|category| forms.map_or(true, |forms| !forms.contains_key(category)))
+                        .collect();
+                    if !missing.is_empty() {
+                        entry.missing_plural_forms.insert(language.clone(), missing);
+                    }
+                }
+            });
+        Ok(translation_entries)
     }
 
-    pub async fn get_frontend_translations(path: &str) -> Vec<TranslationEntry> {
-        let keys = Self::get_key_values_from_messages_ts(path).await;
-        let translation_entries = Self::read_lang_files_in_locales(path, keys);
-        translation_entries
+    pub async fn get_frontend_translations(
+        path: &str,
+        layout: &FrontendLayout,
+    ) -> Result<Vec<TranslationEntry>, io::Error> {
+        let keys = Self::get_key_values_from_messages_ts(path, layout).await?;
+        let mut translation_entries = Self::read_lang_files_in_locales(path, keys, layout)?;
+        let messages_file = PathType::MessageTsFile.create_path(path.to_owned(), layout);
+        let usages = scan_usages(Path::new(path), Path::new(&messages_file))?;
+        for entry in translation_entries.iter_mut() {
+            entry.in_use = usages.is_used(&entry.key);
+            entry.possibly_used = usages.is_possibly_used(&entry.key);
+            entry.usages = usages.locations(&entry.key, Path::new(path));
+        }
+        Ok(translation_entries)
     }
 
     pub async fn remove_frontend_key(
         path: String,
         ts_keys: Vec<String>,
         json_keys: Vec<String>,
+        layout: &FrontendLayout,
     ) -> Result<(), std::io::Error> {
-        let messages_ts_path = PathType::MessageTsFile.create_path(path.clone());
+        let messages_ts_path = PathType::MessageTsFile.create_path(path.clone(), layout);
         remove_key_from_messages_ts(messages_ts_path, ts_keys)?;
-        let locales_path = PathType::TranslationDirectory.create_path(path.clone());
-        remove_key_from_language_jsons(locales_path, json_keys)?;
+        remove_key_from_locale_files(&path, &json_keys, layout)?;
 
         Ok(())
     }
 }
 
+/// i18next plural variants (`key_one`, `key_other`...) of a key, keyed by category.
+fn plural_forms(data: &Map<String, Value>, key: &str) -> HashMap<String, String> {
+    ALL_CATEGORIES
+        .iter()
+        .filter_map(|category| {
+            let value = get_value(data, &plural_key(key, *category)).and_then(value_as_text)?;
+            Some((category.as_str().to_string(), value))
+        })
+        .collect()
+}
+
 pub fn remove_key_from_language_jsons(
     locales_path: String,
     keys: Vec<String>,
@@ -163,79 +195,7 @@ pub fn remove_key_from_language_jsons(
 
     for entry in json_files {
         match entry {
-            Ok(path) => {
-                let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown");
-                info!(target: "remover", "Processing file: {}", path.display());
-
-                let file_content = fs::read_to_string(&path)?;
-                
-                // Detect original indentation and newline style
-                let indent = file_content
-                    .lines()
-                    .find(|line| line.trim().starts_with('"'))
-                    .map(|line| {
-                        line.chars().take_while(|c| c.is_whitespace()).collect::<String>()
-                    })
-                    .unwrap_or("  ".to_string());
-                let newline = if file_content.contains("\r\n") { "\r\n" } else { "\n" };
-                info!(target: "remover", "Detected indent for {}: {:?}", path.display(), indent);
-                info!(target: "remover", "Detected newline for {}: {:?}", path.display(), newline);
-
-                // Parse JSON content while preserving order
-                let json: serde_json::Map<String, Value> = serde_json::from_str(&file_content)
-                    .map_err(|e| {
-                        error!(target: "remover", "Failed to parse JSON in {}: {}", path.display(), e);
-                        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid JSON: {}", e))
-                    })?;
-
-                // Log original content
-                info!(target: "remover", "Original content for {}: {:?}", path.display(), json);
-
-                // Remove specified keys while preserving order
-                let mut new_json = serde_json::Map::new();
-                for (key, value) in json.clone() {
-                    if !keys.contains(&key) {
-                        new_json.insert(key, value);
-                    }
-                }
-
-                // Log if keys were removed
-                let original_len = json.len();
-                let new_len = new_json.len();
-                if new_len < original_len {
-                    info!(target: "remover", "Removed keys from {}: {:?}", path.display(), keys);
-                } else {
-                    info!(target: "remover", "No matching keys found in {}", path.display());
-                }
-
-                // Serialize with original formatting
-                let mut new_content = String::new();
-                new_content.push_str("{");
-                new_content.push_str(newline);
-                let entries: Vec<_> = new_json.into_iter().collect(); // Preserve order
-                for (i, (key, value)) in entries.iter().enumerate() {
-                    let formatted_value = serde_json::to_string(value)
-                        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Serialization error: {}", e)))?;
-                    new_content.push_str(&format!(
-                        "{}\"{}\": {}{}",
-                        indent,
-                        key,
-                        formatted_value,
-                        if i < entries.len() - 1 { "," } else { "" }
-                    ));
-                    new_content.push_str(newline);
-                }
-                new_content.push_str("}");
-
-                // Write back to file
-                let mut file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .open(&path)?;
-                file.write_all(new_content.as_bytes())?;
-                file.flush()?;
-                info!(target: "remover", "Successfully updated: {}", path.display());
-            }
+            Ok(path) => remove_keys_from_locale_file(&path, &keys)?,
             Err(e) => {
                 error!(target: "remover", "Error reading file: {:?}", e);
             }
@@ -244,61 +204,67 @@ pub fn remove_key_from_language_jsons(
     Ok(())
 }
 
-pub fn remove_key_from_messages_ts(path: String, keys: Vec<String>) -> Result<(), std::io::Error> {
-    info!(target: "remover", "Removing keys from message.ts at {}", path);
-    let mut file = OpenOptions::new().read(true).open(path.clone())?;
+/// Removes keys from every locale file of a location, following its layout.
+pub fn remove_key_from_locale_files(
+    path: &str,
+    keys: &[String],
+    layout: &FrontendLayout,
+) -> Result<(), std::io::Error> {
+    info!(target: "remover", "Removing keys from the locale files of {}", path);
+    info!(target: "remover", "Requested keys to remove: {:?}", keys);
+    for (_, file) in layout.locale_files(path) {
+        remove_keys_from_locale_file(&file, keys)?;
+    }
+    Ok(())
+}
 
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
+fn remove_keys_from_locale_file(path: &Path, keys: &[String]) -> Result<(), std::io::Error> {
+    info!(target: "remover", "Processing file: {}", path.display());
 
-    let re = Regex::new(r"(?s)\s*export\s+default\s+defineLocales\s*\(").unwrap();
-    let json_start = re.find(&content).map(|m| m.end()).ok_or_else(|| {
-        info!(target: "remover", "Did not find 'export default defineLocales(' in {}", path);
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            "Could not find 'export default defineLocales(' pattern",
-        )
+    // Parse JSON content while keeping order, indentation and newline style
+    let mut locale_file = LocaleFile::read(path).map_err(|e| {
+        error!(target: "remover", "Failed to parse JSON in {}: {}", path.display(), e);
+        e
     })?;
 
-    let json_end = content[json_start..]
-        .find("locales")
-        .map(|i| json_start + i)
-        .ok_or_else(|| {
-            info!(target: "remover", "Did not find 'locales' in {}", path);
-            io::Error::new(io::ErrorKind::InvalidData, "Could not find 'locales' in file")
-        })?;
-
-    let key_value_pairs = &content[json_start..json_end];
-    let mut new_entries = String::new();
-
-    let key_value_regex = Regex::new(r#"\s*(\w+)\s*:\s*(?:"([^"]*)"|'([^']*)')\s*,?\s*"#).unwrap();
-    let newline = if std::env::consts::OS == "windows" { "\r\n" } else { "\n" };
-
-    key_value_pairs
-        .lines()
-        .for_each(|line| match key_value_regex.captures(line) {
-            None => new_entries.push_str(&format!("{}{}", line, newline)),
-            Some(capture) => {
-                let key = capture.get(1).unwrap().as_str();
-                if !keys.iter().any(|k| k == key) {
-                    new_entries.push_str(&format!("{}{}", line, newline));
-                }
+    // Keys can be flat (`"a.b"`) or a dotted path into nested objects, and take their
+    // plural variants with them
+    let removed: Vec<&String> = keys
+        .iter()
+        .filter(|key| {
+            let mut removed = remove_value(&mut locale_file.data, key);
+            for category in ALL_CATEGORIES {
+                removed |= remove_value(&mut locale_file.data, &plural_key(key, category));
             }
-        });
-
-    if new_entries.ends_with(newline) {
-        new_entries.pop();
-        if newline.len() > 1 {
-            new_entries.pop();
-        }
+            removed
+        })
+        .collect();
+    if removed.is_empty() {
+        info!(target: "remover", "No matching keys found in {}", path.display());
+    } else {
+        info!(target: "remover", "Removed keys from {}: {:?}", path.display(), removed);
     }
 
-    let new_file = format!("{}{}{}", &content[..json_start], new_entries, &content[json_end..]);
+    locale_file.write(path)?;
+    info!(target: "remover", "Successfully updated: {}", path.display());
+    Ok(())
+}
+
+pub fn remove_key_from_messages_ts(path: String, keys: Vec<String>) -> Result<(), std::io::Error> {
+    info!(target: "remover", "Removing keys from message.ts at {}", path);
+    let content = fs::read_to_string(&path)?;
+
+    let mut editor = MessagesEditor::new(content).map_err(|e| {
+        info!(target: "remover", "Failed to parse {}: {}", path, e);
+        io::Error::new(e.kind(), format!("{}: {}", path, e))
+    })?;
+    let removed = editor.remove(&keys)?;
+    info!(target: "remover", "Removed keys from {}: {:?}", path, removed);
 
     let mut file = OpenOptions::new().write(true).truncate(true).open(path)?;
-    file.write_all(new_file.as_bytes())?;
+    file.write_all(editor.source().as_bytes())?;
     file.flush()?;
 
     info!(target: "remover", "Successfully removed keys and updated file");
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,9 +1,18 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+use crate::frontend::editor::{InsertPosition, MessagesEditor};
+use crate::frontend::json::{remove_value, set_value, JsonLayout, LocaleFile};
+use crate::frontend::layout::FrontendLayout;
+use crate::plural::{plural_key, PluralCategory, ALL_CATEGORIES};
 use crate::{frontend::PathType, TranslationHandler};
 use db::prisma::settings::Data;
 use local_storage::stores::translation_store::TranslationEntry;
-use std::fs::OpenOptions;
-use std::io::{Read, Write};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use tracing::info;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -14,27 +23,51 @@ impl TranslationHandler {
         path: String,
         ts_key: String,
         json_key: String,
-        en_gb_value: String,
+        source_value: String,
+        plural_values: HashMap<PluralCategory, String>,
         settings: Data,
+        layout: &FrontendLayout,
     ) -> Result<Vec<TranslationEntry>, std::io::Error> {
-        let messages_ts_path = PathType::MessageTsFile.create_path(path.clone());
+        let messages_ts_path = PathType::MessageTsFile.create_path(path.clone(), layout);
 
         add_key_to_messages_ts(messages_ts_path, ts_key.clone(), json_key.clone())?;
 
-        add_translation_to_default_language(path.clone(), json_key, en_gb_value)?;
+        let source_language = layout.source_language(&settings.default_language);
+        if plural_values.is_empty() {
+            add_translation_to_default_language(
+                path.clone(),
+                json_key,
+                source_value,
+                source_language,
+                layout,
+            )?;
+        } else {
+            let source_path = PathType::LanguageFile(source_language.to_string())
+                .create_path(path.clone(), layout);
+            write_plural_forms(
+                Path::new(&source_path),
+                &json_key,
+                &plural_values,
+                layout.json_layout,
+            )?;
+        }
 
         if settings.translate_new_strings {
-            run_translation_command(&path.clone(), settings.translation_command);
+            run_translation_command(&path.clone(), settings.translation_command, layout);
         }
 
         let translations =
-            TranslationHandler::get_frontend_translations(path.clone().as_str()).await;
+            TranslationHandler::get_frontend_translations(path.clone().as_str(), layout).await?;
 
         Ok(translations)
     }
 }
-pub fn run_translation_command(dir_path: &str, translation_command: String) {
-    let locales_path = PathType::TranslationDirectory.create_path(dir_path.to_string());
+pub fn run_translation_command(
+    dir_path: &str,
+    translation_command: String,
+    layout: &FrontendLayout,
+) {
+    let locales_path = PathType::TranslationDirectory.create_path(dir_path.to_string(), layout);
     let program = if cfg!(target_os = "windows") {
         "C:\\Windows\\System32\\WindowsPowerShell\\v1.0\\powershell.exe"
     } else {
@@ -64,85 +97,136 @@ fn add_key_to_messages_ts(
     ts_key: String,
     json_key: String,
 ) -> Result<(), std::io::Error> {
-    let mut file = OpenOptions::new().read(true).open(path.clone())?;
-
-    let mut lines: Vec<String> = Vec::new();
-    let mut check_line = 0;
-    let mut content = String::new();
-    file.read_to_string(&mut content)
-        .expect("Failed to read file");
-
-    content.lines().enumerate().for_each(|(index, line)| {
-        // Always push the current line
-        lines.push(String::from(line));
-
-        // Check if the current line is a key-value pair (ends with ',')
-        if line.trim().ends_with("',") {
-            // Safely check if the next two lines exist
-            if index + 2 < content.lines().count() {
-                // Check if the next line is empty and the one after is "},"
-                if content.lines().nth(index + 1).unwrap().trim().is_empty()
-                    && content.lines().nth(index + 2).unwrap().trim() == "},"
-                {
-                    // Insert the new key-value pair with proper indentation
-                    lines.push(format!("    {}: '{}',", ts_key, json_key));
-                }
-            }
-        }
-    });
+    let content = fs::read_to_string(&path)?;
+    let mut editor = MessagesEditor::new(content)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+
+    // Only keep the keys sorted when the file already is
+    let position = if editor.is_sorted() {
+        InsertPosition::Sorted
+    } else {
+        InsertPosition::End
+    };
+    editor.insert(&ts_key, &json_key, position)?;
 
-    let lines_content: String = lines.join("\r\n");
     let mut file = OpenOptions::new()
         .write(true)
         .truncate(true)
         .open(path.clone())?;
-    file.write_all(lines_content.as_bytes())?;
+    file.write_all(editor.source().as_bytes())?;
 
     Ok(())
 }
 pub fn add_translation_to_default_language(
     path: String,
     json_key: String,
-    en_gb_value: String,
+    value: String,
+    source_language: &str,
+    layout: &FrontendLayout,
 ) -> Result<(), std::io::Error> {
-    let en_gb_path = PathType::EnGbFile.create_path(path.clone());
+    let source_path =
+        PathType::LanguageFile(source_language.to_string()).create_path(path.clone(), layout);
+
+    let mut locale_file = LocaleFile::read(Path::new(&source_path))?;
+    set_value(
+        &mut locale_file.data,
+        &json_key,
+        Value::String(value),
+        layout.json_layout,
+    )?;
+    locale_file.write(Path::new(&source_path))?;
 
-    let mut file = OpenOptions::new()
-        .write(true)
-        .read(true)
-        .open(en_gb_path.clone())?;
-
-    let mut lines: Vec<String> = Vec::new();
-    let mut content = String::new();
-    file.read_to_string(&mut content)
-        .expect("Failed to read file");
-
-    content.lines().enumerate().for_each(|(_index, line)| {
-        let trimmed_line = line.to_owned();
-
-        if trimmed_line != "{" && trimmed_line != "}" {
-            let mut updated_line = line.to_owned();
-            if !updated_line.ends_with(",") {
-                updated_line.push_str(", ");
-            }
-            lines.push(updated_line);
-        } else if trimmed_line == "}" {
-            lines.push(format!(r#"    "{}": "{}""#, json_key, en_gb_value));
-            lines.push(String::from(line))
-        } else if trimmed_line == "{" {
-            lines.push(trimmed_line);
-        }
-    });
-    let lines_content: String = lines.join("\r\n");
-    let mut file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(en_gb_path.clone())?;
-    file.write_all(lines_content.as_bytes())?;
+    Ok(())
+}
+
+/// Creates an empty locale file for `language`, formatted like the existing ones, and lists the
+/// language in the languages file when there is one.
+pub fn add_frontend_language(
+    path: &str,
+    language: &str,
+    layout: &FrontendLayout,
+) -> Result<(), std::io::Error> {
+    let locale_path = layout.locale_file(path, language);
+    if locale_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", locale_path.display()),
+        ));
+    }
+    let mut locale_file = match layout.locale_files(path).first() {
+        Some((_, existing)) => LocaleFile::read(existing)?,
+        None => LocaleFile::parse("{}")?,
+    };
+    locale_file.data.clear();
+    if let Some(parent) = locale_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    locale_file.write(&locale_path)?;
 
+    let languages_path = PathType::TranslationExportFile.create_path(path.to_string(), layout);
+    if !Path::new(&languages_path).is_file() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&languages_path)?;
+    let language_code_regex = Regex::new(r"'(\w{2}-\w{2})").unwrap();
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    if lines
+        .iter()
+        .any(|line| line.contains(&format!("'{}'", language)))
+    {
+        return Ok(());
+    }
+    // New languages go after the last listed one, indented the same way
+    if let Some(index) = lines
+        .iter()
+        .rposition(|line| language_code_regex.is_match(line))
+    {
+        let indent: String = lines[index]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        if !lines[index].trim_end().ends_with(',') {
+            lines[index] = format!("{},", lines[index].trim_end());
+        }
+        lines.insert(index + 1, format!("{}'{}': '{}',", indent, language, language));
+        let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let mut updated = lines.join(newline);
+        if content.ends_with('\n') {
+            updated.push_str(newline);
+        }
+        fs::write(&languages_path, updated)?;
+    } else {
+        info!("No language listed in {}, not registering {}", languages_path, language);
+    }
     Ok(())
 }
 
+/// Writes the plural variants of a key to a locale file, in place of a plain value of the key
+/// and of the variants it had before, so categories left out of `forms` don't linger.
+pub fn write_plural_forms(
+    file: &Path,
+    json_key: &str,
+    forms: &HashMap<PluralCategory, String>,
+    json_layout: JsonLayout,
+) -> Result<(), std::io::Error> {
+    let mut locale_file = LocaleFile::read(file)?;
+    remove_value(&mut locale_file.data, json_key);
+    for category in ALL_CATEGORIES {
+        remove_value(&mut locale_file.data, &plural_key(json_key, category));
+    }
+    for category in ALL_CATEGORIES {
+        if let Some(value) = forms.get(&category) {
+            set_value(
+                &mut locale_file.data,
+                &plural_key(json_key, category),
+                Value::String(value.clone()),
+                json_layout,
+            )?;
+        }
+    }
+    locale_file.write(file)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
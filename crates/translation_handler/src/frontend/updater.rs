@@ -1,23 +1,34 @@
-use crate::frontend::remover::remove_key_from_language_jsons;
-use crate::frontend::setter::{add_translation_to_default_language, run_translation_command};
-use crate::{frontend::PathType, TranslationHandler};
+use crate::frontend::editor::MessagesEditor;
+use crate::frontend::json::{get_value, set_value, JsonLayout, LocaleFile};
+use crate::frontend::layout::FrontendLayout;
+use crate::frontend::remover::remove_key_from_locale_files;
+use crate::frontend::setter::{
+    add_translation_to_default_language, run_translation_command, write_plural_forms,
+};
+use crate::plural::PluralCategory;
+use crate::TranslationHandler;
 use db::prisma::settings;
-use glob::glob;
 use local_storage::stores::translation_store::TranslationEntry;
 use serde::Deserialize;
-use serde_json::{Map, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
+use std::io::Write;
 use std::path::Path;
-use tracing::{error, info};
+use tracing::info;
 
 #[derive(Deserialize, Clone, specta::Type, Debug)]
 pub struct UpdatedKeyValues {
     pub ts_key: String,
     pub json_key: String,
     pub translation_values: HashMap<String, String>,
+    /// Plural forms per language, written as `json_key_{category}` keys
+    #[serde(default)]
+    pub plural_values: HashMap<String, HashMap<PluralCategory, String>>,
+    /// New translator note of the key, removed when empty. Formats without notes ignore it
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 impl TranslationHandler {
@@ -25,50 +36,58 @@ impl TranslationHandler {
         path: String,
         updated_key: UpdatedKeyValues,
         settings: settings::Data,
+        layout: &FrontendLayout,
     ) -> Result<(), std::io::Error> {
         info!("Updating key {} ", updated_key.ts_key );
-        let translations_directory_path = PathType::TranslationDirectory.create_path(path.clone());
-        let json_files = glob(format!("{}/*.json", translations_directory_path).as_str())
-            .expect("Failed to read glob pattern");
+        let json_files = layout.locale_files(&path);
 
-        // if update is only en-GB and retranslate is enabled, remove all entries of key from other files, just put it in en-GB and run translation
-        if updated_key.translation_values.len() == 1
-            && updated_key.translation_values.get("en-GB").is_some()
-            && settings.translate_updated_strings
-        {
+        let source_language = layout.source_language(&settings.default_language);
+        let only_source_language = updated_key
+            .translation_values
+            .keys()
+            .chain(updated_key.plural_values.keys())
+            .all(|lang| lang == source_language);
+        let source_updated = updated_key.translation_values.contains_key(source_language)
+            || updated_key.plural_values.contains_key(source_language);
+        // if update is only the source language and retranslate is enabled, remove all entries of key from other files, just put it in the source language and run translation
+        if only_source_language && source_updated && settings.translate_updated_strings {
             println!("retranslating");
-            remove_key_from_language_jsons(
-                translations_directory_path,
-                vec![updated_key.json_key.clone()],
-            )?;
-            add_translation_to_default_language(
-                path.clone(),
-                updated_key.json_key,
-                updated_key
-                    .translation_values
-                    .get("en-GB")
-                    .unwrap()
-                    .to_owned(),
-            )?;
-            run_translation_command(path.clone().as_str(), settings.translation_command);
+            remove_key_from_locale_files(&path, &[updated_key.json_key.clone()], layout)?;
+            match updated_key.plural_values.get(source_language) {
+                Some(forms) => {
+                    let source_path = layout.locale_file(&path, source_language);
+                    write_plural_forms(
+                        &source_path,
+                        &updated_key.json_key,
+                        forms,
+                        layout.json_layout,
+                    )?;
+                }
+                None => add_translation_to_default_language(
+                    path.clone(),
+                    updated_key.json_key,
+                    updated_key
+                        .translation_values
+                        .get(source_language)
+                        .unwrap()
+                        .to_owned(),
+                    source_language,
+                    layout,
+                )?,
+            }
+            run_translation_command(path.clone().as_str(), settings.translation_command, layout);
         } else {
-            for file in json_files {
-                if let Ok(path) = file {
-                    let lang = path.file_stem().unwrap().to_str().unwrap();
-                    if updated_key.translation_values.contains_key(lang) {
-                        update_translation_file(
-                            &path,
-                            updated_key.json_key.to_owned(),
-                            updated_key
-                                .translation_values
-                                .get(lang)
-                                .unwrap()
-                                .to_string(),
-                        )
-                        .await?;
-                    }
-                } else if let Err(e) = file {
-                    error!("{:?}", e);
+            for (lang, path) in json_files {
+                if let Some(forms) = updated_key.plural_values.get(&lang) {
+                    write_plural_forms(&path, &updated_key.json_key, forms, layout.json_layout)?;
+                } else if let Some(value) = updated_key.translation_values.get(&lang) {
+                    update_translation_file(
+                        &path,
+                        updated_key.json_key.to_owned(),
+                        value.to_string(),
+                        layout.json_layout,
+                    )
+                    .await?;
                 }
             }
         }
@@ -77,37 +96,33 @@ impl TranslationHandler {
     }
 }
 
+/// Renames a key of the `defineLocales` object, leaving the rest of the file untouched.
+pub fn rename_key_in_messages_ts(
+    path: String,
+    old_key: String,
+    new_key: String,
+) -> Result<(), std::io::Error> {
+    info!("Renaming key {} to {} in {}", old_key, new_key, path);
+    let content = fs::read_to_string(&path)?;
+    let mut editor = MessagesEditor::new(content)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+    editor.rename(&old_key, &new_key)?;
+
+    let mut file = OpenOptions::new().write(true).truncate(true).open(path)?;
+    file.write_all(editor.source().as_bytes())?;
+    Ok(())
+}
+
 async fn update_translation_file(
     path: &Path,
     key: String,
     value: String,
+    layout: JsonLayout,
 ) -> Result<(), std::io::Error> {
-    let file = fs::File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut lines = Vec::new();
-    let mut found = false;
-
-    for line_res in reader.lines() {
-        let mut line = line_res?;
-        if line.contains(&format!("\"{}\":", &key)) {
-            let key_value_regex = regex::Regex::new(r#"(\w+): '(.*)'|"(.*?)": "(.*)""#).unwrap();
-            if let Some(capture) = key_value_regex.captures(line.clone().as_str()) {
-                let old_value = &capture[4];
-                line = line.replace(old_value, value.as_str());
-                found = true;
-            }
-        }
-        lines.push(format!("{}", line.trim_end_matches("\r\n").trim_end_matches("\n")));
+    let mut locale_file = LocaleFile::read(path)?;
+    if get_value(&locale_file.data, &key).is_none() {
+        info!("Key {} not found in {}, adding it", key, path.display());
     }
-
-    if !found {
-        error!("Key not found")
-    } else {
-        let mut file = OpenOptions::new().write(true).truncate(true).open(path)?;
-
-        for line in lines {
-            write!(file, "{}\r\n", line)?;
-        }
-    }
-    Ok(())
+    set_value(&mut locale_file.data, &key, Value::String(value), layout)?;
+    locale_file.write(path)
 }
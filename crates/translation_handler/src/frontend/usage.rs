@@ -0,0 +1,909 @@
+use crate::frontend::parser::{is_ident_part, is_ident_start};
+use local_storage::stores::translation_store::SourceLocation;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::SystemTime;
+
+/// Extensions of the source files searched for references to the messages object.
+const SOURCE_EXTENSIONS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+
+/// Where the messages object or one of its keys is referenced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// References to the keys of a messages object across the sources of a location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageReport {
+    /// Static references such as `messages.fooBar` or `const { fooBar } = messages`, per key
+    pub keys: BTreeMap<String, Vec<Usage>>,
+    /// Computed accesses (`messages[key]`), spreads and places the whole object is handed to,
+    /// any of which may reach every key
+    pub dynamic: Vec<Usage>,
+}
+
+impl UsageReport {
+    pub fn count(&self, key: &str) -> usize {
+        self.keys.get(key).map_or(0, Vec::len)
+    }
+
+    pub fn is_used(&self, key: &str) -> bool {
+        self.count(key) > 0
+    }
+
+    /// Whether a key without static references may still be reached through a dynamic access,
+    /// so it must never be removed as unused.
+    pub fn is_possibly_used(&self, key: &str) -> bool {
+        !self.is_used(key) && !self.dynamic.is_empty()
+    }
+
+    /// Adds the references found by another scan, such as the one of a single file.
+    pub fn merge(&mut self, other: &UsageReport) {
+        for (key, usages) in &other.keys {
+            self.keys
+                .entry(key.clone())
+                .or_default()
+                .extend(usages.iter().cloned());
+        }
+        self.dynamic.extend(other.dynamic.iter().cloned());
+    }
+
+    /// The references to a key, with paths relative to `root`.
+    pub fn locations(&self, key: &str, root: &Path) -> Vec<SourceLocation> {
+        self.keys
+            .get(key)
+            .into_iter()
+            .flatten()
+            .map(|usage| SourceLocation {
+                file: usage
+                    .file
+                    .strip_prefix(root)
+                    .unwrap_or(&usage.file)
+                    .to_string_lossy()
+                    .to_string(),
+                line: usage.line as u32,
+                column: usage.column as u32,
+            })
+            .collect()
+    }
+}
+
+struct CachedSource<R> {
+    modified: Option<SystemTime>,
+    len: u64,
+    /// `None` for files that aren't UTF-8
+    report: Option<Arc<R>>,
+}
+
+/// Reports of single source files, kept until a file's size or modification time changes, so
+/// listing the translations of a location again only reads the sources that were edited. The
+/// lock is only taken to look reports up and store them, never while a file is read.
+pub(crate) struct SourceCache<R> {
+    files: Mutex<HashMap<(u64, PathBuf), CachedSource<R>>>,
+}
+
+impl<R> Default for SourceCache<R> {
+    fn default() -> Self {
+        SourceCache {
+            files: Mutex::default(),
+        }
+    }
+}
+
+impl<R> SourceCache<R> {
+    fn files(&self) -> MutexGuard<'_, HashMap<(u64, PathBuf), CachedSource<R>>> {
+        self.files.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Scans `files` with `scan`, reusing the reports of files that did not change since the
+    /// last scan with the same `scanner`. Returns the report of every UTF-8 file, in the order
+    /// of `files`.
+    pub(crate) fn scan(
+        &self,
+        scanner: u64,
+        files: &[PathBuf],
+        scan: impl Fn(&str, &Path) -> R,
+    ) -> io::Result<Vec<Arc<R>>> {
+        let mut reports = Vec::new();
+        for file in files {
+            let metadata = fs::metadata(file)?;
+            let modified = metadata.modified().ok();
+            let key = (scanner, file.clone());
+            let cached = self
+                .files()
+                .get(&key)
+                .filter(|cached| {
+                    cached.modified.is_some()
+                        && cached.modified == modified
+                        && cached.len == metadata.len()
+                })
+                .map(|cached| cached.report.clone());
+            let report = match cached {
+                Some(report) => report,
+                None => {
+                    let report = match fs::read_to_string(file) {
+                        Ok(source) => Some(Arc::new(scan(&source, file))),
+                        Err(error) if error.kind() == io::ErrorKind::InvalidData => None,
+                        Err(error) => return Err(error),
+                    };
+                    self.files().insert(
+                        key,
+                        CachedSource {
+                            modified,
+                            len: metadata.len(),
+                            report: report.clone(),
+                        },
+                    );
+                    report
+                }
+            };
+            reports.extend(report);
+        }
+        // Files that were deleted or moved out of the scanned folder
+        let scanned: HashSet<&PathBuf> = files.iter().collect();
+        self.files().retain(|(cached_scanner, file), _| {
+            *cached_scanner != scanner || scanned.contains(file)
+        });
+        Ok(reports)
+    }
+}
+
+/// Identifies what a scan looks for, so the same file scanned for another messages file or
+/// resource class is cached apart.
+pub(crate) fn scanner_id(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// Splits JavaScript or TypeScript source into identifiers, string literals and punctuation
+/// with their offsets. Unlike the messages.ts parser it never fails: JSX text and regular
+/// expressions with quotes end their "string" at the end of the line. Code in template literal
+/// substitutions is tokenized like any other code.
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let char_at = |index: usize| chars.get(index).map(|(_, c)| *c);
+    let mut tokens = Vec::new();
+    // One entry per open `{`, true when it opened a `${` substitution
+    let mut braces: Vec<bool> = Vec::new();
+    let mut in_template = false;
+    let mut index = 0;
+
+    while let Some(c) = char_at(index) {
+        let offset = chars[index].0;
+        if in_template {
+            match c {
+                '`' => in_template = false,
+                '\\' => index += 1,
+                '$' if char_at(index + 1) == Some('{') => {
+                    braces.push(true);
+                    in_template = false;
+                    index += 1;
+                }
+                _ => (),
+            }
+            index += 1;
+        } else if c == '/' && char_at(index + 1) == Some('/') {
+            while char_at(index).is_some_and(|c| c != '\n') {
+                index += 1;
+            }
+        } else if c == '/' && char_at(index + 1) == Some('*') {
+            index += 2;
+            while char_at(index).is_some()
+                && !(char_at(index) == Some('*') && char_at(index + 1) == Some('/'))
+            {
+                index += 1;
+            }
+            index += 2;
+        } else if c == '\'' || c == '"' {
+            let mut value = String::new();
+            index += 1;
+            while let Some(next) = char_at(index) {
+                index += 1;
+                match next {
+                    '\\' => {
+                        value.extend(char_at(index));
+                        index += 1;
+                    }
+                    '\n' => break,
+                    next if next == c => break,
+                    next => value.push(next),
+                }
+            }
+            tokens.push((Token::Str(value), offset));
+        } else if c == '`' {
+            in_template = true;
+            index += 1;
+        } else if is_ident_start(c) {
+            let mut ident = String::new();
+            while let Some(next) = char_at(index).filter(|c| is_ident_part(*c)) {
+                ident.push(next);
+                index += 1;
+            }
+            tokens.push((Token::Ident(ident), offset));
+        } else if c.is_ascii_digit() {
+            while char_at(index).is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            {
+                index += 1;
+            }
+        } else {
+            index += 1;
+            match c {
+                c if c.is_whitespace() => (),
+                '{' => {
+                    braces.push(false);
+                    tokens.push((Token::Punct(c), offset));
+                }
+                '}' if braces.pop() == Some(true) => in_template = true,
+                c => tokens.push((Token::Punct(c), offset)),
+            }
+        }
+    }
+    tokens
+}
+
+/// 1-based line and column of a byte offset.
+pub(crate) fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|line| line.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
+}
+
+/// Resolves `.` and `..` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Whether an import specifier points at the messages file. Relative specifiers are resolved
+/// against the importing file; aliases such as `@/app/messages` match when the rest of the
+/// specifier is the end of the messages file's path.
+fn imports_messages(specifier: &str, file: &Path, messages_file: &Path) -> bool {
+    let messages = normalize(&messages_file.with_extension(""));
+    let specifier = specifier.trim_end_matches('/');
+    let specifier = [".ts", ".tsx", ".js", ".jsx"]
+        .iter()
+        .find_map(|extension| specifier.strip_suffix(extension))
+        .unwrap_or(specifier);
+    if specifier.starts_with('.') {
+        let directory = file.parent().unwrap_or(Path::new(""));
+        let resolved = normalize(&directory.join(specifier));
+        return resolved == messages || resolved.join("index") == messages;
+    }
+    let mut segments: Vec<&str> = specifier.split('/').collect();
+    if segments
+        .first()
+        .is_some_and(|first| first.starts_with(['@', '~', '#']))
+        && segments.len() > 1
+    {
+        segments.remove(0);
+    }
+    messages.ends_with(segments.join("/"))
+}
+
+/// Index of the `{` that the `}` at `close` ends.
+fn matching_open(tokens: &[(Token, usize)], close: usize) -> Option<usize> {
+    let mut depth = 0;
+    for index in (0..=close).rev() {
+        match tokens[index].0 {
+            Token::Punct('}') => depth += 1,
+            Token::Punct('{') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+/// The bracket pairs of a token list: the index of the bracket every opening bracket is closed
+/// by, and for every token the innermost opening bracket around it.
+struct Brackets {
+    close: HashMap<usize, usize>,
+    enclosing: Vec<Option<usize>>,
+}
+
+impl Brackets {
+    fn new(tokens: &[(Token, usize)]) -> Self {
+        let mut close = HashMap::new();
+        let mut enclosing = Vec::with_capacity(tokens.len());
+        let mut open: Vec<usize> = Vec::new();
+        for (index, (token, _)) in tokens.iter().enumerate() {
+            match token {
+                Token::Punct(')' | ']' | '}') => {
+                    if let Some(opened) = open.pop() {
+                        close.insert(opened, index);
+                    }
+                    enclosing.push(open.last().copied());
+                }
+                Token::Punct('(' | '[' | '{') => {
+                    enclosing.push(open.last().copied());
+                    open.push(index);
+                }
+                _ => enclosing.push(open.last().copied()),
+            }
+        }
+        Self { close, enclosing }
+    }
+
+    /// Index of the bracket that closes the block, parentheses or brackets around `index`.
+    fn scope_end(&self, index: usize) -> usize {
+        self.enclosing[index]
+            .and_then(|open| self.close.get(&open).copied())
+            .unwrap_or(self.enclosing.len())
+    }
+}
+
+fn is_arrow(tokens: &[(Token, usize)], index: usize) -> bool {
+    tokens.get(index).map(|(token, _)| token) == Some(&Token::Punct('='))
+        && tokens.get(index + 1).map(|(token, _)| token) == Some(&Token::Punct('>'))
+}
+
+/// Whether the parentheses from `open` to `close` hold the parameters of a function or arrow
+/// function rather than a call or a condition.
+fn is_parameter_list(tokens: &[(Token, usize)], open: usize, close: usize) -> bool {
+    let mut index = close + 1;
+    // A return type annotation
+    if tokens.get(index).map(|(token, _)| token) == Some(&Token::Punct(':')) {
+        while tokens
+            .get(index)
+            .is_some_and(|(token, _)| !matches!(token, Token::Punct('{' | ';')))
+            && !is_arrow(tokens, index)
+        {
+            index += 1;
+        }
+    }
+    if is_arrow(tokens, index) {
+        return true;
+    }
+    let is_body = tokens.get(index).map(|(token, _)| token) == Some(&Token::Punct('{'));
+    let is_named = match open.checked_sub(1).and_then(|before| tokens.get(before)) {
+        Some((Token::Ident(name), _)) => !matches!(
+            name.as_str(),
+            "if" | "for" | "while" | "switch" | "catch" | "with"
+        ),
+        _ => false,
+    };
+    is_body && is_named
+}
+
+/// Index of the last token of the function whose parameters end at `close`: the `}` of its
+/// body, or the end of the expression an arrow function without braces returns.
+fn function_end(tokens: &[(Token, usize)], brackets: &Brackets, close: usize) -> usize {
+    let mut index = close + 1;
+    while let Some((token, _)) = tokens.get(index) {
+        if is_arrow(tokens, index) {
+            index += 2;
+            if tokens.get(index).map(|(token, _)| token) == Some(&Token::Punct('{')) {
+                break;
+            }
+            return expression_end(tokens, brackets, index);
+        }
+        match token {
+            Token::Punct('{') => break,
+            Token::Punct(';') => return index,
+            _ => index += 1,
+        }
+    }
+    brackets.close.get(&index).copied().unwrap_or(tokens.len())
+}
+
+/// Index of the last token of the expression starting at `start`, which ends before a `;` or
+/// `,` outside of brackets, or before the bracket that closes the group it is in.
+fn expression_end(tokens: &[(Token, usize)], brackets: &Brackets, start: usize) -> usize {
+    let mut index = start;
+    while let Some((token, _)) = tokens.get(index) {
+        match token {
+            Token::Punct('(' | '[' | '{') => match brackets.close.get(&index) {
+                Some(close) => index = close + 1,
+                None => return tokens.len(),
+            },
+            Token::Punct(';' | ',' | ')' | ']' | '}') => return index.saturating_sub(1),
+            _ => index += 1,
+        }
+    }
+    tokens.len()
+}
+
+/// Whether the parameter list or destructuring pattern `pattern`, from its opening to its
+/// closing bracket, declares `name`. Object keys, type annotations and default values don't.
+fn pattern_declares(pattern: &[(Token, usize)], name: &str) -> bool {
+    let mut open: Vec<char> = Vec::new();
+    for (index, (token, _)) in pattern.iter().enumerate() {
+        match token {
+            Token::Punct(c @ ('(' | '[' | '{')) => open.push(*c),
+            Token::Punct(')' | ']' | '}') => {
+                open.pop();
+            }
+            Token::Ident(ident) if ident == name => {
+                let in_object = open.last() == Some(&'{');
+                let previous =
+                    index
+                        .checked_sub(1)
+                        .and_then(|previous| match pattern[previous].0 {
+                            Token::Punct(c) => Some(c),
+                            _ => None,
+                        });
+                let next = pattern.get(index + 1).map(|(token, _)| token);
+                let is_binding = match previous {
+                    Some('(' | ',' | '[' | '{' | '.') => true,
+                    Some(':') => in_object,
+                    _ => false,
+                };
+                if is_binding && !(in_object && next == Some(&Token::Punct(':'))) {
+                    return true;
+                }
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Token ranges in which `name` is a local variable, function, class or parameter rather than
+/// the imported messages object, such as a `messages` prop.
+fn shadowed_ranges(
+    tokens: &[(Token, usize)],
+    brackets: &Brackets,
+    name: &str,
+) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::Ident(keyword)
+                if matches!(
+                    keyword.as_str(),
+                    "const" | "let" | "var" | "function" | "class"
+                ) =>
+            {
+                let declares = match tokens.get(index + 1) {
+                    Some((Token::Ident(declared), _)) => declared == name,
+                    Some((Token::Punct('{' | '['), _)) => brackets
+                        .close
+                        .get(&(index + 1))
+                        .is_some_and(|close| pattern_declares(&tokens[index + 1..=*close], name)),
+                    _ => false,
+                };
+                if declares {
+                    ranges.push((index, brackets.scope_end(index)));
+                }
+            }
+            Token::Punct('(') => {
+                let Some(&close) = brackets.close.get(&index) else {
+                    continue;
+                };
+                if is_parameter_list(tokens, index, close)
+                    && pattern_declares(&tokens[index..=close], name)
+                {
+                    ranges.push((index, function_end(tokens, brackets, close)));
+                }
+            }
+            // `messages => ...`
+            Token::Ident(param) if param == name && is_arrow(tokens, index + 1) => {
+                ranges.push((index, function_end(tokens, brackets, index)));
+            }
+            _ => (),
+        }
+    }
+    ranges
+}
+
+/// Finds the names the messages object is bound to by imports of the messages file, and the
+/// token ranges of those import statements. Besides the default import
+/// (`import messages from './messages'`), named imports such as `import { messages }` and
+/// `import { messages as m }` bind it, as the messages file exports nothing else.
+fn message_bindings(
+    tokens: &[(Token, usize)],
+    file: &Path,
+    messages_file: &Path,
+) -> (Vec<String>, Vec<(usize, usize)>) {
+    let ident = |index: usize| match tokens.get(index) {
+        Some((Token::Ident(name), _)) => Some(name.as_str()),
+        _ => None,
+    };
+    let mut bindings = Vec::new();
+    let mut imports = Vec::new();
+    for (start, (token, _)) in tokens.iter().enumerate() {
+        if *token != Token::Ident("import".to_string()) {
+            continue;
+        }
+        let Some(from) = tokens[start..]
+            .iter()
+            .take(64)
+            .position(|(token, _)| *token == Token::Ident("from".to_string()))
+            .map(|position| start + position)
+        else {
+            continue;
+        };
+        let Some((Token::Str(specifier), _)) = tokens.get(from + 1) else {
+            continue;
+        };
+        imports.push((start, from + 1));
+        // `import type` binds no value
+        if ident(start + 1) == Some("type") || !imports_messages(specifier, file, messages_file) {
+            continue;
+        }
+        let mut index = start + 1;
+        // `import messages from` or `import messages, { other } from`
+        if let Some(binding) = ident(index).filter(|_| index < from) {
+            bindings.push(binding.to_string());
+            index += 1;
+        }
+        let Some(open) = tokens[index..from]
+            .iter()
+            .position(|(token, _)| *token == Token::Punct('{'))
+            .map(|position| index + position)
+        else {
+            continue;
+        };
+        // `{ messages, default as m, type Messages }`
+        for specifier in
+            tokens[open + 1..from].split(|(token, _)| matches!(token, Token::Punct(',' | '}')))
+        {
+            let names: Vec<&str> = specifier
+                .iter()
+                .filter_map(|(token, _)| match token {
+                    Token::Ident(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            match names.as_slice() {
+                [name] | [_, "as", name] if *name != "type" => bindings.push(name.to_string()),
+                _ => (),
+            }
+        }
+    }
+    (bindings, imports)
+}
+
+/// Adds the references to the messages object in one source file to `report`.
+pub fn scan_source(source: &str, file: &Path, messages_file: &Path, report: &mut UsageReport) {
+    let tokens = tokenize(source);
+    let (bindings, imports) = message_bindings(&tokens, file, messages_file);
+    if bindings.is_empty() {
+        return;
+    }
+    let usage = |offset: usize| {
+        let (line, column) = position(source, offset);
+        Usage {
+            file: file.to_path_buf(),
+            line,
+            column,
+        }
+    };
+    let punct = |index: usize| match tokens.get(index) {
+        Some((Token::Punct(c), _)) => Some(*c),
+        _ => None,
+    };
+    let brackets = Brackets::new(&tokens);
+    let shadowed: HashMap<&String, Vec<(usize, usize)>> = bindings
+        .iter()
+        .map(|binding| (binding, shadowed_ranges(&tokens, &brackets, binding)))
+        .collect();
+
+    for (index, (token, offset)) in tokens.iter().enumerate() {
+        let Token::Ident(name) = token else {
+            continue;
+        };
+        let Some(shadowed) = shadowed.get(name) else {
+            continue;
+        };
+        if imports
+            .iter()
+            .chain(shadowed)
+            .any(|(start, end)| (*start..=*end).contains(&index))
+        {
+            continue;
+        }
+        let previous = index.checked_sub(1).and_then(punct);
+        let is_spread = index >= 3 && (1..=3).all(|back| punct(index - back) == Some('.'));
+        if is_spread {
+            report.dynamic.push(usage(*offset));
+            continue;
+        }
+        // A property of another object, a type query, an object key or a JSX attribute named
+        // like the binding
+        let is_type_query = index > 0 && tokens[index - 1].0 == Token::Ident("typeof".to_string());
+        let is_attribute =
+            punct(index + 1) == Some('=') && !matches!(punct(index + 2), Some('=' | '>'));
+        if previous == Some('.')
+            || is_type_query
+            || is_attribute
+            || (matches!(previous, Some('{' | ',')) && punct(index + 1) == Some(':'))
+        {
+            continue;
+        }
+
+        // messages.key and messages?.key
+        let member = match (punct(index + 1), punct(index + 2)) {
+            (Some('.'), _) => Some(index + 2),
+            (Some('?'), Some('.')) => Some(index + 3),
+            _ => None,
+        };
+        if let Some(member) = member {
+            match tokens.get(member) {
+                Some((Token::Ident(key), key_offset)) => report
+                    .keys
+                    .entry(key.clone())
+                    .or_default()
+                    .push(usage(*key_offset)),
+                _ => report.dynamic.push(usage(*offset)),
+            }
+            continue;
+        }
+        // messages['key'] and messages[key]
+        if punct(index + 1) == Some('[') {
+            match (tokens.get(index + 2), punct(index + 3)) {
+                (Some((Token::Str(key), key_offset)), Some(']')) => report
+                    .keys
+                    .entry(key.clone())
+                    .or_default()
+                    .push(usage(*key_offset)),
+                _ => report.dynamic.push(usage(*offset)),
+            }
+            continue;
+        }
+        // const { key, other: alias, ...rest } = messages
+        if previous == Some('=') && index >= 2 && punct(index - 2) == Some('}') {
+            if let Some(open) = matching_open(&tokens, index - 2) {
+                scan_destructuring(&tokens[open..index - 1], &usage, report);
+                continue;
+            }
+        }
+        report.dynamic.push(usage(*offset));
+    }
+}
+
+/// Records the keys an object pattern `{ ... }` takes out of the messages object. A rest
+/// element can hold any key.
+fn scan_destructuring(
+    pattern: &[(Token, usize)],
+    usage: &impl Fn(usize) -> Usage,
+    report: &mut UsageReport,
+) {
+    let mut depth = 0;
+    let mut expects_key = false;
+    for (index, (token, offset)) in pattern.iter().enumerate() {
+        match token {
+            Token::Punct('{' | '[' | '(') => {
+                depth += 1;
+                expects_key = depth == 1;
+            }
+            Token::Punct('}' | ']' | ')') => depth -= 1,
+            Token::Punct(',') if depth == 1 => expects_key = true,
+            Token::Punct('.') if depth == 1 && expects_key => {
+                let is_rest = pattern
+                    .get(index..index + 3)
+                    .is_some_and(|dots| dots.iter().all(|(token, _)| *token == Token::Punct('.')));
+                if is_rest {
+                    report.dynamic.push(usage(*offset));
+                    expects_key = false;
+                }
+            }
+            Token::Ident(key) | Token::Str(key) if depth == 1 && expects_key => {
+                report
+                    .keys
+                    .entry(key.clone())
+                    .or_default()
+                    .push(usage(*offset));
+                expects_key = false;
+            }
+            _ => (),
+        }
+    }
+}
+
+fn find_sources(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for dir_entry in fs::read_dir(directory)? {
+        let path = dir_entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "node_modules" {
+                find_sources(&path, files)?;
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| SOURCE_EXTENSIONS.iter().any(|source| ext == *source))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Scans the `.ts`, `.tsx`, `.js` and `.jsx` files below `directory` for references to the
+/// `defineLocales` object exported by `messages_file`. Files that aren't UTF-8 are skipped, and
+/// files that did not change since the last scan are not read again.
+pub fn scan_usages(directory: &Path, messages_file: &Path) -> io::Result<UsageReport> {
+    let mut files = Vec::new();
+    find_sources(directory, &mut files)?;
+    files.sort();
+    files.retain(|file| normalize(file) != normalize(messages_file));
+    static CACHE: OnceLock<SourceCache<UsageReport>> = OnceLock::new();
+    let mut report = UsageReport::default();
+    let scanned = CACHE.get_or_init(SourceCache::default).scan(
+        scanner_id(normalize(messages_file)),
+        &files,
+        |source, file| {
+            let mut report = UsageReport::default();
+            scan_source(source, file, messages_file, &mut report);
+            report
+        },
+    )?;
+    for file_report in scanned {
+        report.merge(&file_report);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(source: &str) -> UsageReport {
+        let mut report = UsageReport::default();
+        scan_source(
+            source,
+            Path::new("/app/src/pages/home.tsx"),
+            Path::new("/app/src/messages.ts"),
+            &mut report,
+        );
+        report
+    }
+
+    fn lines(report: &UsageReport, key: &str) -> Vec<usize> {
+        report.keys[key].iter().map(|usage| usage.line).collect()
+    }
+
+    #[test]
+    fn member_accesses_and_destructuring_are_counted() {
+        let source = r#"import m from '../messages';
+import { useT } from '@/lib/i18n';
+
+const { title, subtitle: heading, 'quoted-key': quoted } = m;
+
+export function Home({ messages }: Props) {
+  const t = useT();
+  // m.commented is not a reference
+  const label = 'm.inString';
+  return (
+    <div title={t(m.tooltip)}>
+      <h1>{t(m.title)}</h1>
+      <p>{`${t(m?.intro)} and more`}</p>
+      <span>{messages.other}</span>
+      {t(m['bracket'])}
+    </div>
+  );
+}
+"#;
+        let report = scan(source);
+        assert_eq!(
+            vec![
+                "bracket",
+                "intro",
+                "quoted-key",
+                "subtitle",
+                "title",
+                "tooltip"
+            ],
+            report.keys.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![4, 12], lines(&report, "title"));
+        assert_eq!(vec![13], lines(&report, "intro"));
+        assert_eq!(2, report.count("title"));
+        assert_eq!(16, report.keys["title"][1].column);
+        assert!(report.dynamic.is_empty());
+        assert!(!report.is_possibly_used("other"));
+    }
+
+    #[test]
+    fn computed_accesses_make_every_key_possibly_used() {
+        let source = "import messages from '@/messages'\n\
+            const label = (key: keyof typeof messages) => t(messages[key]);\n\
+            const { known, ...rest } = messages;\n\
+            export default () => <List items={messages} />;\n";
+        let report = scan(source);
+        let dynamic: Vec<usize> = report.dynamic.iter().map(|usage| usage.line).collect();
+        assert_eq!(vec![2, 3, 4], dynamic);
+        assert!(report.is_used("known"));
+        assert!(report.is_possibly_used("unknown"));
+        assert!(!report.is_possibly_used("known"));
+    }
+
+    #[test]
+    fn named_and_aliased_imports_bind_the_messages_object() {
+        let source = "import { messages } from '../messages';\nt(messages.title);\n";
+        assert!(scan(source).is_used("title"));
+        let source = "import { messages as m, type Messages } from '../messages';\nt(m.intro);\n";
+        assert!(scan(source).is_used("intro"));
+        let source = "import { default as m } from '../messages';\nconst { footer } = m;\n";
+        assert!(scan(source).is_used("footer"));
+        let source =
+            "import { type Messages } from '../messages';\nlet Messages = {};\nMessages.title;\n";
+        assert!(scan(source).keys.is_empty());
+    }
+
+    #[test]
+    fn locals_and_props_named_like_the_import_are_other_objects() {
+        let source = r#"import messages from '../messages';
+
+function List({ messages, items }: Props) {
+  return items.map((key) => t(messages[key]));
+}
+const format = (messages: Record<string, string>) => messages.other;
+const Field = () => {
+  const messages = useMessages();
+  return <Title messages={messages.label} text={t(messages.name)} />;
+};
+export const Page = () => <h1>{t(messages.title)}</h1>;
+"#;
+        let report = scan(source);
+        assert_eq!(vec!["title"], report.keys.keys().collect::<Vec<_>>());
+        assert!(report.dynamic.is_empty());
+        assert!(!report.is_possibly_used("other"));
+
+        let report = scan(&format!(
+            "{}const label = (key: string) => messages[key];\n",
+            source
+        ));
+        assert_eq!(
+            vec![12],
+            report
+                .dynamic
+                .iter()
+                .map(|usage| usage.line)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn only_imports_of_the_messages_file_count() {
+        let source = "import messages from './messages';\nt(messages.title);\n";
+        assert!(scan(source).keys.is_empty());
+        let source = "import type Messages from '../messages';\nlet m: Messages;\nm.title;\n";
+        assert!(scan(source).keys.is_empty());
+        let source = "import messages from '../messages.ts';\nmessages.title;\n";
+        assert!(scan(source).is_used("title"));
+    }
+}
@@ -1,4 +1,14 @@
+pub mod arb;
 pub mod backend;
+pub mod checker;
+pub mod exchange;
+pub mod fluent;
+pub mod format;
 pub mod frontend;
+pub mod locale;
+pub mod mobile;
+pub mod plural;
+pub mod properties;
+pub mod yaml;
 
 pub struct TranslationHandler;
@@ -0,0 +1,185 @@
+/// Whether `name` looks like a language code such as `de`, `pt-BR` or `zh_Hant`.
+pub fn is_language_code(name: &str) -> bool {
+    let mut subtags = name.split(['-', '_']);
+    let primary = subtags.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Script (`Latn`) or region (`BR`, `419`) subtag, written the way file names use them.
+fn is_qualifier(subtag: &str) -> bool {
+    let mut chars = subtag.chars();
+    match subtag.len() {
+        2 => subtag.chars().all(|c| c.is_ascii_uppercase()),
+        3 => subtag.chars().all(|c| c.is_ascii_digit()),
+        4 => {
+            chars.next().is_some_and(|c| c.is_ascii_uppercase())
+                && chars.all(|c| c.is_ascii_lowercase())
+        }
+        _ => false,
+    }
+}
+
+/// Splits a file stem such as `app_pt_BR` into the prefix `app` and the language `pt_BR`.
+/// Scripts and regions have to be written in their usual case, so that `my_app_en` is the
+/// language `en` of `my_app` rather than `app_en`.
+pub fn split_language_suffix(stem: &str) -> Option<(&str, &str)> {
+    stem.match_indices('_').find_map(|(index, _)| {
+        let language = &stem[index + 1..];
+        let mut subtags = language.split('_');
+        let primary = subtags.next()?;
+        let is_language = (2..=3).contains(&primary.len())
+            && primary.chars().all(|c| c.is_ascii_lowercase())
+            && subtags.all(is_qualifier);
+        is_language.then_some((&stem[..index], language))
+    })
+}
+
+/// Primary language subtags of the cultures .NET knows: ISO 639-1 codes, and ISO 639-2/3
+/// codes for languages without one.
+const CULTURE_LANGUAGES: &[&str] = &[
+    "af", "agq", "ak", "am", "ar", "arn", "as", "asa", "ast", "az", "ba", "bas", "be", "bem",
+    "bez", "bg", "bin", "bm", "bn", "bo", "br", "brx", "bs", "byn", "ca", "ccp", "ce", "ceb",
+    "cgg", "chr", "ckb", "co", "cs", "cu", "cy", "da", "dav", "de", "dje", "doi", "dsb", "dua",
+    "dv", "dyo", "dz", "ebu", "ee", "el", "en", "eo", "es", "et", "eu", "ewo", "fa", "ff", "fi",
+    "fil", "fo", "fr", "fur", "fy", "ga", "gd", "gl", "gn", "gsw", "gu", "guz", "gv", "ha", "haw",
+    "he", "hi", "hr", "hsb", "hu", "hy", "ia", "ibb", "id", "ig", "ii", "is", "it", "iu", "ja",
+    "jgo", "jmc", "jv", "ka", "kab", "kam", "kde", "kea", "kgp", "khq", "ki", "kk", "kkj", "kl",
+    "kln", "km", "kn", "ko", "kok", "kr", "ks", "ksb", "ksf", "ksh", "ku", "kw", "ky", "la", "lag",
+    "lb", "lg", "lkt", "ln", "lo", "lrc", "lt", "lu", "luo", "luy", "lv", "mai", "mas", "mer",
+    "mfe", "mg", "mgh", "mgo", "mi", "mk", "ml", "mn", "mni", "moh", "mr", "ms", "mt", "mua", "my",
+    "mzn", "naq", "nb", "nd", "nds", "ne", "nl", "nmg", "nn", "nnh", "no", "nqo", "nr", "nso",
+    "nus", "nyn", "oc", "om", "or", "os", "pa", "pap", "pcm", "pl", "prg", "prs", "ps", "pt",
+    "quc", "quz", "rm", "rn", "ro", "rof", "ru", "rw", "rwk", "sa", "sah", "saq", "sat", "sbp",
+    "sc", "sd", "se", "seh", "ses", "sg", "shi", "si", "sk", "sl", "sma", "smj", "smn", "sms",
+    "sn", "so", "sq", "sr", "ss", "ssy", "st", "su", "sv", "sw", "syr", "ta", "te", "teo", "tg",
+    "th", "ti", "tig", "tk", "tn", "to", "tr", "ts", "tt", "twq", "tzm", "ug", "uk", "ur", "uz",
+    "vai", "ve", "vi", "vo", "vun", "wae", "wal", "wo", "xh", "xog", "yav", "yi", "yo", "yue",
+    "zgh", "zh", "zu",
+];
+
+/// Whether `name` is a .NET culture name such as `de`, `de-AT`, `sr-Latn-RS` or
+/// `ca-ES-valencia`: a known language, then an optional script, region and variants. Culture
+/// names are compared without regard to case, as .NET does.
+pub fn is_culture_name(name: &str) -> bool {
+    let mut subtags = name.split('-').peekable();
+    let language = subtags.next().unwrap_or_default().to_ascii_lowercase();
+    if !CULTURE_LANGUAGES.contains(&language.as_str()) {
+        return false;
+    }
+    let is_alphabetic = |subtag: &str, length| {
+        subtag.len() == length && subtag.chars().all(|c| c.is_ascii_alphabetic())
+    };
+    if subtags
+        .peek()
+        .is_some_and(|subtag| is_alphabetic(subtag, 4))
+    {
+        subtags.next();
+    }
+    if subtags.peek().is_some_and(|subtag| {
+        is_alphabetic(subtag, 2)
+            || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+    }) {
+        subtags.next();
+    }
+    // Legacy Chinese names such as zh-CHS and variants such as valencia or 1901
+    subtags.all(|subtag| {
+        subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            && ((5..=8).contains(&subtag.len())
+                || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit()))
+                || (language == "zh" && subtag.len() == 3))
+    })
+}
+
+/// Splits a resx file stem such as `Errors.Validation.de-DE` into the neutral name
+/// `Errors.Validation` and the culture `de-DE`.
+pub fn split_culture_suffix(stem: &str) -> Option<(&str, &str)> {
+    let (name, culture) = stem.rsplit_once('.')?;
+    (!name.is_empty() && is_culture_name(culture)).then_some((name, culture))
+}
+
+/// The cultures .NET looks up a resource in, from `culture` itself to its most neutral
+/// parent: `de-AT`, `de`. Chinese regions fall back to their script, `zh-TW` to `zh-Hant`.
+pub fn culture_fallbacks(culture: &str) -> Vec<String> {
+    let mut chain = vec![culture.to_string()];
+    let mut current = culture.to_string();
+    loop {
+        let parent = match current.to_ascii_lowercase().as_str() {
+            "zh-tw" | "zh-hk" | "zh-mo" | "zh-cht" => "zh-Hant".to_string(),
+            "zh-cn" | "zh-sg" | "zh-chs" => "zh-Hans".to_string(),
+            _ => match current.rsplit_once('-') {
+                Some((parent, _)) => parent.to_string(),
+                None => break,
+            },
+        };
+        chain.push(parent.clone());
+        current = parent;
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_suffixes_are_split_from_the_prefix() {
+        assert_eq!(Some(("app", "en")), split_language_suffix("app_en"));
+        assert_eq!(Some(("my_app", "en")), split_language_suffix("my_app_en"));
+        assert_eq!(
+            Some(("messages", "pt_BR")),
+            split_language_suffix("messages_pt_BR")
+        );
+        assert_eq!(
+            Some(("app", "sr_Latn_RS")),
+            split_language_suffix("app_sr_Latn_RS")
+        );
+        assert_eq!(None, split_language_suffix("messages"));
+        assert!(is_language_code("zh_Hant") && !is_language_code("messages"));
+    }
+
+    #[test]
+    fn culture_names_are_matched_against_known_languages() {
+        for culture in [
+            "de",
+            "de-AT",
+            "DE-at",
+            "sr-Latn-RS",
+            "es-419",
+            "ca-ES-valencia",
+            "zh-CHS",
+        ] {
+            assert!(is_culture_name(culture), "{}", culture);
+        }
+        for name in [
+            "Validation",
+            "Index",
+            "resx",
+            "de_AT",
+            "de-Validation",
+            "xx-XX",
+            "",
+        ] {
+            assert!(!is_culture_name(name), "{}", name);
+        }
+        assert_eq!(
+            Some(("Errors.Validation", "de-DE")),
+            split_culture_suffix("Errors.Validation.de-DE")
+        );
+        assert_eq!(None, split_culture_suffix("Errors.Validation"));
+    }
+
+    #[test]
+    fn cultures_fall_back_to_their_parents() {
+        assert_eq!(vec!["de-AT", "de"], culture_fallbacks("de-AT"));
+        assert_eq!(
+            vec!["sr-Latn-RS", "sr-Latn", "sr"],
+            culture_fallbacks("sr-Latn-RS")
+        );
+        assert_eq!(vec!["zh-TW", "zh-Hant", "zh"], culture_fallbacks("zh-TW"));
+        assert_eq!(vec!["fr"], culture_fallbacks("fr"));
+    }
+}
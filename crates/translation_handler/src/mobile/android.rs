@@ -0,0 +1,574 @@
+use crate::mobile::{collect_entries, MobileValue};
+use crate::plural::PluralCategory;
+use local_storage::stores::translation_store::TranslationEntry;
+use quick_xml::escape::{escape, partial_escape};
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+
+/// Language of the `values` directory, which has no qualifier.
+pub const DEFAULT_LANGUAGE: &str = "default";
+const STRINGS_FILE: &str = "strings.xml";
+const DEFAULT_INDENT: &str = "    ";
+const STRINGS_TEMPLATE: &str =
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n</resources>\n";
+
+pub fn is_resource_directory(path: &Path) -> bool {
+    path.join("values").join(STRINGS_FILE).is_file()
+}
+
+/// Language of a `values-<qualifier>` directory. Directories with other qualifiers, such as
+/// `values-night` or `values-de-land`, hold no translations.
+pub fn language_from_directory(name: &str) -> Option<String> {
+    if name == "values" {
+        return Some(DEFAULT_LANGUAGE.to_string());
+    }
+    let qualifier = name.strip_prefix("values-")?;
+    let is_language =
+        |code: &str| (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase());
+    // BCP 47 qualifiers such as `b+sr+Latn`
+    if let Some(tag) = qualifier.strip_prefix("b+") {
+        let subtags: Vec<&str> = tag.split('+').collect();
+        return is_language(subtags[0]).then(|| subtags.join("-"));
+    }
+    match qualifier.split('-').collect::<Vec<_>>()[..] {
+        [language] if is_language(language) => Some(language.to_string()),
+        [language, region]
+            if is_language(language)
+                && region.len() == 3
+                && region.starts_with('r')
+                && region[1..].chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            Some(format!("{}-{}", language, &region[1..]))
+        }
+        _ => None,
+    }
+}
+
+/// `values-<qualifier>` directory of a language, the inverse of [`language_from_directory`].
+pub fn directory_for_language(language: &str) -> String {
+    if language == DEFAULT_LANGUAGE {
+        return "values".to_string();
+    }
+    let subtags: Vec<&str> = language.split(['-', '_']).collect();
+    match subtags[..] {
+        [language] => format!("values-{}", language.to_lowercase()),
+        [language, region]
+            if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) =>
+        {
+            format!(
+                "values-{}-r{}",
+                language.to_lowercase(),
+                region.to_uppercase()
+            )
+        }
+        _ => format!("values-b+{}", subtags.join("+")),
+    }
+}
+
+pub fn strings_file(resource_directory: &Path, language: &str) -> PathBuf {
+    resource_directory
+        .join(directory_for_language(language))
+        .join(STRINGS_FILE)
+}
+
+/// Languages with a `strings.xml`, the default language first.
+pub fn get_languages(resource_directory: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = fs::read_dir(resource_directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|dir_entry| dir_entry.path().join(STRINGS_FILE).is_file())
+        .filter_map(|dir_entry| language_from_directory(&dir_entry.file_name().to_string_lossy()))
+        .filter(|language| language != DEFAULT_LANGUAGE)
+        .collect();
+    languages.sort();
+    if is_resource_directory(resource_directory) {
+        languages.insert(0, DEFAULT_LANGUAGE.to_string());
+    }
+    languages
+}
+
+pub fn get_translations(resource_directory: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    let mut values = Vec::new();
+    for language in get_languages(resource_directory) {
+        let language_values = read_strings(&strings_file(resource_directory, &language))?;
+        values.push((language, language_values));
+    }
+    Ok(collect_entries(values))
+}
+
+/// Creates an empty `strings.xml` for `language`.
+pub fn add_language(resource_directory: &Path, language: &str) -> Result<PathBuf, io::Error> {
+    let file = strings_file(resource_directory, language);
+    if file.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", file.display()),
+        ));
+    }
+    fs::create_dir_all(file.parent().unwrap())?;
+    fs::write(&file, STRINGS_TEMPLATE)?;
+    Ok(file)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn xml_error(error: quick_xml::Error) -> io::Error {
+    invalid(error.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, io::Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|e| invalid(e.to_string()))?;
+        if attribute.key.local_name().as_ref() == name.as_bytes() {
+            let value = attribute.unescape_value().map_err(xml_error)?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolves the escapes aapt applies: backslash escapes, double quotes that keep whitespace
+/// and whitespace that collapses outside of them.
+pub fn unescape(raw: &str) -> String {
+    let mut value = String::new();
+    let mut quoted = false;
+    // Unquoted whitespace is written as one space once more text follows
+    let mut space = false;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        let text = match c {
+            '"' => {
+                quoted = !quoted;
+                continue;
+            }
+            c if c.is_whitespace() && !quoted => {
+                space = true;
+                continue;
+            }
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        Some(c) => c,
+                        None => continue,
+                    }
+                }
+                Some(c) => c,
+                None => continue,
+            },
+            c => c,
+        };
+        if space && !value.is_empty() {
+            value.push(' ');
+        }
+        space = false;
+        value.push(text);
+    }
+    value
+}
+
+/// Escapes a value so aapt reads it back unchanged.
+pub fn escape_value(value: &str) -> String {
+    let mut escaped = String::new();
+    for (index, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '@' | '?' if index == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    let keeps_whitespace = value.starts_with(' ') || value.ends_with(' ') || value.contains("  ");
+    let escaped = partial_escape(&escaped).to_string();
+    if keeps_whitespace {
+        format!("\"{}\"", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Strings and plurals of a `strings.xml` in file order. Strings marked
+/// `translatable="false"` are left out, and markup inside a string is kept as text.
+pub fn read_strings(path: &Path) -> Result<Vec<(String, MobileValue)>, io::Error> {
+    let content = fs::read_to_string(path)?;
+    parse_strings(&content)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+pub fn parse_strings(content: &str) -> Result<Vec<(String, MobileValue)>, io::Error> {
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(false);
+
+    let mut values = Vec::new();
+    let mut depth = 0;
+    // Name of the string or plurals being read, and the quantity of the current plural item
+    let mut current: Option<(String, Option<PluralCategory>)> = None;
+    let mut plural: Option<BTreeMap<PluralCategory, String>> = None;
+    let mut raw = String::new();
+    // Depth at which the text of the current value starts
+    let mut text_depth = None;
+    loop {
+        let position = reader.buffer_position();
+        let event = reader
+            .read_event()
+            .map_err(|e| invalid(format!("at byte {}: {}", position, e)))?;
+        match event {
+            Event::Start(ref element) => {
+                depth += 1;
+                let name = element.local_name();
+                match (depth, name.as_ref()) {
+                    (_, _) if text_depth.is_some() => {
+                        raw.push('<');
+                        raw.push_str(&String::from_utf8_lossy(element));
+                        raw.push('>');
+                    }
+                    (2, b"string")
+                        if attribute(element, "translatable")?.as_deref() != Some("false") =>
+                    {
+                        let name = attribute(element, "name")?
+                            .ok_or_else(|| invalid("<string> without a name".to_string()))?;
+                        current = Some((name, None));
+                        raw.clear();
+                        text_depth = Some(depth);
+                    }
+                    (2, b"plurals") => {
+                        let name = attribute(element, "name")?
+                            .ok_or_else(|| invalid("<plurals> without a name".to_string()))?;
+                        current = Some((name, None));
+                        plural = Some(BTreeMap::new());
+                    }
+                    (3, b"item") if plural.is_some() => {
+                        let quantity = attribute(element, "quantity")?.unwrap_or_default();
+                        let category = quantity.parse::<PluralCategory>().map_err(invalid)?;
+                        if let Some((_, item)) = current.as_mut() {
+                            *item = Some(category);
+                        }
+                        raw.clear();
+                        text_depth = Some(depth);
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(ref element) => {
+                if text_depth.is_some() {
+                    raw.push('<');
+                    raw.push_str(&String::from_utf8_lossy(element));
+                    raw.push_str("/>");
+                } else if depth == 1
+                    && element.local_name().as_ref() == b"string"
+                    && attribute(element, "translatable")?.as_deref() != Some("false")
+                {
+                    if let Some(name) = attribute(element, "name")? {
+                        values.push((name, MobileValue::Text(String::new())));
+                    }
+                }
+            }
+            Event::Text(ref text) if text_depth.is_some() => {
+                raw.push_str(&text.unescape().map_err(xml_error)?);
+            }
+            Event::CData(content) if text_depth.is_some() => {
+                raw.push_str(&String::from_utf8_lossy(&content.into_inner()));
+            }
+            Event::End(ref element) => {
+                if text_depth == Some(depth) {
+                    text_depth = None;
+                    let value = unescape(&raw);
+                    match (plural.as_mut(), current.as_mut()) {
+                        (Some(forms), Some((_, item))) => {
+                            if let Some(category) = item.take() {
+                                forms.insert(category, value);
+                            }
+                        }
+                        (None, Some(_)) => {
+                            let (name, _) = current.take().unwrap();
+                            values.push((name, MobileValue::Text(value)));
+                        }
+                        _ => {}
+                    }
+                } else if text_depth.is_some() {
+                    raw.push_str("</");
+                    raw.push_str(&String::from_utf8_lossy(element.name().as_ref()));
+                    raw.push('>');
+                } else if depth == 2 && element.local_name().as_ref() == b"plurals" {
+                    if let (Some((name, _)), Some(forms)) = (current.take(), plural.take()) {
+                        values.push((name, MobileValue::Plural(forms)));
+                    }
+                }
+                depth -= 1;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(values)
+}
+
+fn element_xml(name: &str, value: &MobileValue, indent: &str, newline: &str) -> String {
+    match value {
+        MobileValue::Text(text) => format!(
+            "<string name=\"{}\">{}</string>",
+            escape(name),
+            escape_value(text)
+        ),
+        MobileValue::Plural(forms) => {
+            let mut xml = format!("<plurals name=\"{}\">", escape(name));
+            for (category, form) in forms {
+                xml.push_str(&format!(
+                    "{}{}{}<item quantity=\"{}\">{}</item>",
+                    newline,
+                    indent,
+                    indent,
+                    category,
+                    escape_value(form)
+                ));
+            }
+            xml.push_str(&format!("{}{}</plurals>", newline, indent));
+            xml
+        }
+    }
+}
+
+/// Sets the values of a `strings.xml`, creating the file when needed. Existing elements are
+/// replaced where they are, new ones are appended. Comments and formatting are kept.
+pub fn write_values(path: &Path, values: &[(String, MobileValue)]) -> Result<(), io::Error> {
+    if !path.exists() {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, STRINGS_TEMPLATE)?;
+    }
+    let content = fs::read_to_string(path)?;
+    let values: HashMap<&str, &MobileValue> = values
+        .iter()
+        .map(|(name, value)| (name.as_str(), value))
+        .collect();
+    let (rewritten, _) = rewrite(&content, &values, &HashSet::new())?;
+    fs::write(path, rewritten)
+}
+
+/// Removes strings and plurals by name. Returns whether anything was removed.
+pub fn remove_names(path: &Path, names: &[String]) -> Result<bool, io::Error> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(path)?;
+    let names: HashSet<&str> = names.iter().map(String::as_str).collect();
+    let (rewritten, changed) = rewrite(&content, &HashMap::new(), &names)?;
+    if changed {
+        fs::write(path, rewritten)?;
+    }
+    Ok(changed)
+}
+
+fn write(writer: &mut Writer<Cursor<Vec<u8>>>, event: Event) -> Result<(), io::Error> {
+    writer.write_event(event).map_err(xml_error)
+}
+
+/// Streams `content` through, replacing elements named in `values`, dropping the ones named in
+/// `removals` together with the whitespace before them, and appending the remaining values.
+fn rewrite(
+    content: &str,
+    values: &HashMap<&str, &MobileValue>,
+    removals: &HashSet<&str>,
+) -> Result<(String, bool), io::Error> {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(false);
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+
+    let mut changed = false;
+    let mut depth = 0;
+    let mut skip_depth = 0;
+    let mut indent: Option<String> = None;
+    let mut pending_space: Option<String> = None;
+    let mut written: HashSet<&str> = HashSet::new();
+    loop {
+        let event = reader.read_event().map_err(xml_error)?;
+        if skip_depth > 0 {
+            match event {
+                Event::Start(_) => skip_depth += 1,
+                Event::End(_) => skip_depth -= 1,
+                _ => {}
+            }
+            if skip_depth == 0 {
+                depth -= 1;
+            }
+            continue;
+        }
+        match event {
+            Event::Text(ref text) if text.iter().all(u8::is_ascii_whitespace) => {
+                if let Some(space) = pending_space.take() {
+                    write(&mut writer, Event::Text(BytesText::from_escaped(space)))?;
+                }
+                pending_space = Some(String::from_utf8_lossy(text).to_string());
+                continue;
+            }
+            Event::Start(ref element) | Event::Empty(ref element)
+                if depth == 1
+                    && matches!(element.local_name().as_ref(), b"string" | b"plurals") =>
+            {
+                let is_start = matches!(event, Event::Start(_));
+                if indent.is_none() {
+                    indent = pending_space
+                        .as_deref()
+                        .and_then(|space| space.rsplit('\n').next())
+                        .map(str::to_string);
+                }
+                let name = attribute(element, "name")?.unwrap_or_default();
+                if removals.contains(name.as_str()) {
+                    pending_space = None;
+                    changed = true;
+                    if is_start {
+                        depth += 1;
+                        skip_depth = 1;
+                    }
+                    continue;
+                }
+                if let Some((name, value)) = values.get_key_value(name.as_str()) {
+                    if let Some(space) = pending_space.take() {
+                        write(&mut writer, Event::Text(BytesText::from_escaped(space)))?;
+                    }
+                    let indent = indent.as_deref().unwrap_or(DEFAULT_INDENT);
+                    let xml = element_xml(name, value, indent, newline);
+                    write(&mut writer, Event::Text(BytesText::from_escaped(xml)))?;
+                    written.insert(name);
+                    changed = true;
+                    if is_start {
+                        depth += 1;
+                        skip_depth = 1;
+                    }
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        let appending = match &event {
+            Event::End(element) => depth == 1 && element.local_name().as_ref() == b"resources",
+            Event::Empty(element) => depth == 0 && element.local_name().as_ref() == b"resources",
+            _ => false,
+        };
+        if appending {
+            let indent = indent.clone().unwrap_or_else(|| DEFAULT_INDENT.to_string());
+            let mut appended = String::new();
+            let mut names: Vec<&&str> = values
+                .keys()
+                .filter(|name| !written.contains(**name))
+                .collect();
+            names.sort();
+            for name in names {
+                appended.push_str(newline);
+                appended.push_str(&indent);
+                appended.push_str(&element_xml(name, values[*name], &indent, newline));
+                changed = true;
+            }
+            if let Event::Empty(element) = &event {
+                write(&mut writer, Event::Start(element.to_owned()))?;
+                appended.push_str(newline);
+                write(&mut writer, Event::Text(BytesText::from_escaped(appended)))?;
+                write(&mut writer, Event::End(element.to_end().into_owned()))?;
+                continue;
+            }
+            write(&mut writer, Event::Text(BytesText::from_escaped(appended)))?;
+        }
+
+        if let Some(space) = pending_space.take() {
+            write(&mut writer, Event::Text(BytesText::from_escaped(space)))?;
+        }
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => break,
+            _ => {}
+        }
+        write(&mut writer, event)?;
+    }
+    let rewritten =
+        String::from_utf8(writer.into_inner().into_inner()).map_err(|e| invalid(e.to_string()))?;
+    Ok((rewritten, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualifiers_map_to_languages_and_back() {
+        for (directory, language) in [
+            ("values", "default"),
+            ("values-de", "de"),
+            ("values-pt-rBR", "pt-BR"),
+            ("values-b+sr+Latn", "sr-Latn"),
+        ] {
+            assert_eq!(
+                Some(language.to_string()),
+                language_from_directory(directory)
+            );
+            assert_eq!(directory, directory_for_language(language));
+        }
+        assert_eq!(None, language_from_directory("values-night"));
+        assert_eq!(None, language_from_directory("values-v21"));
+    }
+
+    #[test]
+    fn escapes_survive_a_round_trip() {
+        assert_eq!("Don't say \"hi\"\n", unescape(r#"Don\'t say \"hi\"\n"#));
+        assert_eq!("a b", unescape("  a \n   b  "));
+        assert_eq!("  spaced  ", unescape("\"  spaced  \""));
+        assert_eq!("é", unescape("\\u00e9"));
+        for value in [
+            "Don't",
+            "@string/x",
+            "  two  spaces",
+            "back\\slash",
+            "a < b & c",
+        ] {
+            let xml = format!(
+                "<resources><string name=\"x\">{}</string></resources>",
+                escape_value(value)
+            );
+            assert_eq!(
+                vec![("x".to_string(), MobileValue::Text(value.to_string()))],
+                parse_strings(&xml).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rewrite_replaces_removes_and_appends_in_place() {
+        let content = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n  <!-- Buttons -->\n  <string name=\"save\">Save</string>\n  <string name=\"app\" translatable=\"false\">App</string>\n  <plurals name=\"files\">\n    <item quantity=\"one\">%d file</item>\n    <item quantity=\"other\">%d files</item>\n  </plurals>\n</resources>\n";
+        let files = MobileValue::Plural(BTreeMap::from([
+            (PluralCategory::One, "%d Datei".to_string()),
+            (PluralCategory::Other, "%d Dateien".to_string()),
+        ]));
+        let open = MobileValue::Text("Open".to_string());
+        let values = HashMap::from([("files", &files), ("open", &open)]);
+        let (rewritten, changed) = rewrite(content, &values, &HashSet::from(["save"])).unwrap();
+        assert!(changed);
+        assert_eq!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n  <!-- Buttons -->\n  <string name=\"app\" translatable=\"false\">App</string>\n  <plurals name=\"files\">\n    <item quantity=\"one\">%d Datei</item>\n    <item quantity=\"other\">%d Dateien</item>\n  </plurals>\n  <string name=\"open\">Open</string>\n</resources>\n",
+            rewritten
+        );
+        assert_eq!(
+            vec![("files".to_string(), files), ("open".to_string(), open)],
+            parse_strings(&rewritten).unwrap()
+        );
+    }
+}
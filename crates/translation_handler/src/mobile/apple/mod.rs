@@ -0,0 +1,260 @@
+pub mod strings;
+pub mod stringsdict;
+pub mod xcstrings;
+
+use crate::mobile::{collect_entries, MobileValue};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use strings::StringsFile;
+use stringsdict::Stringsdict;
+use xcstrings::StringCatalog;
+
+/// Strings table the app looks values up in.
+const TABLE: &str = "Localizable";
+const BASE_LANGUAGE: &str = "Base";
+
+/// Whether `path` is a String Catalog rather than a directory of `.lproj` folders.
+pub fn is_catalog(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "xcstrings")
+}
+
+pub fn is_location(path: &Path) -> bool {
+    if is_catalog(path) {
+        path.is_file()
+    } else {
+        !lproj_languages(path).is_empty()
+    }
+}
+
+fn table_file(directory: &Path, language: &str, extension: &str) -> PathBuf {
+    directory
+        .join(format!("{}.lproj", language))
+        .join(format!("{}.{}", TABLE, extension))
+}
+
+/// Languages of the `.lproj` folders that have the strings table.
+fn lproj_languages(directory: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|dir_entry| {
+            let name = dir_entry.file_name().to_string_lossy().to_string();
+            let language = name.strip_suffix(".lproj")?.to_string();
+            let has_table = ["strings", "stringsdict"]
+                .iter()
+                .any(|extension| table_file(directory, &language, extension).is_file());
+            has_table.then_some(language)
+        })
+        .collect();
+    languages.sort();
+    languages
+}
+
+/// The language the app is developed in. A catalog names it, for `.lproj` folders the
+/// default language of the settings is looked for, then English and `Base`.
+pub fn development_language(path: &Path, default_language: &str) -> Result<String, io::Error> {
+    if is_catalog(path) {
+        return Ok(StringCatalog::read(path)?.source_language());
+    }
+    let languages = lproj_languages(path);
+    let primary = default_language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default();
+    let language = [default_language, primary, "en", BASE_LANGUAGE]
+        .into_iter()
+        .find(|candidate| languages.iter().any(|language| language == candidate))
+        .or(languages.first().map(String::as_str))
+        .unwrap_or(default_language);
+    Ok(language.to_string())
+}
+
+pub fn get_languages(path: &Path) -> Result<Vec<String>, io::Error> {
+    if is_catalog(path) {
+        Ok(StringCatalog::read(path)?.languages())
+    } else {
+        Ok(lproj_languages(path))
+    }
+}
+
+/// Values of one `.lproj` folder. Keys in the stringsdict take precedence over the strings
+/// file, as they do at runtime.
+fn read_lproj_values(
+    directory: &Path,
+    language: &str,
+) -> Result<Vec<(String, MobileValue)>, io::Error> {
+    let strings_path = table_file(directory, language, "strings");
+    let stringsdict_path = table_file(directory, language, "stringsdict");
+    let plurals = if stringsdict_path.is_file() {
+        Stringsdict::read(&stringsdict_path)?.plurals()
+    } else {
+        Vec::new()
+    };
+    let mut values: Vec<(String, MobileValue)> = Vec::new();
+    if strings_path.is_file() {
+        values.extend(
+            StringsFile::read(&strings_path)?
+                .entries
+                .into_iter()
+                .filter(|entry| plurals.iter().all(|(key, _)| *key != entry.key))
+                .map(|entry| (entry.key, MobileValue::Text(entry.value))),
+        );
+    }
+    values.extend(
+        plurals
+            .into_iter()
+            .map(|(key, forms)| (key, MobileValue::Plural(forms))),
+    );
+    Ok(values)
+}
+
+pub fn get_translations(path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    if is_catalog(path) {
+        let catalog = StringCatalog::read(path)?;
+        let values = catalog
+            .languages()
+            .into_iter()
+            .map(|language| {
+                let values = catalog.values(&language);
+                (language, values)
+            })
+            .collect();
+        let stale_keys = catalog.stale_keys();
+        let mut entries = collect_entries(values);
+        for entry in &mut entries {
+            entry.in_use = !stale_keys.contains(&entry.key);
+        }
+        return Ok(entries);
+    }
+    let mut values = Vec::new();
+    for language in lproj_languages(path) {
+        let language_values = read_lproj_values(path, &language)?;
+        values.push((language, language_values));
+    }
+    Ok(collect_entries(values))
+}
+
+/// Writes values of one language. In `.lproj` folders plain values go to the strings file
+/// and plural values to the stringsdict, each key being removed from the other file.
+pub fn set_values(
+    path: &Path,
+    language: &str,
+    values: &[(String, MobileValue)],
+) -> Result<(), io::Error> {
+    if is_catalog(path) {
+        let mut catalog = StringCatalog::read(path)?;
+        for (key, value) in values {
+            catalog.set(key, language, value);
+        }
+        return catalog.write(path);
+    }
+
+    let strings_path = table_file(path, language, "strings");
+    let stringsdict_path = table_file(path, language, "stringsdict");
+    let mut strings = if strings_path.is_file() {
+        StringsFile::read(&strings_path)?
+    } else {
+        StringsFile::empty()
+    };
+    let mut stringsdict = if stringsdict_path.is_file() {
+        Some(Stringsdict::read(&stringsdict_path)?)
+    } else {
+        None
+    };
+    let (mut strings_changed, mut stringsdict_changed) = (false, false);
+    for (key, value) in values {
+        match value {
+            MobileValue::Text(text) => {
+                strings.set(key, text);
+                strings_changed = true;
+                if let Some(stringsdict) = stringsdict.as_mut() {
+                    stringsdict_changed |= stringsdict.remove(key);
+                }
+            }
+            MobileValue::Plural(forms) => {
+                stringsdict
+                    .get_or_insert_with(Stringsdict::default)
+                    .set_plural(key, forms);
+                stringsdict_changed = true;
+                strings_changed |= strings.remove(key);
+            }
+        }
+    }
+    if strings_changed {
+        fs::create_dir_all(strings_path.parent().unwrap())?;
+        strings.write(&strings_path)?;
+    }
+    if let (true, Some(stringsdict)) = (stringsdict_changed, stringsdict) {
+        fs::create_dir_all(stringsdict_path.parent().unwrap())?;
+        stringsdict.write(&stringsdict_path)?;
+    }
+    Ok(())
+}
+
+/// Whether any language already has `key`.
+pub fn contains_key(path: &Path, key: &str) -> Result<bool, io::Error> {
+    Ok(get_translations(path)?.iter().any(|entry| entry.key == key))
+}
+
+pub fn remove_keys(path: &Path, keys: &[String]) -> Result<(), io::Error> {
+    if is_catalog(path) {
+        let mut catalog = StringCatalog::read(path)?;
+        let mut removed = false;
+        for key in keys {
+            removed |= catalog.remove(key);
+        }
+        if removed {
+            catalog.write(path)?;
+        }
+        return Ok(());
+    }
+    for language in lproj_languages(path) {
+        let strings_path = table_file(path, &language, "strings");
+        if strings_path.is_file() {
+            let mut strings = StringsFile::read(&strings_path)?;
+            let mut removed = false;
+            for key in keys {
+                removed |= strings.remove(key);
+            }
+            if removed {
+                strings.write(&strings_path)?;
+            }
+        }
+        let stringsdict_path = table_file(path, &language, "stringsdict");
+        if stringsdict_path.is_file() {
+            let mut stringsdict = Stringsdict::read(&stringsdict_path)?;
+            let mut removed = false;
+            for key in keys {
+                removed |= stringsdict.remove(key);
+            }
+            if removed {
+                stringsdict.write(&stringsdict_path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Creates an empty strings file in a new `.lproj` folder. A catalog has no list of
+/// languages to add to: a language appears once a key has a value in it.
+pub fn add_language(path: &Path, language: &str) -> Result<(), io::Error> {
+    if is_catalog(path) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "String Catalogs list a language once a key is translated into it",
+        ));
+    }
+    let file = table_file(path, language, "strings");
+    if file.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", file.display()),
+        ));
+    }
+    fs::create_dir_all(file.parent().unwrap())?;
+    fs::write(&file, "")
+}
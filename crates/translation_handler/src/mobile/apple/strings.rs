@@ -0,0 +1,345 @@
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+/// A `"key" = "value";` statement and where it sits in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringsEntry {
+    pub key: String,
+    pub value: String,
+    /// The statement with the comment right above it, up to and including its line break
+    span: Range<usize>,
+    /// The quoted value
+    value_span: Range<usize>,
+}
+
+/// An old-style `.strings` file. Edits are made to the text, so comments, order and the
+/// encoding of the file stay as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringsFile {
+    content: String,
+    encoding: Encoding,
+    pub entries: Vec<StringsEntry>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, io::Error> {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|e| invalid(e.to_string()))
+}
+
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+struct Parser<'a> {
+    content: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, io::Error> {
+        let line = self.content[..self.pos].matches('\n').count() + 1;
+        Err(invalid(format!("line {}: {}", line, message)))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.content[self.pos..]
+    }
+
+    /// Skips whitespace and comments. Returns where the last comment started when only
+    /// whitespace without an empty line follows it.
+    fn skip_trivia(&mut self) -> Result<Option<usize>, io::Error> {
+        let mut comment_start = None;
+        loop {
+            let whitespace = self.rest().len() - self.rest().trim_start().len();
+            if self.rest()[..whitespace].matches('\n').count() > 1 {
+                comment_start = None;
+            }
+            self.pos += whitespace;
+            if self.rest().starts_with("/*") {
+                comment_start = Some(self.pos);
+                match self.rest().find("*/") {
+                    Some(end) => self.pos += end + 2,
+                    None => return self.error("unterminated comment"),
+                }
+            } else if self.rest().starts_with("//") {
+                comment_start = Some(self.pos);
+                self.pos += self.rest().find('\n').unwrap_or(self.rest().len());
+            } else {
+                return Ok(comment_start);
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, io::Error> {
+        if !self.rest().starts_with('"') {
+            // Unquoted keys as allowed by the old plist syntax
+            let length = self
+                .rest()
+                .find(|c: char| !(c.is_alphanumeric() || "_.$-:/".contains(c)))
+                .unwrap_or(self.rest().len());
+            if length == 0 {
+                return self.error("expected a quoted string");
+            }
+            let token = self.rest()[..length].to_string();
+            self.pos += length;
+            return Ok(token);
+        }
+        let mut value = String::new();
+        let mut chars = self.rest()[1..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += index + 2;
+                    return Ok(value);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('0') => value.push('\0'),
+                    Some('U') | Some('u') => {
+                        let code: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                            Some(c) => value.push(c),
+                            None => return self.error("invalid unicode escape"),
+                        }
+                    }
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        self.error("unterminated string")
+    }
+
+    fn expect(&mut self, token: char) -> Result<(), io::Error> {
+        self.skip_trivia()?;
+        if self.rest().starts_with(token) {
+            self.pos += token.len_utf8();
+            Ok(())
+        } else {
+            self.error(&format!("expected `{}`", token))
+        }
+    }
+}
+
+impl StringsFile {
+    pub fn parse(bytes: &[u8]) -> Result<Self, io::Error> {
+        let (encoding, content) = match bytes {
+            [0xFF, 0xFE, rest @ ..] => (Encoding::Utf16Le, decode_utf16(rest, u16::from_le_bytes)?),
+            [0xFE, 0xFF, rest @ ..] => (Encoding::Utf16Be, decode_utf16(rest, u16::from_be_bytes)?),
+            [0xEF, 0xBB, 0xBF, rest @ ..] => (
+                Encoding::Utf8Bom,
+                String::from_utf8(rest.to_vec()).map_err(|e| invalid(e.to_string()))?,
+            ),
+            _ => (
+                Encoding::Utf8,
+                String::from_utf8(bytes.to_vec()).map_err(|e| invalid(e.to_string()))?,
+            ),
+        };
+        Self::from_content(content, encoding)
+    }
+
+    fn from_content(content: String, encoding: Encoding) -> Result<Self, io::Error> {
+        let mut entries = Vec::new();
+        let mut parser = Parser {
+            content: &content,
+            pos: 0,
+        };
+        loop {
+            let comment_start = parser.skip_trivia()?;
+            if parser.rest().is_empty() {
+                break;
+            }
+            let statement_start = parser.pos;
+            let key = parser.string()?;
+            parser.expect('=')?;
+            parser.skip_trivia()?;
+            let value_start = parser.pos;
+            let value = parser.string()?;
+            let value_end = parser.pos;
+            parser.expect(';')?;
+
+            let start = comment_start.unwrap_or(statement_start);
+            let line_start = content[..start].rfind('\n').map_or(0, |index| index + 1);
+            let start = if content[line_start..start].trim().is_empty() {
+                line_start
+            } else {
+                start
+            };
+            let rest = &content[parser.pos..];
+            let end = match rest.find('\n') {
+                Some(index) if rest[..index].trim().is_empty() => parser.pos + index + 1,
+                _ => parser.pos,
+            };
+            entries.push(StringsEntry {
+                key,
+                value,
+                span: start..end,
+                value_span: value_start..value_end,
+            });
+        }
+        Ok(StringsFile {
+            content,
+            encoding,
+            entries,
+        })
+    }
+
+    pub fn empty() -> Self {
+        StringsFile {
+            content: String::new(),
+            encoding: Encoding::Utf8,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let bytes = fs::read(path)?;
+        Self::parse(&bytes)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.encoding {
+            Encoding::Utf8 => self.content.as_bytes().to_vec(),
+            Encoding::Utf8Bom => [&[0xEF, 0xBB, 0xBF], self.content.as_bytes()].concat(),
+            Encoding::Utf16Le => [0xFF, 0xFE]
+                .into_iter()
+                .chain(self.content.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+            Encoding::Utf16Be => [0xFE, 0xFF]
+                .into_iter()
+                .chain(self.content.encode_utf16().flat_map(u16::to_be_bytes))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+
+    fn splice(&mut self, range: Range<usize>, text: &str) {
+        self.content.replace_range(range, text);
+        // Offsets of the following entries moved, so parse the edited text again
+        *self = Self::from_content(std::mem::take(&mut self.content), self.encoding)
+            .expect("an edit of a valid .strings file stays valid");
+    }
+
+    /// Replaces the value of `key`, or appends a statement when the file has no such key.
+    pub fn set(&mut self, key: &str, value: &str) {
+        if let Some(entry) = self.entries.iter().find(|entry| entry.key == key) {
+            let range = entry.value_span.clone();
+            self.splice(range, &quote(value));
+            return;
+        }
+        let mut statement = String::new();
+        if !self.content.is_empty() && !self.content.ends_with('\n') {
+            statement.push('\n');
+        }
+        // Follow files that keep a blank line between statements
+        if self.content.contains(";\n\n") && !self.content.ends_with("\n\n") {
+            statement.push('\n');
+        }
+        statement.push_str(&format!("{} = {};\n", quote(key), quote(value)));
+        let end = self.content.len();
+        self.splice(end..end, &statement);
+    }
+
+    /// Removes the statement of `key` with the comment right above it.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(entry) = self.entries.iter().find(|entry| entry.key == key) else {
+            return false;
+        };
+        let mut range = entry.span.clone();
+        // Drop the blank line that separated the statement from the next one
+        if self.content[range.end..].starts_with('\n')
+            && (range.start == 0 || self.content[..range.start].ends_with("\n\n"))
+        {
+            range.end += 1;
+        }
+        self.splice(range, "");
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "/* Title of the save button */\n\"save\" = \"Save\";\n\n/* Shown when done */\n\"done\" = \"Done \\\"now\\\"\";\n\nlegacy_key = \"Line\\nbreak\";\n";
+
+    #[test]
+    fn parse_reads_quoted_and_bare_keys() {
+        let file = StringsFile::parse(CONTENT.as_bytes()).unwrap();
+        let pairs: Vec<(&str, &str)> = file
+            .entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("save", "Save"),
+                ("done", "Done \"now\""),
+                ("legacy_key", "Line\nbreak")
+            ],
+            pairs
+        );
+    }
+
+    #[test]
+    fn edits_keep_comments_and_encoding() {
+        let bytes: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(CONTENT.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let mut file = StringsFile::parse(&bytes).unwrap();
+        file.set("save", "Sichern");
+        assert!(file.remove("done"));
+        file.set("open", "Öffnen");
+
+        let file = StringsFile::parse(&file.to_bytes()).unwrap();
+        assert_eq!(Encoding::Utf16Le, file.encoding);
+        assert_eq!(
+            "/* Title of the save button */\n\"save\" = \"Sichern\";\n\nlegacy_key = \"Line\\nbreak\";\n\n\"open\" = \"Öffnen\";\n",
+            file.content
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use crate::plural::PluralCategory;
+use quick_xml::escape::partial_escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const PLIST_HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n";
+const FORMAT_KEY: &str = "NSStringLocalizedFormatKey";
+const SPEC_TYPE_KEY: &str = "NSStringFormatSpecTypeKey";
+const PLURAL_RULE_TYPE: &str = "NSStringPluralRuleType";
+const VALUE_TYPE_KEY: &str = "NSStringFormatValueTypeKey";
+/// Variable name used for keys this crate adds
+const VARIABLE: &str = "count";
+
+/// The property list values a stringsdict file is made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlistValue {
+    String(String),
+    Integer(String),
+    Real(String),
+    Bool(bool),
+    Array(Vec<PlistValue>),
+    Dict(Vec<(String, PlistValue)>),
+}
+
+impl PlistValue {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            PlistValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn as_dict(&self) -> Option<&Vec<(String, PlistValue)>> {
+        match self {
+            PlistValue::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+fn get<'a>(dict: &'a [(String, PlistValue)], key: &str) -> Option<&'a PlistValue> {
+    dict.iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value)
+}
+
+/// A `Localizable.stringsdict` file. Each key has a format with one or more variables; the
+/// first variable with plural rules is the one exposed as plural forms.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stringsdict {
+    pub entries: Vec<(String, PlistValue)>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn xml_error(error: quick_xml::Error) -> io::Error {
+    invalid(error.to_string())
+}
+
+/// Values on the stack while reading: containers being filled and the text of scalars.
+enum Frame {
+    Array(Vec<PlistValue>),
+    Dict(Vec<(String, PlistValue)>, Option<String>),
+    Text(String),
+}
+
+fn push_value(stack: &mut [Frame], value: PlistValue) -> Result<Option<PlistValue>, io::Error> {
+    match stack.last_mut() {
+        None => Ok(Some(value)),
+        Some(Frame::Array(values)) => {
+            values.push(value);
+            Ok(None)
+        }
+        Some(Frame::Dict(entries, key)) => {
+            let key = key
+                .take()
+                .ok_or_else(|| invalid("dict value without a <key>".to_string()))?;
+            entries.push((key, value));
+            Ok(None)
+        }
+        Some(Frame::Text(_)) => Err(invalid("element inside a scalar value".to_string())),
+    }
+}
+
+impl Stringsdict {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(false);
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut root = None;
+        loop {
+            let position = reader.buffer_position();
+            let event = reader
+                .read_event()
+                .map_err(|e| invalid(format!("at byte {}: {}", position, e)))?;
+            match event {
+                Event::Start(ref element) => match element.local_name().as_ref() {
+                    b"plist" => {}
+                    b"dict" => stack.push(Frame::Dict(Vec::new(), None)),
+                    b"array" => stack.push(Frame::Array(Vec::new())),
+                    b"key" | b"string" | b"integer" | b"real" => {
+                        stack.push(Frame::Text(String::new()))
+                    }
+                    other => {
+                        return Err(invalid(format!(
+                            "unsupported plist element <{}>",
+                            String::from_utf8_lossy(other)
+                        )))
+                    }
+                },
+                Event::Empty(ref element) => {
+                    let value = match element.local_name().as_ref() {
+                        b"true" => PlistValue::Bool(true),
+                        b"false" => PlistValue::Bool(false),
+                        b"string" => PlistValue::String(String::new()),
+                        b"dict" => PlistValue::Dict(Vec::new()),
+                        b"array" => PlistValue::Array(Vec::new()),
+                        _ => continue,
+                    };
+                    if let Some(value) = push_value(&mut stack, value)? {
+                        root = Some(value);
+                    }
+                }
+                Event::Text(ref text) => {
+                    if let Some(Frame::Text(value)) = stack.last_mut() {
+                        value.push_str(&text.unescape().map_err(xml_error)?);
+                    }
+                }
+                Event::End(ref element) => {
+                    let value = match (element.local_name().as_ref(), stack.pop()) {
+                        (b"plist", None) => continue,
+                        (b"key", Some(Frame::Text(key))) => {
+                            match stack.last_mut() {
+                                Some(Frame::Dict(_, pending)) => *pending = Some(key),
+                                _ => return Err(invalid("<key> outside a dict".to_string())),
+                            }
+                            continue;
+                        }
+                        (b"string", Some(Frame::Text(text))) => PlistValue::String(text),
+                        (b"integer", Some(Frame::Text(text))) => PlistValue::Integer(text),
+                        (b"real", Some(Frame::Text(text))) => PlistValue::Real(text),
+                        (b"dict", Some(Frame::Dict(entries, _))) => PlistValue::Dict(entries),
+                        (b"array", Some(Frame::Array(values))) => PlistValue::Array(values),
+                        (name, _) => {
+                            return Err(invalid(format!(
+                                "unexpected </{}>",
+                                String::from_utf8_lossy(name)
+                            )))
+                        }
+                    };
+                    if let Some(value) = push_value(&mut stack, value)? {
+                        root = Some(value);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        match root {
+            Some(PlistValue::Dict(entries)) => Ok(Stringsdict { entries }),
+            None => Ok(Stringsdict::default()),
+            Some(_) => Err(invalid("the plist root is not a dict".to_string())),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_xml())
+    }
+
+    /// Name of the first variable of a key that has plural rules.
+    fn plural_variable(rule: &[(String, PlistValue)]) -> Option<&str> {
+        rule.iter()
+            .find(|(_, value)| {
+                value.as_dict().is_some_and(|variable| {
+                    get(variable, SPEC_TYPE_KEY).and_then(PlistValue::as_str)
+                        == Some(PLURAL_RULE_TYPE)
+                })
+            })
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Plural forms of every key, in file order.
+    pub fn plurals(&self) -> Vec<(String, BTreeMap<PluralCategory, String>)> {
+        self.entries
+            .iter()
+            .filter_map(|(key, rule)| {
+                let rule = rule.as_dict()?;
+                let variable = get(rule, Self::plural_variable(rule)?)?.as_dict()?;
+                let forms = variable
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((
+                            name.parse::<PluralCategory>().ok()?,
+                            value.as_str()?.to_string(),
+                        ))
+                    })
+                    .collect();
+                Some((key.clone(), forms))
+            })
+            .collect()
+    }
+
+    /// Replaces the plural forms of `key`, keeping its format and value type. Keys that are not
+    /// in the file yet get a format with a single `count` variable.
+    pub fn set_plural(&mut self, key: &str, forms: &BTreeMap<PluralCategory, String>) {
+        let form_values = forms.iter().map(|(category, form)| {
+            (
+                category.as_str().to_string(),
+                PlistValue::String(form.clone()),
+            )
+        });
+
+        if let Some((_, PlistValue::Dict(rule))) =
+            self.entries.iter_mut().find(|(name, _)| name == key)
+        {
+            if let Some(variable) = Self::plural_variable(rule).map(str::to_string) {
+                if let Some((_, PlistValue::Dict(entries))) =
+                    rule.iter_mut().find(|(name, _)| *name == variable)
+                {
+                    entries.retain(|(name, _)| name.parse::<PluralCategory>().is_err());
+                    entries.extend(form_values);
+                    return;
+                }
+            }
+        }
+
+        let value_type = if forms.values().any(|form| form.contains("%lld")) {
+            "lld"
+        } else if forms.values().any(|form| form.contains("%@")) {
+            "@"
+        } else {
+            "d"
+        };
+        let mut variable = vec![
+            (
+                SPEC_TYPE_KEY.to_string(),
+                PlistValue::String(PLURAL_RULE_TYPE.to_string()),
+            ),
+            (
+                VALUE_TYPE_KEY.to_string(),
+                PlistValue::String(value_type.to_string()),
+            ),
+        ];
+        variable.extend(form_values);
+        let rule = PlistValue::Dict(vec![
+            (
+                FORMAT_KEY.to_string(),
+                PlistValue::String(format!("%#@{}@", VARIABLE)),
+            ),
+            (VARIABLE.to_string(), PlistValue::Dict(variable)),
+        ]);
+        self.entries.retain(|(name, _)| name != key);
+        self.entries.push((key.to_string(), rule));
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        let length = self.entries.len();
+        self.entries.retain(|(name, _)| name != key);
+        self.entries.len() != length
+    }
+
+    /// Writes the plist the way Xcode does, indented with tabs.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(PLIST_HEADER);
+        write_value(&mut xml, &PlistValue::Dict(self.entries.clone()), 0);
+        xml.push_str("</plist>\n");
+        xml
+    }
+}
+
+fn write_value(xml: &mut String, value: &PlistValue, depth: usize) {
+    let indent = "\t".repeat(depth);
+    match value {
+        PlistValue::String(text) => xml.push_str(&format!(
+            "{}<string>{}</string>\n",
+            indent,
+            partial_escape(text)
+        )),
+        PlistValue::Integer(text) => {
+            xml.push_str(&format!("{}<integer>{}</integer>\n", indent, text))
+        }
+        PlistValue::Real(text) => xml.push_str(&format!("{}<real>{}</real>\n", indent, text)),
+        PlistValue::Bool(true) => xml.push_str(&format!("{}<true/>\n", indent)),
+        PlistValue::Bool(false) => xml.push_str(&format!("{}<false/>\n", indent)),
+        PlistValue::Array(values) => {
+            xml.push_str(&format!("{}<array>\n", indent));
+            for value in values {
+                write_value(xml, value, depth + 1);
+            }
+            xml.push_str(&format!("{}</array>\n", indent));
+        }
+        PlistValue::Dict(entries) => {
+            xml.push_str(&format!("{}<dict>\n", indent));
+            for (key, value) in entries {
+                xml.push_str(&format!("{}\t<key>{}</key>\n", indent, partial_escape(key)));
+                write_value(xml, value, depth + 1);
+            }
+            xml.push_str(&format!("{}</dict>\n", indent));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>files_selected</key>
+	<dict>
+		<key>NSStringLocalizedFormatKey</key>
+		<string>%#@files@ in %@</string>
+		<key>files</key>
+		<dict>
+			<key>NSStringFormatSpecTypeKey</key>
+			<string>NSStringPluralRuleType</string>
+			<key>NSStringFormatValueTypeKey</key>
+			<string>lld</string>
+			<key>one</key>
+			<string>%lld file</string>
+			<key>other</key>
+			<string>%lld files</string>
+		</dict>
+	</dict>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn plural_forms_are_read_and_replaced() {
+        let mut stringsdict = Stringsdict::parse(CONTENT).unwrap();
+        assert_eq!(CONTENT, stringsdict.to_xml());
+        let (key, forms) = &stringsdict.plurals()[0];
+        assert_eq!("files_selected", key);
+        assert_eq!("%lld files", forms[&PluralCategory::Other]);
+
+        let forms = BTreeMap::from([
+            (PluralCategory::One, "%lld Datei".to_string()),
+            (PluralCategory::Other, "%lld Dateien".to_string()),
+        ]);
+        stringsdict.set_plural("files_selected", &forms);
+        stringsdict.set_plural("photos", &forms);
+        let stringsdict = Stringsdict::parse(&stringsdict.to_xml()).unwrap();
+        assert_eq!(
+            vec![
+                ("files_selected".to_string(), forms.clone()),
+                ("photos".to_string(), forms)
+            ],
+            stringsdict.plurals()
+        );
+        let rule = stringsdict.entries[0].1.as_dict().unwrap();
+        assert_eq!(
+            Some("%#@files@ in %@"),
+            get(rule, FORMAT_KEY).and_then(PlistValue::as_str)
+        );
+    }
+}
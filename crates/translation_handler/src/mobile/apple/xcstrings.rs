@@ -0,0 +1,319 @@
+use crate::mobile::MobileValue;
+use crate::plural::PluralCategory;
+use serde::Serialize;
+use serde_json::ser::{Formatter, PrettyFormatter};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A String Catalog (`.xcstrings`). Keys without a value in the source language are their own
+/// source text, as in Xcode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringCatalog {
+    json: Value,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Xcode writes `"key" : value` with two spaces of indentation.
+struct XcodeFormatter<'a>(PrettyFormatter<'a>);
+
+impl Formatter for XcodeFormatter<'_> {
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.0.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.0.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b" : ")
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.0.end_object_value(writer)
+    }
+}
+
+/// Inserts `key` before the first key that sorts after it, the order Xcode keeps.
+fn insert_sorted(map: &mut Map<String, Value>, key: &str, value: Value) {
+    if let Some(existing) = map.get_mut(key) {
+        *existing = value;
+        return;
+    }
+    let entries = std::mem::take(map);
+    let mut inserted = false;
+    for (name, existing) in entries {
+        if !inserted && name.as_str() > key {
+            map.insert(key.to_string(), value.clone());
+            inserted = true;
+        }
+        map.insert(name, existing);
+    }
+    if !inserted {
+        map.insert(key.to_string(), value);
+    }
+}
+
+fn string_unit(value: &str) -> Value {
+    json!({ "stringUnit": { "state": "translated", "value": value } })
+}
+
+fn unit_value(localization: &Value) -> Option<String> {
+    localization
+        .pointer("/stringUnit/value")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+impl StringCatalog {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let json: Value = serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?;
+        if !json.get("strings").is_some_and(Value::is_object) {
+            return Err(invalid(
+                "not a String Catalog: no `strings` object".to_string(),
+            ));
+        }
+        Ok(StringCatalog { json })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_json()?)
+    }
+
+    pub fn to_json(&self) -> Result<String, io::Error> {
+        let mut serializer = serde_json::Serializer::with_formatter(
+            Vec::new(),
+            XcodeFormatter(PrettyFormatter::with_indent(b"  ")),
+        );
+        self.json
+            .serialize(&mut serializer)
+            .map_err(|e| invalid(e.to_string()))?;
+        let mut content =
+            String::from_utf8(serializer.into_inner()).map_err(|e| invalid(e.to_string()))?;
+        content.push('\n');
+        Ok(content)
+    }
+
+    pub fn source_language(&self) -> String {
+        self.json
+            .get("sourceLanguage")
+            .and_then(Value::as_str)
+            .unwrap_or("en")
+            .to_string()
+    }
+
+    fn strings(&self) -> &Map<String, Value> {
+        self.json["strings"].as_object().unwrap()
+    }
+
+    fn strings_mut(&mut self) -> &mut Map<String, Value> {
+        self.json["strings"].as_object_mut().unwrap()
+    }
+
+    /// The source language and every language with at least one localization.
+    pub fn languages(&self) -> Vec<String> {
+        let source_language = self.source_language();
+        let mut languages: Vec<String> = self
+            .strings()
+            .values()
+            .filter_map(|string| string.get("localizations")?.as_object())
+            .flat_map(|localizations| localizations.keys().cloned())
+            .filter(|language| *language != source_language)
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages.insert(0, source_language);
+        languages
+    }
+
+    /// Values of `language` in catalog order.
+    pub fn values(&self, language: &str) -> Vec<(String, MobileValue)> {
+        let is_source = language == self.source_language();
+        self.strings()
+            .iter()
+            .filter_map(|(key, string)| {
+                let localization = string.pointer(&format!(
+                    "/localizations/{}",
+                    language.replace('~', "~0").replace('/', "~1")
+                ));
+                let value = match localization {
+                    Some(localization) => match localization.pointer("/variations/plural") {
+                        Some(Value::Object(plural)) => MobileValue::Plural(
+                            plural
+                                .iter()
+                                .filter_map(|(category, form)| {
+                                    Some((
+                                        category.parse::<PluralCategory>().ok()?,
+                                        unit_value(form)?,
+                                    ))
+                                })
+                                .collect(),
+                        ),
+                        _ => MobileValue::Text(unit_value(localization)?),
+                    },
+                    None if is_source => MobileValue::Text(key.clone()),
+                    None => return None,
+                };
+                Some((key.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Keys Xcode no longer found in the code.
+    pub fn stale_keys(&self) -> Vec<String> {
+        self.strings()
+            .iter()
+            .filter(|(_, string)| {
+                string.get("extractionState").and_then(Value::as_str) == Some("stale")
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.strings().contains_key(key)
+    }
+
+    /// Sets the value of `key` in `language`. Keys that are not in the catalog yet are added as
+    /// manually managed, since Xcode did not extract them.
+    pub fn set(&mut self, key: &str, language: &str, value: &MobileValue) {
+        if !self.contains(key) {
+            insert_sorted(
+                self.strings_mut(),
+                key,
+                json!({ "extractionState": "manual", "localizations": {} }),
+            );
+        }
+        let string = self.strings_mut()[key].as_object_mut().unwrap();
+        if !string.get("localizations").is_some_and(Value::is_object) {
+            string.insert("localizations".to_string(), json!({}));
+        }
+        let localization = match value {
+            MobileValue::Text(text) => string_unit(text),
+            MobileValue::Plural(forms) => {
+                let plural: Map<String, Value> = forms
+                    .iter()
+                    .map(|(category, form)| (category.as_str().to_string(), string_unit(form)))
+                    .collect();
+                json!({ "variations": { "plural": plural } })
+            }
+        };
+        let localizations = string["localizations"].as_object_mut().unwrap();
+        insert_sorted(localizations, language, localization);
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.strings_mut().shift_remove(key).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"{
+  "sourceLanguage" : "en",
+  "strings" : {
+    "%lld files" : {
+      "localizations" : {
+        "de" : {
+          "variations" : {
+            "plural" : {
+              "one" : {
+                "stringUnit" : {
+                  "state" : "translated",
+                  "value" : "%lld Datei"
+                }
+              },
+              "other" : {
+                "stringUnit" : {
+                  "state" : "translated",
+                  "value" : "%lld Dateien"
+                }
+              }
+            }
+          }
+        }
+      }
+    },
+    "Old" : {
+      "extractionState" : "stale"
+    }
+  },
+  "version" : "1.0"
+}
+"#;
+
+    #[test]
+    fn catalog_values_and_xcode_formatting() {
+        let mut catalog = StringCatalog::parse(CONTENT).unwrap();
+        assert_eq!(CONTENT, catalog.to_json().unwrap());
+        assert_eq!(vec!["en", "de"], catalog.languages());
+        assert_eq!(
+            vec![
+                (
+                    "%lld files".to_string(),
+                    MobileValue::Text("%lld files".to_string())
+                ),
+                ("Old".to_string(), MobileValue::Text("Old".to_string())),
+            ],
+            catalog.values("en")
+        );
+        assert_eq!(vec!["Old".to_string()], catalog.stale_keys());
+
+        catalog.set("Cancel", "de", &MobileValue::Text("Abbrechen".to_string()));
+        assert!(catalog.remove("Old"));
+        let catalog = StringCatalog::parse(&catalog.to_json().unwrap()).unwrap();
+        let keys: Vec<&String> = catalog.strings().keys().collect();
+        assert_eq!(vec!["%lld files", "Cancel"], keys);
+        assert_eq!(
+            vec![
+                ("%lld files".to_string(), catalog.values("de")[0].1.clone()),
+                (
+                    "Cancel".to_string(),
+                    MobileValue::Text("Abbrechen".to_string())
+                ),
+            ],
+            catalog.values("de")
+        );
+    }
+}
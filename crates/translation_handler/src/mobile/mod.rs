@@ -0,0 +1,145 @@
+pub mod android;
+pub mod apple;
+
+use crate::frontend::updater::UpdatedKeyValues;
+use crate::plural::{required_categories, PluralCategory};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::Path;
+
+/// Native app platforms whose string files can be a location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobilePlatform {
+    Android,
+    Apple,
+}
+
+impl MobilePlatform {
+    /// The platform a picked path belongs to: an Android `res` directory, a directory with
+    /// `.lproj` folders or a `.xcstrings` String Catalog.
+    pub fn detect(path: &Path) -> Option<Self> {
+        if android::is_resource_directory(path) {
+            Some(MobilePlatform::Android)
+        } else if apple::is_location(path) {
+            Some(MobilePlatform::Apple)
+        } else {
+            None
+        }
+    }
+
+    /// Tag the location is stored with, which picks its translation format.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            MobilePlatform::Android => "ANDROID",
+            MobilePlatform::Apple => "IOS",
+        }
+    }
+
+    pub fn source_language(
+        &self,
+        path: &Path,
+        default_language: &str,
+    ) -> Result<String, io::Error> {
+        match self {
+            MobilePlatform::Android => Ok(android::DEFAULT_LANGUAGE.to_string()),
+            MobilePlatform::Apple => apple::development_language(path, default_language),
+        }
+    }
+
+    pub fn get_translations(&self, path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+        match self {
+            MobilePlatform::Android => android::get_translations(path),
+            MobilePlatform::Apple => apple::get_translations(path),
+        }
+    }
+}
+
+/// A value that is either a plain string or one string per plural category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MobileValue {
+    Text(String),
+    Plural(BTreeMap<PluralCategory, String>),
+}
+
+impl MobileValue {
+    /// The text shown for the value, which is the `other` form of a plural.
+    pub fn text(&self) -> String {
+        match self {
+            MobileValue::Text(text) => text.clone(),
+            MobileValue::Plural(forms) => forms
+                .get(&PluralCategory::Other)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Value per language of an update. Languages with plural forms get those, the others their
+/// plain value.
+pub fn updated_values(key: &UpdatedKeyValues) -> BTreeMap<String, MobileValue> {
+    let mut values: BTreeMap<String, MobileValue> = key
+        .translation_values
+        .iter()
+        .map(|(language, value)| (language.clone(), MobileValue::Text(value.clone())))
+        .collect();
+    for (language, forms) in &key.plural_values {
+        if !forms.is_empty() {
+            let forms = forms
+                .iter()
+                .map(|(category, form)| (*category, form.clone()))
+                .collect();
+            values.insert(language.clone(), MobileValue::Plural(forms));
+        }
+    }
+    values
+}
+
+/// Collects the values of every language into entries, in the order keys first appear.
+/// Languages without a value for a key get an empty translation.
+fn collect_entries(values: Vec<(String, Vec<(String, MobileValue)>)>) -> Vec<TranslationEntry> {
+    let languages: Vec<String> = values
+        .iter()
+        .map(|(language, _)| language.clone())
+        .collect();
+    let mut entries: Vec<TranslationEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (language, language_values) in values {
+        for (key, value) in language_values {
+            let position = *positions.entry(key.clone()).or_insert_with(|| {
+                entries.push(TranslationEntry {
+                    key: key.clone(),
+                    value: key.clone(),
+                    in_use: true,
+                    ..Default::default()
+                });
+                entries.len() - 1
+            });
+            let entry = &mut entries[position];
+            entry.translations.insert(language.clone(), value.text());
+            if let MobileValue::Plural(forms) = value {
+                let missing: Vec<String> = required_categories(&language)
+                    .iter()
+                    .filter(|category| !forms.contains_key(category))
+                    .map(|category| category.as_str().to_string())
+                    .collect();
+                if !missing.is_empty() {
+                    entry.missing_plural_forms.insert(language.clone(), missing);
+                }
+                entry.plurals.insert(
+                    language.clone(),
+                    forms
+                        .into_iter()
+                        .map(|(category, form)| (category.as_str().to_string(), form))
+                        .collect(),
+                );
+            }
+        }
+    }
+    for entry in &mut entries {
+        for language in &languages {
+            entry.translations.entry(language.clone()).or_default();
+        }
+    }
+    entries
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// CLDR plural category of a cardinal number.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, specta::Type,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+pub const ALL_CATEGORIES: [PluralCategory; 6] = [
+    PluralCategory::Zero,
+    PluralCategory::One,
+    PluralCategory::Two,
+    PluralCategory::Few,
+    PluralCategory::Many,
+    PluralCategory::Other,
+];
+
+impl PluralCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for PluralCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PluralCategory {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_CATEGORIES
+            .into_iter()
+            .find(|category| category.as_str() == s)
+            .ok_or_else(|| format!("unknown plural category `{}`", s))
+    }
+}
+
+/// Cardinal categories a language needs, following the CLDR plural rules that
+/// `Intl.PluralRules` (and with it i18next) uses.
+pub fn required_categories(language: &str) -> &'static [PluralCategory] {
+    use PluralCategory::*;
+    let primary = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match primary.as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "my" | "km" => &[Other],
+        "es" | "fr" | "it" | "pt" | "ca" => &[One, Many, Other],
+        "pl" | "ru" | "uk" | "be" | "cs" | "sk" | "lt" => &[One, Few, Many, Other],
+        "hr" | "sr" | "bs" | "ro" | "mo" => &[One, Few, Other],
+        "sl" => &[One, Two, Few, Other],
+        "lv" => &[Zero, One, Other],
+        "he" | "iw" => &[One, Two, Other],
+        "ga" => &[One, Two, Few, Many, Other],
+        "ar" | "cy" => &[Zero, One, Two, Few, Many, Other],
+        _ => &[One, Other],
+    }
+}
+
+/// Key of a plural variant in the i18next JSON format, e.g. `items_one`.
+pub fn plural_key(key: &str, category: PluralCategory) -> String {
+    format!("{}_{}", key, category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_categories_follow_the_primary_language() {
+        assert_eq!(
+            &[PluralCategory::One, PluralCategory::Other],
+            required_categories("en-GB")
+        );
+        assert_eq!(
+            &[
+                PluralCategory::One,
+                PluralCategory::Few,
+                PluralCategory::Many,
+                PluralCategory::Other
+            ],
+            required_categories("pl-PL")
+        );
+        assert_eq!(&[PluralCategory::Other], required_categories("ja"));
+    }
+}
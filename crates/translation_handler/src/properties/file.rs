@@ -0,0 +1,333 @@
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    /// ISO 8859-1, which `Properties.load` reads before Java 9
+    Latin1,
+}
+
+/// A `key = value` pair and the lines it is written on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertiesEntry {
+    pub key: String,
+    pub value: String,
+    /// The comment right above the pair and its continued lines
+    span: Range<usize>,
+    /// The lines of the pair only
+    lines: Range<usize>,
+    /// What is written between the key and the value, such as ` = `
+    separator: String,
+}
+
+/// A Java `.properties` file. Edits replace only the lines of the changed pair, so comments,
+/// blank lines and order stay as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertiesFile {
+    lines: Vec<String>,
+    newline: &'static str,
+    encoding: Encoding,
+    /// Whether characters outside ASCII are written as `\uXXXX`, as in files that have none
+    escape_unicode: bool,
+    pub entries: Vec<PropertiesEntry>,
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with(['#', '!'])
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Whether a line ends with an odd number of backslashes, which continues it on the next.
+fn continues(line: &str) -> bool {
+    (line.len() - line.trim_end_matches('\\').len()) % 2 == 1
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '=' | ':' | ' ' | '\t' | '\x0c')
+}
+
+pub fn unescape(text: &str) -> String {
+    let mut value = String::new();
+    // UTF-16 units of `\uXXXX` escapes, as characters beyond the BMP take two
+    let mut units: Vec<u16> = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let escaped = if c == '\\' { chars.next() } else { None };
+        if escaped == Some('u') {
+            let code: String = chars.by_ref().take(4).collect();
+            if let Ok(unit) = u16::from_str_radix(&code, 16) {
+                units.push(unit);
+                continue;
+            }
+        }
+        if !units.is_empty() {
+            value.push_str(&String::from_utf16_lossy(&units));
+            units.clear();
+        }
+        match (c, escaped) {
+            ('\\', Some('t')) => value.push('\t'),
+            ('\\', Some('n')) => value.push('\n'),
+            ('\\', Some('r')) => value.push('\r'),
+            ('\\', Some('f')) => value.push('\x0c'),
+            ('\\', Some(other)) => value.push(other),
+            ('\\', None) => {}
+            (c, _) => value.push(c),
+        }
+    }
+    value.push_str(&String::from_utf16_lossy(&units));
+    value
+}
+
+impl PropertiesFile {
+    fn escape(&self, text: &str, is_key: bool) -> String {
+        let mut escaped = String::new();
+        for (index, c) in text.chars().enumerate() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\t' => escaped.push_str("\\t"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\x0c' => escaped.push_str("\\f"),
+                ' ' if is_key || index == 0 => escaped.push_str("\\ "),
+                '=' | ':' if is_key => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '#' | '!' if is_key && index == 0 => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                c if c.is_control()
+                    || (!c.is_ascii() && (self.escape_unicode || c as u32 > 0xff)) =>
+                {
+                    let mut units = [0; 2];
+                    for unit in c.encode_utf16(&mut units) {
+                        escaped.push_str(&format!("\\u{:04X}", unit));
+                    }
+                }
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self, io::Error> {
+        let (encoding, content) = match String::from_utf8(bytes.to_vec()) {
+            Ok(content) => (Encoding::Utf8, content),
+            Err(_) => (Encoding::Latin1, bytes.iter().map(|b| *b as char).collect()),
+        };
+        let content = content.trim_start_matches('\u{feff}');
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        Ok(PropertiesFile {
+            entries: parse_entries(&lines),
+            newline,
+            encoding,
+            escape_unicode: content.is_ascii(),
+            lines,
+        })
+    }
+
+    pub fn empty() -> Self {
+        PropertiesFile {
+            lines: Vec::new(),
+            newline: "\n",
+            encoding: Encoding::Utf8,
+            escape_unicode: true,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let bytes = fs::read(path)?;
+        Self::parse(&bytes)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut content = self.lines.join(self.newline);
+        if !self.lines.is_empty() {
+            content.push_str(self.newline);
+        }
+        match self.encoding {
+            Encoding::Utf8 => content.into_bytes(),
+            // Characters beyond Latin-1 are escaped when written
+            Encoding::Latin1 => content.chars().map(|c| c as u32 as u8).collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+
+    fn splice(&mut self, range: Range<usize>, lines: Vec<String>) {
+        self.lines.splice(range, lines);
+        self.entries = parse_entries(&self.lines);
+    }
+
+    /// Replaces the value of `key`, or appends a pair when the file has no such key.
+    pub fn set(&mut self, key: &str, value: &str) {
+        if let Some(entry) = self.entries.iter().find(|entry| entry.key == key) {
+            let line = format!(
+                "{}{}{}",
+                self.escape(key, true),
+                entry.separator,
+                self.escape(value, false)
+            );
+            let range = entry.lines.clone();
+            self.splice(range, vec![line]);
+            return;
+        }
+        let separator = self
+            .entries
+            .first()
+            .map_or("=".to_string(), |entry| entry.separator.clone());
+        let line = format!(
+            "{}{}{}",
+            self.escape(key, true),
+            separator,
+            self.escape(value, false)
+        );
+        let end = self.lines.len();
+        self.splice(end..end, vec![line]);
+    }
+
+    /// Removes the pair of `key` with the comment right above it.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(entry) = self.entries.iter().find(|entry| entry.key == key) else {
+            return false;
+        };
+        let mut range = entry.span.clone();
+        // Don't leave two blank lines where the pair was
+        let blank_before = range.start == 0 || is_blank(&self.lines[range.start - 1]);
+        if blank_before && self.lines.get(range.end).is_some_and(|line| is_blank(line)) {
+            range.end += 1;
+        }
+        self.splice(range, Vec::new());
+        true
+    }
+}
+
+/// Splits a logical line into the escaped key, the separator and the escaped value.
+fn split_pair(text: &str) -> (&str, &str, &str) {
+    let mut key_end = text.len();
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if is_separator(c) {
+            key_end = index;
+            break;
+        }
+    }
+    let rest = &text[key_end..];
+    let after_whitespace = rest.trim_start_matches([' ', '\t', '\x0c']);
+    let after_separator = after_whitespace
+        .strip_prefix(['=', ':'])
+        .unwrap_or(after_whitespace)
+        .trim_start_matches([' ', '\t', '\x0c']);
+    let separator_length = rest.len() - after_separator.len();
+    (&text[..key_end], &rest[..separator_length], after_separator)
+}
+
+fn parse_entries(lines: &[String]) -> Vec<PropertiesEntry> {
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if is_blank(&lines[index]) || is_comment(&lines[index]) {
+            index += 1;
+            continue;
+        }
+        let mut start = index;
+        while start > 0 && is_comment(&lines[start - 1]) {
+            start -= 1;
+        }
+        // Join continued lines, dropping the backslash and the indentation that follows
+        let mut text = lines[index].trim_start().to_string();
+        let mut end = index + 1;
+        while continues(&text) && end < lines.len() {
+            text.pop();
+            text.push_str(lines[end].trim_start());
+            end += 1;
+        }
+        if continues(&text) {
+            text.pop();
+        }
+        let (key, separator, value) = split_pair(&text);
+        entries.push(PropertiesEntry {
+            key: unescape(key),
+            value: unescape(value),
+            span: start..end,
+            lines: index..end,
+            separator: separator.to_string(),
+        });
+        index = end;
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Dialog texts\n\n# Title of the dialog\ndialog.title = Caf\\u00e9 \\u0026 more\nwelcome.message = Hello,\\\n    {0}!\npath\\ key:C:\\\\temp\\n\n! legacy\nlegacy=old\n";
+
+    #[test]
+    fn escapes_and_continuations_are_read() {
+        let file = PropertiesFile::parse(CONTENT.as_bytes()).unwrap();
+        let pairs: Vec<(&str, &str)> = file
+            .entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("dialog.title", "Café & more"),
+                ("welcome.message", "Hello,{0}!"),
+                ("path key", "C:\\temp\n"),
+                ("legacy", "old"),
+            ],
+            pairs
+        );
+        assert_eq!("😀", unescape("\\uD83D\\uDE00"));
+    }
+
+    #[test]
+    fn edits_keep_comments_and_escape_values() {
+        let mut file = PropertiesFile::parse(CONTENT.as_bytes()).unwrap();
+        file.set("dialog.title", "Tschüss");
+        file.set("welcome.message", "Hallo {0}");
+        file.set("new key", " padded");
+        assert!(file.remove("legacy"));
+        assert!(!file.remove("legacy"));
+        assert_eq!(
+            "# Dialog texts\n\n# Title of the dialog\ndialog.title = Tsch\\u00FCss\nwelcome.message = Hallo {0}\npath\\ key:C:\\\\temp\\n\nnew\\ key = \\ padded\n",
+            String::from_utf8(file.to_bytes()).unwrap()
+        );
+
+        let latin1: Vec<u8> = b"greeting=Gr\xfc\xdfe\n".to_vec();
+        let mut file = PropertiesFile::parse(&latin1).unwrap();
+        assert_eq!(Some("Grüße"), file.get("greeting"));
+        file.set("greeting", "Grüße ✓");
+        assert_eq!(b"greeting=Gr\xfc\xdfe \\u2713\n".to_vec(), file.to_bytes());
+    }
+}
@@ -0,0 +1,216 @@
+pub mod file;
+
+use crate::locale::split_language_suffix;
+use file::PropertiesFile;
+use local_storage::stores::translation_store::TranslationEntry;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tag the location is stored with, which picks its translation format.
+pub const TAG: &str = "PROPERTIES";
+
+/// Language of the base file without a suffix, which `ResourceBundle` falls back to.
+pub const DEFAULT_LANGUAGE: &str = "default";
+
+/// A `<base>.properties` or `<base>_<language>.properties` file of a location directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleFile {
+    pub base: String,
+    pub language: String,
+    pub path: PathBuf,
+}
+
+fn bundle_file(path: PathBuf) -> Option<BundleFile> {
+    let stem = path.file_stem()?.to_str()?;
+    let (base, language) = split_language_suffix(stem).unwrap_or((stem, DEFAULT_LANGUAGE));
+    Some(BundleFile {
+        base: base.to_string(),
+        language: language.to_string(),
+        path: path.clone(),
+    })
+}
+
+/// Files of every bundle in the directory, such as `messages.properties` and
+/// `messages_de.properties`.
+pub fn bundle_files(directory: &Path) -> Vec<BundleFile> {
+    let mut files: Vec<BundleFile> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "properties"))
+        .filter_map(bundle_file)
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+pub fn is_location(path: &Path) -> bool {
+    path.is_dir() && !bundle_files(path).is_empty()
+}
+
+pub fn get_languages(path: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = bundle_files(path)
+        .into_iter()
+        .map(|file| file.language)
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// The base file when there is one, as the other files translate it. Otherwise the default
+/// language of the settings, its primary subtag or English, whichever has a file.
+pub fn source_language(path: &Path, default_language: &str) -> String {
+    let languages = get_languages(path);
+    let underscored = default_language.replace('-', "_");
+    let primary = default_language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default();
+    let language = [
+        DEFAULT_LANGUAGE,
+        default_language,
+        &underscored,
+        primary,
+        "en",
+    ]
+    .into_iter()
+    .find(|candidate| languages.iter().any(|language| language == candidate))
+    .or(languages.first().map(String::as_str))
+    .unwrap_or(default_language)
+    .to_string();
+    language
+}
+
+/// Entries of every bundle in the order keys first appear. Languages without a value for a
+/// key get an empty translation.
+pub fn get_translations(path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    let languages = get_languages(path);
+    let mut entries: Vec<TranslationEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for file in bundle_files(path) {
+        for entry in PropertiesFile::read(&file.path)?.entries {
+            let position = *positions.entry(entry.key.clone()).or_insert_with(|| {
+                entries.push(TranslationEntry {
+                    key: entry.key.clone(),
+                    value: entry.key.clone(),
+                    in_use: true,
+                    ..Default::default()
+                });
+                entries.len() - 1
+            });
+            entries[position]
+                .translations
+                .insert(file.language.clone(), entry.value);
+        }
+    }
+    for entry in &mut entries {
+        for language in &languages {
+            entry.translations.entry(language.clone()).or_default();
+        }
+    }
+    Ok(entries)
+}
+
+/// Base name of the bundle that has `key` in any language.
+fn bundle_with_key(path: &Path, key: &str) -> Result<Option<String>, io::Error> {
+    for file in bundle_files(path) {
+        if PropertiesFile::read(&file.path)?.get(key).is_some() {
+            return Ok(Some(file.base));
+        }
+    }
+    Ok(None)
+}
+
+pub fn contains_key(path: &Path, key: &str) -> Result<bool, io::Error> {
+    Ok(bundle_with_key(path, key)?.is_some())
+}
+
+fn file_name(base: &str, language: &str) -> String {
+    if language == DEFAULT_LANGUAGE {
+        format!("{}.properties", base)
+    } else {
+        format!("{}_{}.properties", base, language)
+    }
+}
+
+/// Sets the value of `key` in `language`, in the bundle that has the key in some language.
+/// New keys go to the first bundle, and a missing file is created.
+pub fn set_value(path: &Path, language: &str, key: &str, value: &str) -> Result<(), io::Error> {
+    let base = match bundle_with_key(path, key)? {
+        Some(base) => base,
+        None => bundle_files(path)
+            .into_iter()
+            .next()
+            .map(|file| file.base)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} has no .properties files", path.display()),
+                )
+            })?,
+    };
+    let file = path.join(file_name(&base, language));
+    let mut properties = if file.is_file() {
+        PropertiesFile::read(&file)?
+    } else {
+        PropertiesFile::empty()
+    };
+    properties.set(key, value);
+    properties.write(&file)
+}
+
+/// Removes keys with their comments from every file.
+pub fn remove_keys(path: &Path, keys: &[String]) -> Result<(), io::Error> {
+    for file in bundle_files(path) {
+        let mut properties = PropertiesFile::read(&file.path)?;
+        let mut removed = false;
+        for key in keys {
+            removed |= properties.remove(key);
+        }
+        if removed {
+            properties.write(&file.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates an empty file of `language` for every bundle, written with underscores as in
+/// `messages_pt_BR.properties`.
+pub fn add_language(path: &Path, language: &str) -> Result<(), io::Error> {
+    let language = &language.replace('-', "_");
+    if get_languages(path)
+        .iter()
+        .any(|existing| existing == language)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has files for {}", path.display(), language),
+        ));
+    }
+    let mut bases: Vec<String> = bundle_files(path)
+        .into_iter()
+        .map(|file| file.base)
+        .collect();
+    bases.sort();
+    bases.dedup();
+    if let Some(base) = bases.iter().find(|base| {
+        split_language_suffix(&format!("{}_{}", base, language))
+            != Some((base.as_str(), language.as_str()))
+    }) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} is not a language code {} files can have",
+                language, base
+            ),
+        ));
+    }
+    for base in bases {
+        PropertiesFile::empty().write(&path.join(file_name(&base, language)))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,759 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const DEFAULT_INDENT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarStyle {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    /// A `|` or `>` block
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Scalar(String, ScalarStyle),
+    Mapping(Vec<Node>),
+    /// Sequences, flow collections, aliases and tagged values, which are kept as written
+    Other,
+}
+
+/// A `key: value` pair and the lines it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    key: String,
+    indent: usize,
+    line: usize,
+    /// One past the last line of the value
+    end: usize,
+    /// Comment after a scalar on the key line, including the `#`
+    comment: Option<String>,
+    value: Value,
+}
+
+/// A YAML file of nested mappings with string values, as used for locale files. Edits replace
+/// only the lines of the touched keys, so comments, blank lines and key order are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlDocument {
+    lines: Vec<String>,
+    bom: bool,
+    newline: &'static str,
+    roots: Vec<Node>,
+}
+
+fn invalid(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line + 1, message),
+    )
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+/// Document markers and directives carry no keys.
+fn is_marker(line: &str) -> bool {
+    line.starts_with("---") || line.starts_with("...") || line.starts_with('%')
+}
+
+/// Splits `key: rest` into the unquoted key and the text after the colon.
+fn split_key(text: &str, line: usize) -> Result<(String, &str), io::Error> {
+    let (key, after_key) = match text.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let (key, length) = parse_quoted(&text[1..], quote)
+                .ok_or_else(|| invalid(line, "unterminated quoted key"))?;
+            (key, &text[1 + length..])
+        }
+        Some('?') => return Err(invalid(line, "complex keys are not supported")),
+        _ => {
+            let colon = text
+                .match_indices(':')
+                .map(|(index, _)| index)
+                .find(|index| {
+                    text[index + 1..].is_empty() || text[index + 1..].starts_with([' ', '\t'])
+                })
+                .ok_or_else(|| invalid(line, "expected `key: value`"))?;
+            (text[..colon].trim_end().to_string(), &text[colon..])
+        }
+    };
+    let after_key = after_key.trim_start();
+    match after_key.strip_prefix(':') {
+        Some(rest) if rest.is_empty() || rest.starts_with([' ', '\t']) => Ok((key, rest.trim())),
+        _ => Err(invalid(line, "expected `:` after the key")),
+    }
+}
+
+/// Reads a quoted scalar whose opening quote was already consumed. Returns the value and the
+/// length of the text it took, including the closing quote.
+fn parse_quoted(text: &str, quote: char) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\'' if quote == '\'' => {
+                if chars.peek().map(|(_, c)| *c) == Some('\'') {
+                    chars.next();
+                    value.push('\'');
+                } else {
+                    return Some((value, index + 1));
+                }
+            }
+            '"' if quote == '"' => return Some((value, index + 1)),
+            '\\' if quote == '"' => {
+                let (_, escaped) = chars.next()?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    '0' => value.push('\0'),
+                    ' ' => value.push(' '),
+                    '/' => value.push('/'),
+                    'x' | 'u' | 'U' => {
+                        let length = match escaped {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let code: String = (0..length)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        value.push(
+                            u32::from_str_radix(&code, 16)
+                                .ok()
+                                .and_then(char::from_u32)?,
+                        );
+                    }
+                    // A line break escaped at the end of a line joins the lines
+                    '\n' => {
+                        while chars.peek().is_some_and(|(_, c)| *c == ' ') {
+                            chars.next();
+                        }
+                    }
+                    c => value.push(c),
+                }
+            }
+            '\n' => {
+                // Line breaks inside quotes fold into a space, empty lines into a line break
+                let trailing = value.trim_end_matches(' ').len();
+                value.truncate(trailing);
+                let mut empty_lines = 0;
+                loop {
+                    while chars.peek().is_some_and(|(_, c)| *c == ' ' || *c == '\t') {
+                        chars.next();
+                    }
+                    if chars.peek().map(|(_, c)| *c) == Some('\n') {
+                        chars.next();
+                        empty_lines += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if empty_lines == 0 {
+                    value.push(' ');
+                } else {
+                    value.push_str(&"\n".repeat(empty_lines));
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Strips a ` # comment` from a plain scalar.
+fn split_comment(text: &str) -> (&str, Option<String>) {
+    match text.find(" #").or(text.find("\t#")) {
+        Some(index) => (
+            text[..index].trim_end(),
+            Some(text[index..].trim().to_string()),
+        ),
+        None if text.starts_with('#') => ("", Some(text.to_string())),
+        None => (text, None),
+    }
+}
+
+/// Content of a `|` or `>` block scalar.
+fn parse_block(
+    header: &str,
+    lines: &[String],
+    line: usize,
+    key_indent: usize,
+) -> Result<String, io::Error> {
+    let (header, _) = split_comment(header);
+    let folded = header.starts_with('>');
+    let chomping = header.chars().find(|c| *c == '-' || *c == '+');
+    let explicit_indent = header
+        .chars()
+        .find_map(|c| c.to_digit(10))
+        .map(|digit| key_indent + digit as usize);
+
+    let content_indent = explicit_indent.or_else(|| {
+        lines
+            .iter()
+            .find(|line| !is_blank(line))
+            .map(|line| indent_of(line))
+    });
+    let Some(content_indent) = content_indent else {
+        return Ok(String::new());
+    };
+    let mut text_lines: Vec<&str> = Vec::new();
+    for (offset, text) in lines.iter().enumerate() {
+        if is_blank(text) {
+            text_lines.push("");
+        } else if indent_of(text) < content_indent {
+            return Err(invalid(
+                line + 1 + offset,
+                "block scalar line is not indented enough",
+            ));
+        } else {
+            text_lines.push(&text[content_indent..]);
+        }
+    }
+    let trailing_empty = text_lines
+        .iter()
+        .rev()
+        .take_while(|text| text.is_empty())
+        .count();
+    let body = &text_lines[..text_lines.len() - trailing_empty];
+
+    let mut value = String::new();
+    for (index, text) in body.iter().enumerate() {
+        if index > 0 {
+            let previous = body[index - 1];
+            let joins = folded
+                && !text.is_empty()
+                && !previous.is_empty()
+                && !text.starts_with(' ')
+                && !previous.starts_with(' ');
+            value.push(if joins { ' ' } else { '\n' });
+        }
+        value.push_str(text);
+    }
+    match chomping {
+        Some('-') => {}
+        Some('+') => value.push_str(&"\n".repeat(trailing_empty + 1)),
+        _ if !body.is_empty() => value.push('\n'),
+        _ => {}
+    }
+    Ok(value)
+}
+
+fn parse_nodes(lines: &[String], start: usize, end: usize) -> Result<Vec<Node>, io::Error> {
+    let mut nodes = Vec::new();
+    let mut mapping_indent = None;
+    let mut index = start;
+    while index < end {
+        let text = &lines[index];
+        if is_blank(text) || is_comment(text) || is_marker(text) {
+            index += 1;
+            continue;
+        }
+        if text.starts_with('\t') {
+            return Err(invalid(index, "tabs are not allowed for indentation"));
+        }
+        let indent = indent_of(text);
+        match mapping_indent {
+            None => mapping_indent = Some(indent),
+            Some(expected) if expected != indent => {
+                return Err(invalid(index, "unexpected indentation"))
+            }
+            _ => {}
+        }
+        let (key, rest) = split_key(&text[indent..], index)?;
+        let is_block = rest.starts_with(['|', '>']);
+
+        // The value covers the following lines that are indented deeper, and a sequence
+        // written at the same indentation as its key. Comments after the value are left to
+        // the lines that follow.
+        let mut last = index;
+        let mut next = index + 1;
+        while next < end {
+            let text = &lines[next];
+            if is_blank(text) {
+                next += 1;
+                continue;
+            }
+            let next_indent = indent_of(text);
+            if is_comment(text) && !is_block {
+                next += 1;
+                continue;
+            }
+            let same_level_sequence = next_indent == indent
+                && rest.is_empty()
+                && (text[indent..].starts_with("- ") || text[indent..] == *"-");
+            if next_indent > indent || same_level_sequence {
+                last = next;
+                next += 1;
+            } else {
+                break;
+            }
+        }
+        let node_end = last + 1;
+        let children = &lines[index + 1..node_end];
+
+        let mut comment = None;
+        let value = match rest.chars().next() {
+            None | Some('#') => {
+                let first_child = children
+                    .iter()
+                    .find(|line| !is_blank(line) && !is_comment(line));
+                match first_child {
+                    Some(child) if child.trim_start().starts_with('-') => Value::Other,
+                    Some(_) => Value::Mapping(parse_nodes(lines, index + 1, node_end)?),
+                    None => {
+                        comment = split_comment(rest).1;
+                        Value::Scalar(String::new(), ScalarStyle::Plain)
+                    }
+                }
+            }
+            Some('|' | '>') => Value::Scalar(
+                parse_block(rest, children, index, indent)?,
+                ScalarStyle::Block,
+            ),
+            Some(quote @ ('"' | '\'')) => {
+                let mut text = rest[1..].to_string();
+                for child in children {
+                    text.push('\n');
+                    text.push_str(child);
+                }
+                let (value, length) = parse_quoted(&text, quote)
+                    .ok_or_else(|| invalid(index, "unterminated quoted value"))?;
+                comment = split_comment(text[length..].trim()).1;
+                let style = if quote == '"' {
+                    ScalarStyle::DoubleQuoted
+                } else {
+                    ScalarStyle::SingleQuoted
+                };
+                Value::Scalar(value, style)
+            }
+            Some('[' | '{' | '&' | '*' | '!') => Value::Other,
+            Some(_) => {
+                let (first, line_comment) = split_comment(rest);
+                comment = line_comment;
+                let mut value = first.to_string();
+                for child in children
+                    .iter()
+                    .filter(|line| !is_blank(line) && !is_comment(line))
+                {
+                    value.push(' ');
+                    value.push_str(child.trim());
+                }
+                let value = if value == "~" || value == "null" {
+                    String::new()
+                } else {
+                    value
+                };
+                Value::Scalar(value, ScalarStyle::Plain)
+            }
+        };
+        nodes.push(Node {
+            key,
+            indent,
+            line: index,
+            end: node_end,
+            comment,
+            value,
+        });
+        index = node_end;
+    }
+    Ok(nodes)
+}
+
+/// Whether a string can be written without quotes and still be read back as that string.
+fn is_plain_safe(value: &str) -> bool {
+    let Some(first) = value.chars().next() else {
+        return false;
+    };
+    let resolves_to_other_type = matches!(
+        value.to_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n" | "null" | "~"
+    ) || value.parse::<f64>().is_ok()
+        || (first.is_ascii_digit()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_digit() || "_:.-".contains(c)));
+    !resolves_to_other_type
+        && !"-?:,[]{}#&*!|>'\"%@`".contains(first)
+        && !first.is_whitespace()
+        && !value.ends_with(char::is_whitespace)
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.ends_with(':')
+        && !value.contains(['\n', '\t', '\r'])
+}
+
+fn double_quote(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+pub fn format_key(key: &str) -> String {
+    if is_plain_safe(key) {
+        key.to_string()
+    } else {
+        single_quote(key)
+    }
+}
+
+/// The text after `key: ` and the lines that follow it, for a key indented by `indent`.
+/// Multi-line values become literal blocks, other values keep the quoting of the value they
+/// replace where that is possible.
+fn format_value(
+    value: &str,
+    style: ScalarStyle,
+    indent: usize,
+    step: usize,
+) -> (String, Vec<String>) {
+    if value.contains('\n') && !value.contains('\r') {
+        let body = value.trim_end_matches('\n');
+        let trailing = value.len() - body.len();
+        let chomping = match trailing {
+            0 => "-",
+            1 => "",
+            _ => "+",
+        };
+        let indicator = if body.starts_with(' ') {
+            step.to_string()
+        } else {
+            String::new()
+        };
+        let padding = " ".repeat(indent + step);
+        let mut lines: Vec<String> = body
+            .split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}", padding, line)
+                }
+            })
+            .collect();
+        lines.extend((1..trailing).map(|_| String::new()));
+        return (format!("|{}{}", indicator, chomping), lines);
+    }
+    let text = match style {
+        ScalarStyle::DoubleQuoted => double_quote(value),
+        ScalarStyle::SingleQuoted if !value.contains(['\n', '\r', '\t']) => single_quote(value),
+        _ if is_plain_safe(value) => value.to_string(),
+        _ if value.chars().any(char::is_control) => double_quote(value),
+        _ => single_quote(value),
+    };
+    (text, Vec::new())
+}
+
+fn find<'a>(nodes: &'a [Node], key: &str) -> Option<&'a Node> {
+    nodes.iter().find(|node| node.key == key)
+}
+
+impl YamlDocument {
+    pub fn parse(content: &str) -> Result<Self, io::Error> {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let bom = content.starts_with('\u{feff}');
+        let lines: Vec<String> = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let roots = parse_nodes(&lines, 0, lines.len())?;
+        Ok(YamlDocument {
+            lines,
+            bom,
+            newline,
+            roots,
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, io::Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_yaml())
+    }
+
+    pub fn to_yaml(&self) -> String {
+        let mut content = if self.bom {
+            String::from('\u{feff}')
+        } else {
+            String::new()
+        };
+        content.push_str(&self.lines.join(self.newline));
+        if !self.lines.is_empty() {
+            content.push_str(self.newline);
+        }
+        content
+    }
+
+    /// Keys at the top of the file.
+    pub fn root_keys(&self) -> Vec<&str> {
+        self.roots.iter().map(|node| node.key.as_str()).collect()
+    }
+
+    /// String values below `scope`, keyed by their path from there joined with dots.
+    pub fn entries(&self, scope: &[&str]) -> Vec<(String, String)> {
+        fn collect(nodes: &[Node], prefix: &str, entries: &mut Vec<(String, String)>) {
+            for node in nodes {
+                let path = if prefix.is_empty() {
+                    node.key.clone()
+                } else {
+                    format!("{}.{}", prefix, node.key)
+                };
+                match &node.value {
+                    Value::Scalar(value, _) => entries.push((path, value.clone())),
+                    Value::Mapping(children) => collect(children, &path, entries),
+                    Value::Other => {}
+                }
+            }
+        }
+        let mut nodes = self.roots.as_slice();
+        for key in scope {
+            match find(nodes, key).map(|node| &node.value) {
+                Some(Value::Mapping(children)) => nodes = children,
+                _ => return Vec::new(),
+            }
+        }
+        let mut entries = Vec::new();
+        collect(nodes, "", &mut entries);
+        entries
+    }
+
+    pub fn get(&self, path: &[&str]) -> Option<&str> {
+        let (last, parents) = path.split_last()?;
+        let mut nodes = self.roots.as_slice();
+        for key in parents {
+            match &find(nodes, key)?.value {
+                Value::Mapping(children) => nodes = children,
+                _ => return None,
+            }
+        }
+        match &find(nodes, last)?.value {
+            Value::Scalar(value, _) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Indentation step of the file, taken from its first nested mapping.
+    fn indent_step(&self) -> usize {
+        fn step(nodes: &[Node]) -> Option<usize> {
+            nodes.iter().find_map(|node| match &node.value {
+                Value::Mapping(children) => {
+                    children.first().map(|child| child.indent - node.indent)
+                }
+                _ => None,
+            })
+        }
+        step(&self.roots)
+            .filter(|step| *step > 0)
+            .unwrap_or(DEFAULT_INDENT)
+    }
+
+    fn splice(&mut self, start: usize, end: usize, lines: Vec<String>) -> Result<(), io::Error> {
+        self.lines.splice(start..end, lines);
+        self.roots = parse_nodes(&self.lines, 0, self.lines.len())?;
+        Ok(())
+    }
+
+    /// Sets the string at `path`, adding the missing scopes after the last key of the deepest
+    /// existing one.
+    pub fn set(&mut self, path: &[&str], value: &str) -> Result<(), io::Error> {
+        let step = self.indent_step();
+        let mut nodes = self.roots.as_slice();
+        let mut parent: Option<&Node> = None;
+        for (depth, key) in path.iter().enumerate() {
+            let Some(node) = find(nodes, key) else {
+                // Add the rest of the path below `parent`
+                let (insert_at, indent) = match parent {
+                    Some(parent) => (
+                        parent.end,
+                        nodes
+                            .first()
+                            .map_or(parent.indent + step, |node| node.indent),
+                    ),
+                    None => (
+                        self.roots.last().map_or(self.lines.len(), |node| node.end),
+                        self.roots.first().map_or(0, |node| node.indent),
+                    ),
+                };
+                let mut lines = Vec::new();
+                for (offset, key) in path[depth..].iter().enumerate() {
+                    let padding = " ".repeat(indent + offset * step);
+                    if depth + offset + 1 < path.len() {
+                        lines.push(format!("{}{}:", padding, format_key(key)));
+                    } else {
+                        let (text, block) =
+                            format_value(value, ScalarStyle::Plain, indent + offset * step, step);
+                        lines.push(format!("{}{}: {}", padding, format_key(key), text));
+                        lines.extend(block);
+                    }
+                }
+                return self.splice(insert_at, insert_at, lines);
+            };
+            let is_last = depth + 1 == path.len();
+            match &node.value {
+                Value::Scalar(_, style) if is_last => {
+                    let (text, block) = format_value(value, *style, node.indent, step);
+                    let mut line =
+                        format!("{}{}: {}", " ".repeat(node.indent), format_key(key), text);
+                    if let (Some(comment), true) = (&node.comment, block.is_empty()) {
+                        line.push(' ');
+                        line.push_str(comment);
+                    }
+                    let mut lines = vec![line];
+                    lines.extend(block);
+                    let (start, end) = (node.line, node.end);
+                    return self.splice(start, end, lines);
+                }
+                // A key without a value can take nested keys
+                Value::Scalar(existing, ScalarStyle::Plain)
+                    if existing.is_empty() && node.end == node.line + 1 =>
+                {
+                    nodes = &[];
+                }
+                Value::Mapping(children) if !is_last => nodes = children,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "`{}` is not a string value that can be set",
+                            path[..=depth].join(".")
+                        ),
+                    ))
+                }
+            }
+            if let Value::Scalar(..) = node.value {
+                // Drop the empty value marker so the nested keys follow the colon
+                let line = format!("{}{}:", " ".repeat(node.indent), format_key(key));
+                let node_line = node.line;
+                self.lines[node_line] = line;
+            }
+            parent = Some(node);
+        }
+        Ok(())
+    }
+
+    /// Removes the value at `path` with the comment lines right above it. Scopes left empty are
+    /// removed as well, down to `keep_depth` levels.
+    pub fn remove(&mut self, path: &[&str], keep_depth: usize) -> Result<bool, io::Error> {
+        let mut nodes = self.roots.as_slice();
+        let mut chain: Vec<&Node> = Vec::new();
+        for key in path {
+            let Some(node) = find(nodes, key) else {
+                return Ok(false);
+            };
+            chain.push(node);
+            nodes = match &node.value {
+                Value::Mapping(children) => children,
+                _ => &[],
+            };
+        }
+        // Remove the highest scope that holds nothing but the removed value
+        let mut target = chain.len() - 1;
+        while target > keep_depth {
+            match &chain[target - 1].value {
+                Value::Mapping(children) if children.len() == 1 => target -= 1,
+                _ => break,
+            }
+        }
+        let node = chain[target];
+        let mut start = node.line;
+        while start > 0
+            && is_comment(&self.lines[start - 1])
+            && indent_of(&self.lines[start - 1]) == node.indent
+        {
+            start -= 1;
+        }
+        let end = node.end;
+        self.splice(start, end, Vec::new())?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Rails locale\nen:\n  # Buttons\n  buttons:\n    save: Save # short\n    cancel: 'Don''t save'\n  greeting: \"Hello\\n%{name}\"\n  long: >\n    Folded\n    text\n  days: [Mon, Tue]\n\n  empty:\n";
+
+    #[test]
+    fn entries_are_dotted_paths_below_the_scope() {
+        let document = YamlDocument::parse(CONTENT).unwrap();
+        assert_eq!(vec!["en"], document.root_keys());
+        assert_eq!(
+            vec![
+                ("buttons.save".to_string(), "Save".to_string()),
+                ("buttons.cancel".to_string(), "Don't save".to_string()),
+                ("greeting".to_string(), "Hello\n%{name}".to_string()),
+                ("long".to_string(), "Folded text\n".to_string()),
+                ("empty".to_string(), "".to_string()),
+            ],
+            document.entries(&["en"])
+        );
+    }
+
+    #[test]
+    fn edits_keep_comments_order_and_quoting() {
+        let mut document = YamlDocument::parse(CONTENT).unwrap();
+        document
+            .set(&["en", "buttons", "save"], "Save now")
+            .unwrap();
+        document.set(&["en", "buttons", "cancel"], "Won't").unwrap();
+        document.set(&["en", "greeting"], "Hi").unwrap();
+        document
+            .set(&["en", "errors", "blank"], "can't be blank")
+            .unwrap();
+        document.set(&["en", "empty", "nested"], "yes").unwrap();
+        document.set(&["en", "long"], "Two\nlines").unwrap();
+        assert!(document.set(&["en", "days"], "x").is_err());
+
+        assert_eq!(
+            "# Rails locale\nen:\n  # Buttons\n  buttons:\n    save: Save now # short\n    cancel: 'Won''t'\n  greeting: \"Hi\"\n  long: |-\n    Two\n    lines\n  days: [Mon, Tue]\n\n  empty:\n    nested: 'yes'\n  errors:\n    blank: can't be blank\n",
+            document.to_yaml()
+        );
+
+        assert!(document.remove(&["en", "buttons", "save"], 1).unwrap());
+        assert!(document.remove(&["en", "buttons", "cancel"], 1).unwrap());
+        assert!(!document.remove(&["en", "buttons", "cancel"], 1).unwrap());
+        assert_eq!(
+            "# Rails locale\nen:\n  greeting: \"Hi\"\n  long: |-\n    Two\n    lines\n  days: [Mon, Tue]\n\n  empty:\n    nested: 'yes'\n  errors:\n    blank: can't be blank\n",
+            document.to_yaml()
+        );
+        let reparsed = YamlDocument::parse(&document.to_yaml()).unwrap();
+        assert_eq!(Some("Two\nlines"), reparsed.get(&["en", "long"]));
+        assert_eq!(Some("yes"), reparsed.get(&["en", "empty", "nested"]));
+    }
+}
@@ -0,0 +1,208 @@
+pub mod document;
+
+use crate::locale::is_language_code;
+use document::{format_key, YamlDocument};
+use local_storage::stores::translation_store::TranslationEntry;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tag the location is stored with, which picks its translation format.
+pub const TAG: &str = "YAML";
+
+const EXTENSIONS: [&str; 2] = ["yml", "yaml"];
+
+/// A locale file of a location directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleFile {
+    pub language: String,
+    pub path: PathBuf,
+}
+
+/// Files named `<language>.yml` or `<domain>.<language>.yml`, as Rails and Symfony keep them.
+pub fn locale_files(directory: &Path) -> Vec<LocaleFile> {
+    let mut files: Vec<LocaleFile> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let extension = path.extension()?.to_str()?;
+            if !EXTENSIONS.contains(&extension) {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?;
+            let language = stem.rsplit('.').next()?;
+            is_language_code(language).then(|| LocaleFile {
+                language: language.to_string(),
+                path: path.clone(),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+pub fn is_location(path: &Path) -> bool {
+    path.is_dir() && !locale_files(path).is_empty()
+}
+
+pub fn get_languages(path: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = locale_files(path)
+        .into_iter()
+        .map(|file| file.language)
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// The language the others are translated from: the default language of the settings, its
+/// primary subtag or English, whichever has a locale file.
+pub fn source_language(path: &Path, default_language: &str) -> String {
+    let languages = get_languages(path);
+    let primary = default_language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default();
+    [default_language, primary, "en"]
+        .into_iter()
+        .find(|candidate| languages.iter().any(|language| language == candidate))
+        .or(languages.first().map(String::as_str))
+        .unwrap_or(default_language)
+        .to_string()
+}
+
+/// Keys of a Rails file sit below the language code, Symfony files start at the root.
+fn scope<'a>(document: &YamlDocument, language: &'a str) -> Vec<&'a str> {
+    let root_keys = document.root_keys();
+    if root_keys.is_empty() || root_keys == [language] {
+        vec![language]
+    } else {
+        Vec::new()
+    }
+}
+
+fn key_path<'a>(scope: &[&'a str], key: &'a str) -> Vec<&'a str> {
+    scope.iter().copied().chain(key.split('.')).collect()
+}
+
+/// Entries keyed by their dotted path, in the order keys first appear. Languages without a
+/// value for a key get an empty translation.
+pub fn get_translations(path: &Path) -> Result<Vec<TranslationEntry>, io::Error> {
+    let languages = get_languages(path);
+    let mut entries: Vec<TranslationEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for file in locale_files(path) {
+        let document = YamlDocument::read(&file.path)?;
+        for (key, value) in document.entries(&scope(&document, &file.language)) {
+            let position = *positions.entry(key.clone()).or_insert_with(|| {
+                entries.push(TranslationEntry {
+                    key: key.clone(),
+                    value: key.clone(),
+                    in_use: true,
+                    ..Default::default()
+                });
+                entries.len() - 1
+            });
+            entries[position]
+                .translations
+                .insert(file.language.clone(), value);
+        }
+    }
+    for entry in &mut entries {
+        for language in &languages {
+            entry.translations.entry(language.clone()).or_default();
+        }
+    }
+    Ok(entries)
+}
+
+/// Whether any locale file already has `key`.
+pub fn contains_key(path: &Path, key: &str) -> Result<bool, io::Error> {
+    for file in locale_files(path) {
+        let document = YamlDocument::read(&file.path)?;
+        if document
+            .get(&key_path(&scope(&document, &file.language), key))
+            .is_some()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn new_file_path(path: &Path, language: &str) -> PathBuf {
+    let extension = locale_files(path)
+        .first()
+        .and_then(|file| file.path.extension()?.to_str().map(str::to_string))
+        .unwrap_or_else(|| EXTENSIONS[0].to_string());
+    path.join(format!("{}.{}", language, extension))
+}
+
+/// Sets the value of `key` in the file of `language` that has it, or else in
+/// `<language>.yml`, which is created when the language has no file yet.
+pub fn set_value(path: &Path, language: &str, key: &str, value: &str) -> Result<(), io::Error> {
+    let files: Vec<LocaleFile> = locale_files(path)
+        .into_iter()
+        .filter(|file| file.language == language)
+        .collect();
+    let mut documents = Vec::new();
+    for file in &files {
+        documents.push((file.path.clone(), YamlDocument::read(&file.path)?));
+    }
+    let existing = documents.iter().position(|(_, document)| {
+        document
+            .get(&key_path(&scope(document, language), key))
+            .is_some()
+    });
+    let preferred = documents
+        .iter()
+        .position(|(file, _)| file.file_stem().and_then(|stem| stem.to_str()) == Some(language));
+    let first = (!documents.is_empty()).then_some(0);
+    let (file, mut document) = match existing.or(preferred).or(first) {
+        Some(index) => documents.swap_remove(index),
+        None => (new_file_path(path, language), YamlDocument::parse("")?),
+    };
+    let scope = scope(&document, language);
+    document
+        .set(&key_path(&scope, key), value)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", file.display(), e)))?;
+    document.write(&file)
+}
+
+pub fn remove_keys(path: &Path, keys: &[String]) -> Result<(), io::Error> {
+    for file in locale_files(path) {
+        let mut document = YamlDocument::read(&file.path)?;
+        let scope = scope(&document, &file.language);
+        let mut removed = false;
+        for key in keys {
+            removed |= document.remove(&key_path(&scope, key), scope.len())?;
+        }
+        if removed {
+            document.write(&file.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates `<language>.yml` with the language as its root key.
+pub fn add_language(path: &Path, language: &str) -> Result<(), io::Error> {
+    if get_languages(path)
+        .iter()
+        .any(|existing| existing == language)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already has a locale file for {}",
+                path.display(),
+                language
+            ),
+        ));
+    }
+    let file = new_file_path(path, language);
+    fs::write(file, format!("{}:\n", format_key(language)))
+}
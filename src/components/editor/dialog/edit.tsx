@@ -28,8 +28,11 @@ export default function EditTranslationDialog({
   translation,
 }: EditTranslationDialogProps) {
   const [translationsJson, setTranslationsJson] = useState("");
+  const [description, setDescription] = useState("");
   const { last_selected_location} = useLocationStore();
   const { languages } = useTranslationStore();
+  // Images and other typed resources can't be edited as text
+  const editable = translation.metadata?.editable !== "false";
 
   useEffect(() => {
     // @ts-expect-error reasons
@@ -42,6 +45,7 @@ export default function EditTranslationDialog({
       }, {});
 
     setTranslationsJson(JSON.stringify(orderedTranslations, null, 2));
+    setDescription(translation.description ?? "");
   }, [translation]);
   const updateMutation = rspc.useMutation("translations.update_keys");
 
@@ -66,6 +70,8 @@ export default function EditTranslationDialog({
       json_key: translation.value!,
       translation_values: newChangedValues,
       ts_key: translation.key!,
+      description:
+        description !== (translation.description ?? "") ? description : null,
     };
     const body: UpdateKeysBody = {
       key,
@@ -116,9 +122,23 @@ export default function EditTranslationDialog({
           </div>
         </div>
 
+        <div className="grid gap-2">
+          <Label htmlFor="comment">Comment for translators</Label>
+          <Input
+            id="comment"
+            value={description}
+            onChange={(e) => setDescription(e.target.value)}
+            placeholder="Where the text is shown, length limits..."
+            autoComplete="off"
+            spellCheck={false}
+          />
+        </div>
+
         <div className="grid h-full w-full gap-2">
           <Label htmlFor="description">
-            Here you can change the translations
+            {editable
+              ? "Here you can change the translations"
+              : `This is a ${translation.metadata?.type ?? translation.metadata?.mimetype} resource and can't be edited as text`}
           </Label>
           <Textarea
             id="description"
@@ -127,6 +147,7 @@ export default function EditTranslationDialog({
             onChange={(e) => setTranslationsJson(e.target.value)}
             className="h-[300px] resize-none"
             spellCheck={false}
+            disabled={!editable}
           />
         </div>
       </CardContent>
@@ -135,7 +156,9 @@ export default function EditTranslationDialog({
           <Button variant="ghost">Cancel</Button>
         </DialogTrigger>
         <DialogTrigger>
-          <Button onClick={update}>Submit</Button>
+          <Button onClick={update} disabled={!editable}>
+            Submit
+          </Button>
         </DialogTrigger>
       </CardFooter>
     </>
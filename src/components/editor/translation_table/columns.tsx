@@ -103,6 +103,18 @@ export const frontend_columns: ColumnDef<TranslationEntry>[] = [
 			const default_language = useSettingsStore(
 				(state) => state.translation_settings.default_language,
 			);
+			// Languages without a value of their own show the one they fall back to
+			const fallback = row.original.fallbacks?.[default_language];
+			if (fallback !== undefined) {
+				return (
+					<div
+						className="text-muted-foreground italic"
+						title={`Falls back to ${fallback}`}
+					>
+						{row.original.effective_values?.[default_language]}
+					</div>
+				);
+			}
 			return (
 				//@ts-expect-error reason
 				<div className="">{row.original.translations[default_language]}</div>
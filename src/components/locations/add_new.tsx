@@ -108,7 +108,7 @@ export default function AddNewLocation(props: props) {
 			filters: [
 				{
 					name: "Translation Definiton Files",
-					extensions: ["ts", "csproj"],
+					extensions: ["ts", "csproj", "sln"],
 				},
 			],
 		});
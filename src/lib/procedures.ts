@@ -3,24 +3,33 @@
 export type Procedures = {
     queries: 
         { key: "hi", input: never, result: string } | 
+        { key: "translations.check_placeholders", input: string, result: PlaceholderIssue[] } | 
         { key: "translations.get_languages", input: string, result: string[] } | 
         { key: "translations.get_number_of_keys", input: string, result: number },
     mutations: 
         { key: "locations.add_location", input: ScanInput, result: Location[] } | 
         { key: "locations.delete_location", input: Location, result: Location } | 
         { key: "locations.rescan_location", input: RescanInput, result: Location } | 
+        { key: "locations.update_location", input: UpdateLocationInput, result: Location } | 
         { key: "stores.getStore", input: string, result: Store } | 
         { key: "stores.removeStore", input: string, result: null } | 
         { key: "stores.setStore", input: Store, result: null } | 
         { key: "translations.add_key", input: AddNewKeyBody, result: TranslationEntry[] } | 
+        { key: "translations.add_language", input: AddLanguageBody, result: null } | 
+        { key: "translations.export_gettext", input: ExportBody, result: string[] } | 
+        { key: "translations.export_spreadsheet", input: SpreadsheetExportBody, result: string } | 
+        { key: "translations.export_xliff", input: XliffExportBody, result: string[] } | 
         { key: "translations.get_translations", input: string, result: TranslationEntry[] } | 
+        { key: "translations.import_gettext", input: ImportBody, result: ImportReport } | 
+        { key: "translations.import_spreadsheet", input: ImportBody, result: ImportReport } | 
+        { key: "translations.import_xliff", input: ImportBody, result: ImportReport } | 
         { key: "translations.remove_keys", input: RemoveTranslationBody, result: null } | 
         { key: "translations.update_keys", input: UpdateKeysBody, result: null },
     subscriptions: 
         { key: "test", input: never, result: string }
 };
 
-export type ScanInput = { path: string; name: string }
+export type ScanInput = { path: string; name: string; layout?: FrontendLayout | null }
 
 export type UpdateKeysBody = { path: string; key: UpdatedKeyValues }
 
@@ -30,7 +39,9 @@ export type LocationStoreState = { last_selected_location?: Location | null; loc
 
 export type Store = SettingsStore | LocationStore | TranslationStore
 
-export type AddNewKeyBody = { path: string; ts_key: string; json_key: string; value: string }
+export type AddLanguageBody = { path: string; language: string }
+
+export type AddNewKeyBody = { path: string; ts_key: string; json_key: string; value: string; plural_values?: { [key: PluralCategory]: string }; description?: string | null; translations?: { [key: string]: string } }
 
 export type TranslationSettings = { translate_new_strings?: boolean; translate_updated_strings?: boolean; default_language?: string; translation_command?: string }
 
@@ -38,7 +49,7 @@ export type Notifications = { file_changes?: boolean; finished_translation?: boo
 
 export type ResizablePanelState = { home_default_sizes?: number[]; home_nav_collapsed?: boolean; home_collapsed_size?: number }
 
-export type UpdatedKeyValues = { ts_key: string; json_key: string; translation_values: { [key: string]: string } }
+export type UpdatedKeyValues = { ts_key: string; json_key: string; translation_values: { [key: string]: string }; plural_values?: { [key: string]: { [key: PluralCategory]: string } }; description?: string | null }
 
 export type SettingsStoreState = { nav_open?: boolean; theme: Theme; notifications_enabled?: boolean; toast_rich_colors?: boolean; enabled_notification_types: Notifications; translation_settings: TranslationSettings; resizable_panel_state: ResizablePanelState }
 
@@ -50,10 +61,42 @@ export type SettingsStore = { state: SettingsStoreState; version: number }
 
 export type RescanInput = { path: string; tag: string }
 
-export type Location = { id: number; tag: string; name: string; path: string; is_favourite: boolean; num_of_keys: number; num_of_untranslated_keys: number; added_at: string }
+export type UpdateLocationInput = { path: string; layout: FrontendLayout }
+
+export type FrontendLayout = { messages_file: string; locales_directory: string; locale_file_pattern: string; languages_file: string; source_language?: string | null; json_layout?: JsonLayout }
+
+export type JsonLayout = "flat" | "nested"
+
+export type PluralCategory = "zero" | "one" | "two" | "few" | "many" | "other"
+
+export type Location = { id: number; tag: string; name: string; path: string; is_favourite: boolean; num_of_keys: number; num_of_untranslated_keys: number; added_at: string; json_layout: string; messages_file: string; locales_directory: string; locale_file_pattern: string; languages_file: string; source_language: string | null }
 
-export type TranslationEntry = { key?: string; value?: string; translations?: { [key: string]: string }; in_use?: boolean }
+export type TranslationEntry = { key?: string; value?: string; translations?: { [key: string]: string }; in_use?: boolean; possibly_used?: boolean; usages?: SourceLocation[]; plurals?: { [key: string]: { [key: string]: string } }; missing_plural_forms?: { [key: string]: string[] }; description?: string | null; metadata?: { [key: string]: string }; effective_values?: { [key: string]: string }; fallbacks?: { [key: string]: string } }
+
+export type SourceLocation = { file: string; line: number; column: number }
 
 export type TranslationStore = { state: TranslationStoreState; version: number }
 
 export type RemoveTranslationBody = { path: string; ts_key: string[]; json_key: string[] }
+
+export type PlaceholderIssue = { key: string; language: string; problem: Problem }
+
+export type Problem = { type: "missing"; placeholder: string } | { type: "extra"; placeholder: string } | { type: "renamed"; from: string; to: string } | { type: "type_changed"; placeholder: string; expected: string | null; found: string | null } | { type: "syntax_error"; message: string; offset: number }
+
+export type ExportBody = { path: string; directory: string }
+
+export type ImportBody = { path: string; file: string; language?: string | null; dry_run?: boolean }
+
+export type ImportReport = { changed: ValueChange[]; unknown: string[]; conflicted: ImportConflict[]; unchanged: number }
+
+export type ValueChange = { key: string; language: string; old_value: string; new_value: string }
+
+export type ImportConflict = { key: string; language: string; reason: ConflictReason }
+
+export type ConflictReason = { type: "source_changed"; exported: string; current: string } | { type: "needs_review" } | { type: "duplicate" } | { type: "plural_forms" }
+
+export type XliffExportBody = { path: string; directory: string; version: XliffVersion; untranslated_only?: boolean }
+
+export type XliffVersion = "1.2" | "2.0"
+
+export type SpreadsheetExportBody = { path: string; file: string }